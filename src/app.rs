@@ -1,13 +1,35 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use arboard::Clipboard;
 use egui::{Color32, Key, Modifiers, RichText, ScrollArea, Ui};
+use regex::{Regex, RegexBuilder};
+use tempfile::TempPath;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::clipboard_backend;
-use crate::history::ClipboardHistory;
-use crate::hotkey::{hotkey_display, start_hotkey_listener};
-use crate::interpreter::{get_interpreters, Interpreter, InterpretItem};
+use crate::classify::ContentKind;
+use crate::clipboard_backend::{self, Backend, ClipboardAccess};
+#[cfg(unix)]
+use crate::control::{self, ControlCommand};
+use crate::detect;
+use crate::event::AppEvent;
+use crate::history::{self, export, import};
+use crate::history::fuzzy::fuzzy_match;
+use crate::history::{
+    ClipboardEntry, ClipboardHistory, ClipboardImage, DisplayZone, HistoryKey, Persist, Source,
+    TimestampStyle,
+};
+use crate::hotkey::{self, start_hotkey_listener, HotkeyConfig};
+use crate::interpreter::cache::InterpretCache;
+use crate::interpreter::{
+    color, get_interpreters, hex, image::interpret_image, InterpretContext, InterpretResult,
+    Interpreter,
+};
+use crate::settings::{self, DoubleClickAction, SecretPolicy, Settings, TriggerResult};
+use crate::watch::{start_clipboard_watcher, WatchHandle};
 use crate::window_state;
 
 /// Touching this file signals the app to capture the clipboard.
@@ -15,17 +37,613 @@ use crate::window_state;
 ///   e.g. bind = CTRL+SHIFT+H, exec, touch /tmp/clipboard-hack-trigger
 const TRIGGER_FILE: &str = "/tmp/clipboard-hack-trigger";
 
+/// Suggested location for the trigger result file, shown as a hint in settings.
+const TRIGGER_RESULT_HINT: &str = "/tmp/clipboard-hack-result";
+
+/// How long the "Undo clear" button stays available after clearing.
+const UNDO_CLEAR_WINDOW: Duration = Duration::from_secs(10);
+
+/// Window geometry is written once it has been stable this long.
+const WINDOW_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// How long the "Last" status chip stays highlighted after a new capture.
+const CAPTURE_FLASH: Duration = Duration::from_millis(1500);
+
+/// Title of the main window. Captures made while it has focus aren't
+/// attributed to it as their source app.
+pub const WINDOW_TITLE: &str = "Clipboard Hack";
+
+/// Longest source app title shown on a history row.
+const ROW_APP_CHARS: usize = 24;
+
+/// Timestamp formats offered in Settings, with their labels.
+const TIMESTAMP_PRESETS: [(&str, &str); 5] = [
+    (history::DEFAULT_TIMESTAMP_FORMAT, "Date and time"),
+    ("%Y-%m-%d %H:%M", "Date and time, no seconds"),
+    ("%H:%M:%S", "Time only"),
+    ("%a %d %b %Y, %H:%M", "Weekday and date"),
+    ("%Y-%m-%dT%H:%M:%S%:z", "RFC 3339"),
+];
+
+const CHIP_OK: Color32 = Color32::from_rgb(120, 200, 120);
+const CHIP_WARN: Color32 = Color32::from_rgb(230, 170, 80);
+const CHIP_ERROR: Color32 = Color32::from_rgb(230, 110, 110);
+
+/// How long a toast stays up, and the final stretch over which it fades out.
+const TOAST_DURATION: Duration = Duration::from_millis(2500);
+const ERROR_TOAST_DURATION: Duration = Duration::from_secs(5);
+const TOAST_FADE: Duration = Duration::from_millis(500);
+/// Older toasts are dropped once this many are on screen.
+const MAX_TOASTS: usize = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> Color32 {
+        match self {
+            ToastKind::Info => Color32::from_rgb(180, 180, 180),
+            ToastKind::Success => CHIP_OK,
+            ToastKind::Error => CHIP_ERROR,
+        }
+    }
+
+    fn duration(self) -> Duration {
+        match self {
+            ToastKind::Error => ERROR_TOAST_DURATION,
+            _ => TOAST_DURATION,
+        }
+    }
+}
+
+/// A transient message in the corner of the detail panel.
+struct Toast {
+    kind: ToastKind,
+    text: String,
+    shown_at: Instant,
+}
+
+/// Which entry the popped-out detail window shows.
+#[derive(Clone, Copy, PartialEq)]
+enum PopoutTarget {
+    /// Whatever is selected in the history list.
+    Selection,
+    /// The entry with this hash, regardless of the selection.
+    Entry(u64),
+}
+
+/// Small rounded "label: value" badge used in the toolbar status area.
+fn status_chip(ui: &mut Ui, label: &str, value: &str, color: Color32) -> egui::Response {
+    egui::Frame::none()
+        .fill(ui.visuals().faint_bg_color)
+        .stroke(egui::Stroke::new(1.0, color.gamma_multiply(0.5)))
+        .rounding(8.0)
+        .inner_margin(egui::Margin::symmetric(6.0, 1.0))
+        .show(ui, |ui| {
+            ui.label(RichText::new(format!("{label}: {value}")).small().color(color));
+        })
+        .response
+}
+
+/// Parse a shortcut like `Ctrl+Shift+W`. Modifier names are case-insensitive;
+/// the key uses egui's key names.
+fn parse_shortcut(s: &str) -> Option<egui::KeyboardShortcut> {
+    let mut modifiers = Modifiers::NONE;
+    let mut key = None;
+    for part in s.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers = modifiers | Modifiers::CTRL,
+            "shift" => modifiers = modifiers | Modifiers::SHIFT,
+            "alt" => modifiers = modifiers | Modifiers::ALT,
+            "cmd" | "command" => modifiers = modifiers | Modifiers::COMMAND,
+            _ => key = Some(Key::from_name(part)?),
+        }
+    }
+    Some(egui::KeyboardShortcut::new(modifiers, key?))
+}
+
+/// One-line, length-capped rendering of a copy action's text for menus, so
+/// long payloads such as pretty-printed JSON don't blow up the menu.
+fn menu_preview(text: &str) -> String {
+    const MAX_CHARS: usize = 48;
+    let first = text.lines().next().unwrap_or("");
+    let preview: String = first.chars().take(MAX_CHARS).collect();
+    if preview.len() < text.len() {
+        format!("{preview}…")
+    } else {
+        preview
+    }
+}
+
+/// Byte, character, grapheme, line and word counts in one line, e.g.
+/// `12 bytes · 11 chars · 10 graphemes · 1 line · 2 words`.
+fn content_stats(content: &str) -> String {
+    let count = |n: usize, unit: &str| {
+        if n == 1 {
+            format!("1 {unit}")
+        } else {
+            format!("{n} {unit}s")
+        }
+    };
+    [
+        count(content.len(), "byte"),
+        count(content.chars().count(), "char"),
+        count(content.graphemes(true).count(), "grapheme"),
+        count(content.lines().count(), "line"),
+        count(content.unicode_words().count(), "word"),
+    ]
+    .join(" · ")
+}
+
+/// Apply the user's accent color (or restore egui's defaults) to both themes.
+fn apply_accent(ctx: &egui::Context, accent: Option<[u8; 4]>) {
+    ctx.all_styles_mut(|style| {
+        let visuals = &mut style.visuals;
+        let defaults = if visuals.dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        let Some(color) = accent_color32(accent) else {
+            visuals.selection = defaults.selection;
+            visuals.hyperlink_color = defaults.hyperlink_color;
+            visuals.widgets.hovered.bg_stroke = defaults.widgets.hovered.bg_stroke;
+            visuals.widgets.active.weak_bg_fill = defaults.widgets.active.weak_bg_fill;
+            return;
+        };
+        visuals.selection.bg_fill = color;
+        visuals.hyperlink_color = color;
+        visuals.widgets.hovered.bg_stroke.color = color;
+        visuals.widgets.active.weak_bg_fill = color;
+    });
+}
+
+fn accent_color32(accent: Option<[u8; 4]>) -> Option<Color32> {
+    accent.map(|[r, g, b, a]| Color32::from_rgba_unmultiplied(r, g, b, a))
+}
+
+/// Leading content-type icon for a history row; colors get a real swatch.
+fn draw_kind_icon(ui: &mut Ui, kind: ContentKind, size: egui::Vec2) {
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    match kind {
+        ContentKind::Color([r, g, b, a]) => {
+            let swatch = egui::Rect::from_center_size(rect.center(), egui::vec2(12.0, 12.0));
+            ui.painter()
+                .rect_filled(swatch, 2.0, Color32::from_rgba_unmultiplied(r, g, b, a));
+            ui.painter()
+                .rect_stroke(swatch, 2.0, egui::Stroke::new(1.0, Color32::GRAY));
+        }
+        _ => {
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                kind.icon(),
+                egui::TextStyle::Small.resolve(ui.style()),
+                Color32::GRAY,
+            );
+        }
+    }
+    response.on_hover_text(kind.label());
+}
+
+/// Native file dialog for import and export, starting in the home directory.
+fn history_file_dialog(title: &str) -> rfd::FileDialog {
+    let dialog = rfd::FileDialog::new().set_title(title).add_filter("JSON", &["json"]);
+    match std::env::var("HOME") {
+        Ok(home) => dialog.set_directory(home),
+        Err(_) => dialog,
+    }
+}
+
+fn pick_export_file() -> Option<PathBuf> {
+    history_file_dialog("Export history")
+        .set_file_name("clipboard-history.json")
+        .add_filter("CSV", &["csv"])
+        .add_filter("Plain text", &["txt"])
+        .save_file()
+}
+
+fn pick_import_file() -> Option<PathBuf> {
+    history_file_dialog("Import history")
+        .add_filter("Plain text", &["txt"])
+        .add_filter("All files", &["*"])
+        .pick_file()
+}
+
+/// How the history search box filters and highlights entries.
+enum SearchFilter {
+    All,
+    Fuzzy(String),
+    Regex(Regex),
+}
+
+impl SearchFilter {
+    /// Sort score for an entry, or `None` if the filter hides it.
+    fn score(&self, content: &str) -> Option<i32> {
+        match self {
+            SearchFilter::All => Some(0),
+            SearchFilter::Fuzzy(query) => fuzzy_match(query, content).map(|m| m.score),
+            SearchFilter::Regex(re) => re.is_match(content).then_some(0),
+        }
+    }
+
+    /// Character indices of `text` to highlight.
+    fn positions(&self, text: &str) -> Vec<usize> {
+        match self {
+            SearchFilter::All => Vec::new(),
+            SearchFilter::Fuzzy(query) => {
+                fuzzy_match(query, text).map(|m| m.positions).unwrap_or_default()
+            }
+            SearchFilter::Regex(re) => re
+                .find_iter(text)
+                .flat_map(|m| {
+                    let start = text[..m.start()].chars().count();
+                    start..start + m.as_str().chars().count()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Two-line history row label. Characters of `preview` matched by the
+/// search query are drawn in `highlight`.
+fn row_label(
+    ts: &str,
+    preview: &str,
+    filter: &SearchFilter,
+    font_id: &egui::FontId,
+    highlight: Color32,
+) -> egui::text::LayoutJob {
+    let plain = egui::TextFormat::simple(font_id.clone(), Color32::PLACEHOLDER);
+    let matched = egui::TextFormat::simple(font_id.clone(), highlight);
+    let mut job = egui::text::LayoutJob::default();
+    job.append(ts, 0.0, plain.clone());
+    job.append("\n", 0.0, plain.clone());
+    let positions = filter.positions(preview);
+    let mut buf = [0u8; 4];
+    for (i, c) in preview.chars().enumerate() {
+        let format = if positions.contains(&i) { &matched } else { &plain };
+        job.append(c.encode_utf8(&mut buf), 0.0, format.clone());
+    }
+    job
+}
+
+/// `text` in the monospace font with line `i` drawn in `colors[i]`. Lines
+/// without a color use the default text color.
+fn colored_lines(ui: &Ui, text: &str, colors: &[Option<[u8; 4]>]) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let default = ui.visuals().text_color();
+    let mut job = egui::text::LayoutJob::default();
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            job.append("\n", 0.0, egui::TextFormat::simple(font_id.clone(), default));
+        }
+        let color = match colors.get(i).copied().flatten() {
+            Some([r, g, b, a]) => Color32::from_rgba_unmultiplied(r, g, b, a),
+            None => default,
+        };
+        job.append(line, 0.0, egui::TextFormat::simple(font_id.clone(), color));
+    }
+    job
+}
+
+/// Indices into `interpreters` in display order: the names in `saved` first,
+/// in that order, then the rest in `get_interpreters()` order.
+fn interpreter_order(interpreters: &[Box<dyn Interpreter>], saved: &[String]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(interpreters.len());
+    let saved_indices =
+        saved.iter().filter_map(|name| interpreters.iter().position(|i| i.name() == name));
+    for i in saved_indices.chain(0..interpreters.len()) {
+        if !order.contains(&i) {
+            order.push(i);
+        }
+    }
+    order
+}
+
+/// Hover details for a history row: a longer excerpt, the timestamp, size,
+/// and which interpreters recognise the content.
+fn draw_entry_tooltip(
+    ui: &mut Ui,
+    entry: &ClipboardEntry,
+    interpreters: &[Box<dyn Interpreter>],
+    disabled: &[String],
+    style: &TimestampStyle,
+) {
+    let content = entry.content();
+    // Hex Dump and the code point listing apply to everything, so they say
+    // nothing about the content type.
+    let generic = ["Hex Dump", "Unicode Code Points"];
+    let matches: Vec<&str> = interpreters
+        .iter()
+        .map(|interp| interp.name())
+        .zip(interpreters)
+        .filter(|(name, _)| !generic.contains(name) && !disabled.iter().any(|d| d == name))
+        .filter(|(_, interp)| interp.applies(content))
+        .map(|(name, _)| name)
+        .collect();
+
+    ui.set_max_width(420.0);
+    ui.label(RichText::new(entry.excerpt(600, 12)).monospace());
+    ui.separator();
+    egui::Grid::new("entry_tooltip").num_columns(2).show(ui, |ui| {
+        ui.label(RichText::new("Captured").color(Color32::GRAY));
+        ui.label(entry.timestamp_str(style));
+        ui.end_row();
+        ui.label(RichText::new("Source").color(Color32::GRAY));
+        ui.label(entry.source().label());
+        ui.end_row();
+        if let Some(app) = entry.source_app() {
+            ui.label(RichText::new("App").color(Color32::GRAY));
+            ui.label(app);
+            ui.end_row();
+        }
+        if let Some(note) = entry.note() {
+            ui.label(RichText::new("Note").color(Color32::GRAY));
+            ui.label(note);
+            ui.end_row();
+        }
+        if !entry.tags().is_empty() {
+            ui.label(RichText::new("Tags").color(Color32::GRAY));
+            ui.label(entry.tags().join(", "));
+            ui.end_row();
+        }
+        ui.label(RichText::new("Size").color(Color32::GRAY));
+        ui.label(format!(
+            "{} bytes, {} chars, {} lines",
+            content.len(),
+            content.chars().count(),
+            content.lines().count()
+        ));
+        ui.end_row();
+        ui.label(RichText::new("Looks like").color(Color32::GRAY));
+        ui.label(if matches.is_empty() { "plain text".to_string() } else { matches.join(", ") });
+        ui.end_row();
+    });
+}
+
+/// Tiny line chart of `values`, scaled to fit between their min and max.
+fn draw_sparkline(ui: &mut Ui, values: &[f64]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(160.0, 24.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    if values.len() < 2 {
+        return;
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = if max > min { max - min } else { 1.0 };
+    // More points than horizontal pixels would just overdraw; sample evenly.
+    let n = values.len().min(rect.width() as usize);
+    let points: Vec<egui::Pos2> = (0..n)
+        .map(|i| {
+            let v = values[i * (values.len() - 1) / (n - 1).max(1)];
+            let x = rect.left() + rect.width() * i as f32 / (n - 1).max(1) as f32;
+            let y = rect.bottom() - 2.0 - (rect.height() - 4.0) * ((v - min) / span) as f32;
+            egui::pos2(x, y)
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, ui.visuals().selection.bg_fill),
+    ));
+}
+
+/// Write `content` to a private (0600, randomly named) temp file and open it
+/// for editing.
+///
+/// When the app was started from a terminal, `$VISUAL` runs in it; the value
+/// is split like a shell command, so `code --wait` works too. The file is
+/// removed once the editor exits. Otherwise the desktop's default text editor
+/// opens it. There's no telling when that one is done, so the file is handed
+/// back and must be kept until the app quits.
+fn open_in_editor(content: &str) -> Result<(PathBuf, Option<TempPath>), String> {
+    use std::io::{IsTerminal as _, Write as _};
+
+    let mut file = tempfile::Builder::new()
+        .prefix("clipboard-hack-")
+        .suffix(".txt")
+        .tempfile()
+        .map_err(|e| e.to_string())?;
+    file.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    let temp = file.into_temp_path();
+    let path = temp.to_path_buf();
+
+    let visual = std::env::var("VISUAL").unwrap_or_default();
+    if !visual.trim().is_empty() && std::io::stdin().is_terminal() {
+        let argv = shlex::split(&visual)
+            .filter(|argv| !argv.is_empty())
+            .ok_or_else(|| format!("$VISUAL isn't a valid command: {visual}"))?;
+        let mut child = std::process::Command::new(&argv[0])
+            .args(&argv[1..])
+            .arg(&path)
+            .spawn()
+            .map_err(|e| format!("{}: {e}", argv[0]))?;
+        std::thread::spawn(move || {
+            let _ = child.wait();
+            drop(temp);
+        });
+        return Ok((path, None));
+    }
+
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = std::process::Command::new("open");
+        cmd.arg("-t");
+        cmd
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    cmd.arg(&path).spawn().map_err(|e| e.to_string())?;
+    Ok((path, Some(temp)))
+}
+
+/// 256 bars, one per byte value. Printable ASCII is drawn in the accent
+/// color so text, base64 and binary blobs look visibly different.
+fn draw_byte_histogram(ui: &mut Ui, counts: &[u32; 256]) {
+    let width = ui.available_width().min(512.0);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, 64.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_w = rect.width() / 256.0;
+    let text_color = ui.visuals().selection.bg_fill;
+    for (b, &count) in counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let h = (rect.height() - 2.0) * count as f32 / max;
+        let x = rect.left() + b as f32 * bar_w;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(x, rect.bottom() - h),
+            egui::pos2(x + bar_w.max(1.0), rect.bottom()),
+        );
+        let color = if (0x20..0x7f).contains(&b) { text_color } else { Color32::GRAY };
+        painter.rect_filled(bar, 0.0, color);
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let b = (((pos.x - rect.left()) / bar_w) as usize).min(255);
+        response.on_hover_text(format!("0x{b:02x}: {}", counts[b]));
+    }
+    ui.label(
+        RichText::new(format!(
+            "Entropy: {:.2} bits/byte  ·  {} distinct byte values",
+            hex::entropy(counts),
+            counts.iter().filter(|&&c| c > 0).count()
+        ))
+        .color(Color32::GRAY)
+        .small(),
+    );
+}
+
 pub struct App {
     history: ClipboardHistory,
+    /// The history file is encrypted and hasn't been unlocked yet; saving is
+    /// suspended so the file isn't overwritten.
+    history_locked: bool,
+    /// Passphrase typed into the unlock prompt or the encryption settings.
+    passphrase_input: String,
+    /// Encrypted history being decrypted on a background thread.
+    unlocking: Option<Receiver<Result<ClipboardHistory, String>>>,
+    /// Key for a new passphrase being derived on a background thread.
+    deriving_key: Option<Receiver<Result<HistoryKey, String>>>,
+    /// "Start a new history" was clicked once and awaits confirmation.
+    confirm_start_over: bool,
     history_path: PathBuf,
     selected_index: Option<usize>,
-    rx: Receiver<()>,
-    clipboard: Option<Clipboard>,
-    interpreters: Vec<Box<dyn Interpreter>>,
+    /// Rows picked with Ctrl/Shift-click for "Delete selected". Cleared
+    /// whenever new entries shift the indices.
+    multi_selection: HashSet<usize>,
+    /// Row the last click landed on; Shift-click selects from here.
+    selection_anchor: Option<usize>,
+    rx: Receiver<AppEvent>,
+    clipboard: Box<dyn ClipboardAccess>,
+    interpreters: Arc<Vec<Box<dyn Interpreter>>>,
+    /// Detail panel results for the selected entry, reused across repaints.
+    interpret_cache: InterpretCache,
+    /// Result of the most recent user-visible action; set with `push_toast`.
     status_message: String,
+    /// Messages currently shown over the detail panel, oldest first.
+    toasts: Vec<Toast>,
+    /// The detail view is shown in its own window instead of the central panel.
+    popout: Option<PopoutTarget>,
+    /// Built from the timestamp settings.
+    timestamp_style: TimestampStyle,
+    /// Backend that served the last read (or the expected one before any read).
+    backend: Option<Backend>,
+    hotkey_active: Arc<AtomicBool>,
+    /// Trigger the desktop assigned when the hotkey was registered through
+    /// the GlobalShortcuts portal (Wayland).
+    hotkey_portal: Option<String>,
     trigger_path: PathBuf,
     window_state_path: PathBuf,
     last_outer_rect: Option<egui::Rect>,
+    /// Latest maximized/fullscreen flags reported by the viewport.
+    maximized: bool,
+    fullscreen: bool,
+    /// What `window_state_path` currently holds, to skip redundant writes.
+    saved_window_state: window_state::WindowState,
+    /// Geometry that differs from the saved one, and when it was first seen.
+    pending_window_state: Option<(window_state::WindowState, Instant)>,
+    /// When the last capture that added an entry happened, for the status flash.
+    capture_flash: Option<Instant>,
+    /// Scroll the history list to the new top entry on the next frame.
+    scroll_to_new: bool,
+    /// Scroll offset of the history list as of the last frame.
+    history_scroll: f32,
+    /// Offset from the previous session, applied when the list is next drawn.
+    restore_scroll: Option<f32>,
+    /// Request user attention on the next frame if the window is unfocused.
+    attention_pending: bool,
+    settings: Settings,
+    settings_path: PathBuf,
+    show_info: bool,
+    show_export: bool,
+    /// Export destination that already exists, waiting for the user to
+    /// confirm replacing it.
+    export_overwrite: Option<PathBuf>,
+    show_onboarding: bool,
+    show_settings: bool,
+    /// Hash of the entry whose truncated output the user chose to expand.
+    expanded_entry: Option<u64>,
+    line_selection: Option<LineSelection>,
+    /// Byte histogram of the selected entry, keyed by its hash.
+    histogram_cache: Option<(u64, [u32; 256])>,
+    /// `detect_kind` verdict for the selected entry, keyed by its hash.
+    kind_cache: Option<(u64, &'static str)>,
+    /// `content_stats` line for the selected entry, keyed by its hash.
+    stats_cache: Option<(u64, String)>,
+    /// Uploaded textures of image entries, keyed by entry hash.
+    textures: HashMap<u64, egui::TextureHandle>,
+    /// Entries removed by the last "Clear History", kept briefly for undo.
+    cleared: Option<(VecDeque<ClipboardEntry>, Instant)>,
+    /// Text typed into the accent color field in Settings.
+    accent_input: String,
+    /// Last input event or capture, for the idle auto-clear.
+    last_activity: Instant,
+    /// Background clipboard poller behind watch mode.
+    watcher: Arc<WatchHandle>,
+    /// History panel filter; fuzzy match on content, or a regex in regex mode.
+    search_query: String,
+    /// Last compiled regex search: (pattern, ignore case, regex or error).
+    compiled_search: Option<(String, bool, Result<Regex, String>)>,
+    /// Tags selected in the history panel's tag bar; only entries carrying
+    /// them are listed.
+    tag_filter: Vec<String>,
+    /// Whether an entry needs every tag in `tag_filter` or just one of them.
+    tag_match_all: bool,
+    /// Text typed into the tag field, with the hash of the entry it's for.
+    tag_input: Option<(u64, String)>,
+    hotkey: HotkeyConfig,
+    hotkey_path: PathBuf,
+    /// Settings is waiting for the next key press to become the hotkey.
+    recording_hotkey: bool,
+    /// "Split into entries" trims whitespace around each line.
+    split_trim: bool,
+    /// Temp files opened in the desktop editor, deleted on exit.
+    editor_files: Vec<TempPath>,
+    /// The bound control socket, removed on exit.
+    control_socket: Option<PathBuf>,
+}
+
+/// Line range picked in the detail view's content pane, tied to one entry.
+#[derive(Clone, Copy)]
+struct LineSelection {
+    entry: u64,
+    anchor: usize,
+    cursor: usize,
+}
+
+impl LineSelection {
+    fn range(&self) -> std::ops::RangeInclusive<usize> {
+        self.anchor.min(self.cursor)..=self.anchor.max(self.cursor)
+    }
 }
 
 /// Search common system font paths for a file that supports Japanese,
@@ -75,235 +693,2326 @@ fn setup_japanese_font(ctx: &egui::Context) {
             return;
         }
     }
-    eprintln!("[font] No Japanese font found; CJK characters may not render.");
-}
+    eprintln!("[font] No Japanese font found; CJK characters may not render.");
+}
+
+/// Files the app reads and writes.
+struct AppPaths {
+    settings: PathBuf,
+    history: PathBuf,
+    window_state: PathBuf,
+    hotkey: PathBuf,
+    trigger: PathBuf,
+}
+
+impl AppPaths {
+    /// The per-user locations the installed app uses.
+    fn standard() -> Self {
+        Self {
+            settings: settings::settings_file_path(),
+            history: history::history_file_path(),
+            window_state: window_state::window_state_file_path(),
+            hotkey: hotkey::hotkey_file_path(),
+            trigger: PathBuf::from(TRIGGER_FILE),
+        }
+    }
+}
+
+impl App {
+    pub fn new(cc: &eframe::CreationContext, clipboard: Box<dyn ClipboardAccess>) -> Self {
+        setup_japanese_font(&cc.egui_ctx);
+
+        let (tx, rx) = mpsc::channel();
+        let mut app = Self::with_paths(&cc.egui_ctx, clipboard, rx, AppPaths::standard());
+        app.hotkey_active = start_hotkey_listener(app.hotkey.clone(), tx.clone());
+        app.watcher = start_clipboard_watcher(
+            tx.clone(),
+            cc.egui_ctx.clone(),
+            app.settings.watch_mode,
+            app.settings.watch_interval_ms,
+        );
+
+        #[cfg(unix)]
+        if app.settings.control_socket {
+            match control::start_control_listener(tx, cc.egui_ctx.clone()) {
+                Ok(path) => {
+                    eprintln!("[control] Listening on {}", path.display());
+                    app.control_socket = Some(path);
+                }
+                Err(e) => eprintln!("[control] Failed to bind: {e}"),
+            }
+        }
+        #[cfg(not(unix))]
+        drop(tx);
+        app
+    }
+
+    /// Everything but the background listeners (hotkey, clipboard watcher,
+    /// control socket), which `new` starts; events arrive on `rx`.
+    fn with_paths(
+        ctx: &egui::Context,
+        clipboard: Box<dyn ClipboardAccess>,
+        rx: Receiver<AppEvent>,
+        paths: AppPaths,
+    ) -> Self {
+        let hotkey_path = paths.hotkey;
+        let hotkey = hotkey::load(&hotkey_path);
+        let backend = clipboard.backend_hint();
+
+        let settings_path = paths.settings;
+        let first_run = !settings_path.exists();
+        let mut settings = settings::load(&settings_path);
+        settings.max_history =
+            settings.max_history.clamp(settings::MIN_HISTORY_SIZE, settings::MAX_HISTORY_SIZE);
+        settings.zoom_factor = if settings.zoom_factor.is_finite() {
+            settings.zoom_factor.clamp(settings::MIN_ZOOM, settings::MAX_ZOOM)
+        } else {
+            1.0
+        };
+
+        let history_path = paths.history;
+        // An encrypted history stays locked (and unsaved) until the passphrase is entered.
+        let (mut history, history_locked) =
+            match ClipboardHistory::load(&history_path, settings.max_history, None) {
+                Ok(history) => (history, false),
+                Err(e) => {
+                    eprintln!("[history] {e}");
+                    (ClipboardHistory::new(settings.max_history), true)
+                }
+            };
+        history.set_dedup_full(settings.dedup_full);
+        eprintln!("[history] Loaded {} entries from {}", history.len(), history_path.display());
+        // With full dedup off, duplicates in the file are intentional.
+        let merged = if settings.dedup_full { history.dedup_all() } else { 0 };
+        if merged > 0 {
+            eprintln!("[history] Merged {merged} duplicate entries");
+            if let Err(e) = history.save(&history_path) {
+                eprintln!("[history] Save failed: {e}");
+            }
+        }
+
+        let window_state_path = paths.window_state;
+        let saved_window_state = window_state::load(&window_state_path);
+        // The entry may have been deleted or fallen off the end since; then
+        // nothing is selected.
+        let selected_index = saved_window_state.selected_hash.and_then(|h| history.position(h));
+        let history_scroll = saved_window_state.history_scroll;
+
+        // Settings files from before the flag existed: only greet users who
+        // haven't captured anything yet.
+        let show_onboarding = !settings.seen_onboarding && (first_run || history.is_empty());
+        // egui's built-in zoom shortcuts don't persist, so we handle them ourselves.
+        ctx.options_mut(|o| o.zoom_with_keyboard = false);
+        ctx.set_zoom_factor(settings.zoom_factor);
+        apply_accent(ctx, settings.accent_color);
+        // egui falls back to dark when the system theme is unknown.
+        ctx.options_mut(|o| o.fallback_theme = egui::Theme::Dark);
+        ctx.set_theme(settings.theme.preference());
+        let watcher = Arc::new(WatchHandle::new(settings.watch_mode, settings.watch_interval_ms));
+
+        let interpreters = Arc::new(get_interpreters());
+        Self {
+            history,
+            history_locked,
+            passphrase_input: String::new(),
+            unlocking: None,
+            deriving_key: None,
+            confirm_start_over: false,
+            history_path,
+            selected_index,
+            multi_selection: HashSet::new(),
+            selection_anchor: None,
+            rx,
+            clipboard,
+            interpret_cache: InterpretCache::new(Arc::clone(&interpreters)),
+            interpreters,
+            status_message: if merged > 0 {
+                format!("Merged {merged} duplicate entries.")
+            } else {
+                "Ready.".to_string()
+            },
+            toasts: Vec::new(),
+            popout: None,
+            timestamp_style: TimestampStyle::new(&settings.timestamp_format, &settings.timezone),
+            backend,
+            hotkey_active: Arc::new(AtomicBool::new(false)),
+            hotkey_portal: None,
+            trigger_path: paths.trigger,
+            window_state_path,
+            last_outer_rect: None,
+            maximized: false,
+            fullscreen: false,
+            saved_window_state,
+            pending_window_state: None,
+            capture_flash: None,
+            scroll_to_new: false,
+            history_scroll,
+            restore_scroll: Some(history_scroll),
+            attention_pending: false,
+            settings,
+            settings_path,
+            show_info: false,
+            show_export: false,
+            export_overwrite: None,
+            show_onboarding,
+            show_settings: false,
+            expanded_entry: None,
+            line_selection: None,
+            histogram_cache: None,
+            kind_cache: None,
+            stats_cache: None,
+            textures: HashMap::new(),
+            cleared: None,
+            accent_input: String::new(),
+            last_activity: Instant::now(),
+            watcher,
+            search_query: String::new(),
+            compiled_search: None,
+            tag_filter: Vec::new(),
+            tag_match_all: true,
+            tag_input: None,
+            hotkey,
+            hotkey_path,
+            recording_hotkey: false,
+            split_trim: true,
+            editor_files: Vec::new(),
+            control_socket: None,
+        }
+    }
+
+    fn save_history(&mut self) {
+        if self.history_locked {
+            return;
+        }
+        self.history.check_secrets(self.settings.secret_policy, &self.settings.secret_rules);
+        if let Err(e) = self.history.save(&self.history_path) {
+            eprintln!("[history] Save failed: {e}");
+        }
+    }
+
+    fn current_window_state(&self) -> Option<window_state::WindowState> {
+        let rect = self.last_outer_rect?;
+        let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+        // A locked history shows no entries; keep the saved place for after
+        // the unlock instead of overwriting it.
+        let (selected_hash, history_scroll) = if self.history_locked {
+            (self.saved_window_state.selected_hash, self.saved_window_state.history_scroll)
+        } else {
+            let selected = self.selected_index.and_then(|i| self.history.get(i));
+            (selected.map(ClipboardEntry::hash), self.history_scroll)
+        };
+        Some(window_state::WindowState {
+            x: if is_wayland { 0.0 } else { rect.min.x },
+            y: if is_wayland { 0.0 } else { rect.min.y },
+            width: rect.size().x,
+            height: rect.size().y,
+            maximized: self.maximized,
+            fullscreen: self.fullscreen,
+            selected_hash,
+            history_scroll,
+        })
+    }
+
+    /// Write the window geometry if it differs from what was last saved.
+    fn save_window_state(&mut self) {
+        self.pending_window_state = None;
+        let Some(state) = self.current_window_state() else {
+            return;
+        };
+        if self.saved_window_state == state {
+            return;
+        }
+        match window_state::save(&state, &self.window_state_path) {
+            Ok(()) => self.saved_window_state = state,
+            Err(e) => eprintln!("[window_state] Save failed: {e}"),
+        }
+    }
+
+    /// Save the geometry once it has stopped changing for `WINDOW_SAVE_DELAY`,
+    /// so a drag or resize doesn't write on every frame.
+    fn autosave_window_state(&mut self) {
+        let Some(state) = self.current_window_state() else {
+            return;
+        };
+        if self.saved_window_state == state {
+            self.pending_window_state = None;
+            return;
+        }
+        match &self.pending_window_state {
+            Some((pending, since)) if *pending == state => {
+                if since.elapsed() >= WINDOW_SAVE_DELAY {
+                    self.save_window_state();
+                }
+            }
+            _ => self.pending_window_state = Some((state, Instant::now())),
+        }
+    }
+
+    fn save_settings(&mut self) {
+        if let Err(e) = settings::save(&self.settings, &self.settings_path) {
+            eprintln!("[settings] Save failed: {e}");
+        }
+    }
+
+    /// Ctrl+= / Ctrl+- / Ctrl+0 adjust the UI zoom on top of the monitor's
+    /// native scale factor, and remember the choice.
+    fn handle_zoom_shortcuts(&mut self, ctx: &egui::Context) {
+        use egui::gui_zoom::kb_shortcuts;
+
+        let mut zoom = self.settings.zoom_factor;
+        ctx.input_mut(|i| {
+            if i.consume_shortcut(&kb_shortcuts::ZOOM_RESET) {
+                zoom = 1.0;
+            }
+            if i.consume_shortcut(&kb_shortcuts::ZOOM_IN)
+                || i.consume_shortcut(&kb_shortcuts::ZOOM_IN_SECONDARY)
+            {
+                zoom += settings::ZOOM_STEP;
+            }
+            if i.consume_shortcut(&kb_shortcuts::ZOOM_OUT) {
+                zoom -= settings::ZOOM_STEP;
+            }
+        });
+        // Round to one decimal so repeated steps don't accumulate float error.
+        let zoom = ((zoom * 10.0).round() / 10.0).clamp(settings::MIN_ZOOM, settings::MAX_ZOOM);
+
+        if zoom != self.settings.zoom_factor {
+            self.settings.zoom_factor = zoom;
+            ctx.set_zoom_factor(zoom);
+            self.save_settings();
+            self.push_toast(ToastKind::Info, format!("Zoom: {:.0}%", zoom * 100.0));
+        }
+    }
+
+    /// Read the clipboard into history. On success the captured text is the
+    /// front entry (either newly added or an identical existing one).
+    fn capture_clipboard(&mut self) -> Result<(), String> {
+        let read = self.clipboard.get_text();
+        // No text on the clipboard: it may hold an image (e.g. a screenshot).
+        if read.is_err() {
+            if let Ok(image) = self.clipboard.get_image() {
+                self.add_image_capture(image);
+                return Ok(());
+            }
+        }
+        self.add_capture(read, Source::Clipboard, "Captured.")
+    }
+
+    fn add_image_capture(&mut self, image: ClipboardImage) {
+        self.backend = Some(Backend::Arboard);
+        self.last_activity = Instant::now();
+        if self.history.add_image(image) {
+            self.record_source_app();
+            self.save_history();
+            self.notify_capture();
+            self.push_toast(ToastKind::Success, "Captured image.");
+        } else {
+            self.push_toast(ToastKind::Info, "Already the newest entry.");
+        }
+        self.selected_index = Some(0);
+        self.clear_multi_selection();
+    }
+
+    /// Add the highlighted PRIMARY selection to history. CLIPBOARD is never
+    /// read or written, so whatever the user copied stays as it was.
+    /// Elsewhere there is no PRIMARY selection; this returns an error without
+    /// touching history, which control socket clients get as the reply.
+    fn capture_primary(&mut self) -> Result<(), String> {
+        if !cfg!(target_os = "linux") {
+            return Err("PRIMARY selection is only available on Linux".to_string());
+        }
+        let read = self.clipboard.get_primary();
+        self.add_capture(read, Source::Primary, "Captured selection.")
+    }
+
+    fn add_capture(
+        &mut self,
+        read: Result<(String, Backend), String>,
+        source: Source,
+        done: &str,
+    ) -> Result<(), String> {
+        match read {
+            Ok((text, backend)) => {
+                self.backend = Some(backend);
+                self.last_activity = Instant::now();
+                if self.history.add(text, source) {
+                    self.record_source_app();
+                    self.save_history();
+                    self.notify_capture();
+                    self.push_toast(ToastKind::Success, done);
+                } else {
+                    self.push_toast(ToastKind::Info, "Already the newest entry.");
+                }
+                self.selected_index = Some(0);
+                self.clear_multi_selection();
+                Ok(())
+            }
+            Err(e) => {
+                self.push_toast(ToastKind::Error, format!("Error: {e}"));
+                Err(e)
+            }
+        }
+    }
+
+    /// Note the focused window on the newest entry. Best effort: nothing is
+    /// recorded when it can't be found or it's this app's own window.
+    fn record_source_app(&mut self) {
+        let app = clipboard_backend::active_window_name().filter(|name| name != WINDOW_TITLE);
+        if let Some(app) = app {
+            self.history.set_source_app(0, app);
+        }
+    }
+
+    /// Show `text` as a toast and make it the toolbar's "Last" status.
+    fn push_toast(&mut self, kind: ToastKind, text: impl Into<String>) {
+        let text = text.into();
+        self.status_message = text.clone();
+        if self.toasts.len() >= MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+        self.toasts.push(Toast { kind, text, shown_at: Instant::now() });
+    }
+
+    /// Stack live toasts in the bottom-right corner of `area`, newest at the
+    /// bottom, fading each out over the end of its lifetime.
+    fn draw_toasts(&mut self, ctx: &egui::Context, area: egui::Rect) {
+        self.toasts.retain(|t| t.shown_at.elapsed() < t.kind.duration());
+        if self.toasts.is_empty() {
+            return;
+        }
+        ctx.request_repaint();
+        egui::Area::new(egui::Id::new("toasts"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(area.right_bottom() - egui::vec2(12.0, 12.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                for toast in &self.toasts {
+                    let left = toast.kind.duration().saturating_sub(toast.shown_at.elapsed());
+                    let opacity = (left.as_secs_f32() / TOAST_FADE.as_secs_f32()).min(1.0);
+                    ui.scope(|ui| {
+                        ui.set_opacity(opacity);
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(RichText::new(&toast.text).color(toast.kind.color()));
+                        });
+                    });
+                }
+            });
+    }
+
+    /// A capture changed the history: flash the status chip, bring the new top
+    /// entry into view and, if enabled, ask for attention while unfocused.
+    fn notify_capture(&mut self) {
+        self.capture_flash = Some(Instant::now());
+        self.scroll_to_new = true;
+        self.attention_pending = self.settings.attention_on_capture;
+    }
+
+    /// After a file-triggered capture, report the outcome to the configured
+    /// result file so scripts can `touch` the trigger and then read the result.
+    fn write_trigger_result(&mut self, outcome: Result<(), String>) {
+        if self.settings.trigger_result_path.is_empty() {
+            return;
+        }
+        let captured = self.history.get(0).map(|e| e.content()).unwrap_or_default();
+        let body = match (outcome, self.settings.trigger_result) {
+            (Ok(()), TriggerResult::CapturedText) => captured.to_string(),
+            (Ok(()), TriggerResult::Status) => format!("ok {}\n", captured.len()),
+            (Err(e), _) => format!("error: {e}\n"),
+        };
+        // Write then rename, so a polling script never sees a half-written file.
+        let path = PathBuf::from(&self.settings.trigger_result_path);
+        let tmp = path.with_extension("tmp");
+        let result = std::fs::write(&tmp, body).and_then(|()| std::fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            let message = format!("Error: writing {} failed: {e}", path.display());
+            self.push_toast(ToastKind::Error, message);
+        }
+    }
+
+    /// Run a control-socket command and build its reply text.
+    #[cfg(unix)]
+    fn handle_control_command(&mut self, cmd: ControlCommand) -> String {
+        match cmd {
+            ControlCommand::Capture => match self.capture_clipboard() {
+                Ok(()) => {
+                    let len = self.history.get(0).map_or(0, |e| e.content().len());
+                    format!("ok {len}")
+                }
+                Err(e) => format!("error: {e}"),
+            },
+            ControlCommand::CapturePrimary => match self.capture_primary() {
+                Ok(()) => {
+                    let len = self.history.get(0).map_or(0, |e| e.content().len());
+                    format!("ok {len}")
+                }
+                Err(e) => format!("error: {e}"),
+            },
+            ControlCommand::GetLatest => match self.history.get(0) {
+                Some(entry) if entry.image().is_some() => {
+                    "error: newest entry is an image".to_string()
+                }
+                Some(entry) => entry.content().to_string(),
+                None => "error: history is empty".to_string(),
+            },
+            ControlCommand::List => self
+                .history
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    // Same fixed format as the `list` CLI command.
+                    let ts = e.timestamp_str(&TimestampStyle::default());
+                    format!("{i}\t{ts}\t{}\n", e.preview(80))
+                })
+                .collect(),
+            ControlCommand::Copy(index) => {
+                let Some(entry) = self.history.get(index) else {
+                    return format!("error: no entry at index {index}");
+                };
+                if let Some(image) = entry.image().cloned() {
+                    return match self.clipboard.set_image(&image) {
+                        Ok(()) => {
+                            self.push_toast(ToastKind::Success, format!("Copied image #{index}."));
+                            "ok".to_string()
+                        }
+                        Err(e) => {
+                            self.push_toast(ToastKind::Error, format!("Error: {e}"));
+                            format!("error: {e}")
+                        }
+                    };
+                }
+                let text = entry.content().to_string();
+                match self.clipboard.set_text(&text) {
+                    Ok(()) => {
+                        self.watcher.ignore(&text);
+                        self.push_toast(ToastKind::Success, format!("Copied entry #{index}."));
+                        "ok".to_string()
+                    }
+                    Err(e) => {
+                        self.push_toast(ToastKind::Error, format!("Error: {e}"));
+                        format!("error: {e}")
+                    }
+                }
+            }
+            ControlCommand::Interpret(text) => {
+                let ctx = InterpretContext {
+                    max_dump_lines: Some(self.settings.hex_dump_max_lines),
+                    hex_uppercase: self.settings.hex_uppercase,
+                };
+                let mut out = String::new();
+                let enabled = self.interpreters_enabled();
+                for (interp, _) in self.interpreters.iter().zip(enabled).filter(|(_, on)| *on) {
+                    let Some(result) = interp.interpret_with(&text, &ctx) else {
+                        continue;
+                    };
+                    out.push_str(&format!("[{}]\n", interp.name()));
+                    for item in &result.items {
+                        out.push_str(&format!("{}: {}\n", item.label, item.value));
+                    }
+                }
+                if out.is_empty() {
+                    "no interpretation".to_string()
+                } else {
+                    out
+                }
+            }
+        }
+    }
+
+    /// Write `text` to the system clipboard; `what` names it in the status bar.
+    fn copy_to_clipboard(&mut self, what: &str, text: &str) {
+        match self.clipboard.set_text(text) {
+            Ok(()) => {
+                // Our own write isn't a new capture for watch mode.
+                self.watcher.ignore(text);
+                self.push_toast(ToastKind::Success, format!("Copied {what}."));
+            }
+            Err(e) => self.push_toast(ToastKind::Error, format!("Error: {e}")),
+        }
+    }
+
+    /// Copy a history entry back to the clipboard, as an image for image entries.
+    fn copy_entry(&mut self, idx: usize) {
+        let Some(entry) = self.history.get(idx) else {
+            return;
+        };
+        if let Some(image) = entry.image() {
+            match self.clipboard.set_image(image) {
+                Ok(()) => self.push_toast(ToastKind::Success, "Copied image."),
+                Err(e) => self.push_toast(ToastKind::Error, format!("Error: {e}")),
+            }
+        } else {
+            let content = entry.content().to_string();
+            self.copy_to_clipboard("entry", &content);
+        }
+    }
+
+    /// Texture for an image entry, uploaded on first use. `None` when the
+    /// image's PNG can't be read.
+    fn image_texture(&mut self, ctx: &egui::Context, idx: usize) -> Option<egui::TextureHandle> {
+        let entry = self.history.get(idx)?;
+        let hash = entry.hash();
+        if let Some(texture) = self.textures.get(&hash) {
+            return Some(texture.clone());
+        }
+        let image = entry.image()?;
+        let rgba = image.rgba().ok()?;
+        let pixels = egui::ColorImage::from_rgba_unmultiplied([image.width, image.height], rgba);
+        let texture =
+            ctx.load_texture(format!("clip-{hash:016x}"), pixels, egui::TextureOptions::LINEAR);
+        self.textures.insert(hash, texture.clone());
+        Some(texture)
+    }
+
+    fn draw_toolbar(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("📋 Capture Now").clicked() {
+                let _ = self.capture_clipboard();
+            }
+            if cfg!(target_os = "linux")
+                && ui
+                    .button("🖱 Capture Selection")
+                    .on_hover_text(format!(
+                        "Capture highlighted text (PRIMARY) without touching the clipboard ({})",
+                        self.hotkey.primary_display()
+                    ))
+                    .clicked()
+            {
+                let _ = self.capture_primary();
+            }
+            let watch = ui
+                .selectable_label(self.settings.watch_mode, "👁 Watch")
+                .on_hover_text(format!(
+                    "Capture every clipboard change ({})",
+                    self.settings.watch_shortcut
+                ));
+            if watch.clicked() {
+                self.toggle_watch_mode();
+            }
+            if ui.button("🗑 Clear History").clicked() {
+                self.clear_history();
+            }
+            if self.undo_clear_available() && ui.button("↶ Undo clear").clicked() {
+                self.undo_clear();
+            }
+            if !self.multi_selection.is_empty() {
+                let label = format!("🗑 Delete selected ({})", self.multi_selection.len());
+                let delete = ui.button(label).on_hover_text("Ctrl/Shift-click rows to select");
+                if delete.clicked() {
+                    self.delete_selected_entries();
+                }
+            }
+            ui.toggle_value(&mut self.show_settings, "⚙ Settings");
+            ui.toggle_value(&mut self.show_info, "ℹ Info");
+            ui.toggle_value(&mut self.show_export, "💾 Import/Export");
+            let theme = ui
+                .button(format!("◐ {}", self.settings.theme.label()))
+                .on_hover_text("Cycle theme: Dark → Light → System");
+            if theme.clicked() {
+                self.settings.theme = self.settings.theme.next();
+                ui.ctx().set_theme(self.settings.theme.preference());
+                self.save_settings();
+            }
+            ui.separator();
+            // Right-to-left: chips are added from the rightmost one.
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                self.draw_status_chips(ui);
+            });
+        });
+    }
+
+    fn draw_status_chips(&self, ui: &mut Ui) {
+        let last_color = if self.status_message.starts_with("Error") {
+            CHIP_ERROR
+        } else {
+            Color32::from_rgb(180, 180, 180)
+        };
+        // After a new capture, fade from the "OK" green back to the normal color.
+        let flash = self
+            .capture_flash
+            .map(|at| at.elapsed().as_secs_f32() / CAPTURE_FLASH.as_secs_f32())
+            .filter(|t| *t < 1.0);
+        let last_color = match flash {
+            Some(t) => {
+                ui.ctx().request_repaint();
+                CHIP_OK.lerp_to_gamma(last_color, t)
+            }
+            None => last_color,
+        };
+        status_chip(ui, "Last", &self.status_message, last_color);
+
+        let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+        if let Some(trigger) = &self.hotkey_portal {
+            let active = self.hotkey_active.load(Ordering::Relaxed);
+            let (value, color) =
+                if active { (trigger.as_str(), CHIP_OK) } else { ("ended", CHIP_WARN) };
+            status_chip(ui, "Global hotkey", value, color).on_hover_text(
+                "Registered through the desktop's GlobalShortcuts portal; \
+                 change the trigger in the desktop's shortcut settings.",
+            );
+        } else if self.hotkey_active.load(Ordering::Relaxed) {
+            status_chip(ui, "Global hotkey", &self.hotkey.display(), CHIP_OK)
+                .on_hover_text(if is_wayland {
+                    "Listening via XWayland; may only fire while an X11 window is focused."
+                } else {
+                    "Listening for the hotkey system-wide."
+                });
+        } else {
+            status_chip(ui, "Global hotkey", "unavailable", CHIP_WARN).on_hover_text(format!(
+                "In-app hotkey {} still works while focused.\nGlobal trigger: touch {}",
+                self.hotkey.display(),
+                TRIGGER_FILE
+            ));
+        }
+
+        if self.settings.watch_mode {
+            status_chip(ui, "Watch", "on", CHIP_OK);
+        }
+
+        match self.backend {
+            Some(backend) => status_chip(ui, "Backend", backend.label(), CHIP_OK),
+            None => status_chip(ui, "Backend", "none", CHIP_WARN),
+        };
+    }
+
+    fn draw_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_settings;
+        let mut changed = false;
+        let mut accent_changed = false;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.heading("Display");
+                ui.horizontal(|ui| {
+                    ui.label("Hex color case:");
+                    changed |= ui
+                        .selectable_value(&mut self.settings.hex_uppercase, false, "#ff5500")
+                        .changed();
+                    changed |= ui
+                        .selectable_value(&mut self.settings.hex_uppercase, true, "#FF5500")
+                        .changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Hex dump lines before \"Show full\":");
+                    let lines = egui::DragValue::new(&mut self.settings.hex_dump_max_lines)
+                        .range(16..=100_000);
+                    changed |= ui.add(lines).changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Accent color:");
+                    let mut custom = self.settings.accent_color.is_some();
+                    if ui.checkbox(&mut custom, "Custom").changed() {
+                        self.settings.accent_color = custom.then_some([90, 170, 255, 255]);
+                        accent_changed = true;
+                    }
+                    if let Some(accent) = &mut self.settings.accent_color {
+                        let mut color = Color32::from_rgba_unmultiplied(
+                            accent[0], accent[1], accent[2], accent[3],
+                        );
+                        if ui.color_edit_button_srgba(&mut color).changed() {
+                            *accent = color.to_srgba_unmultiplied();
+                            accent_changed = true;
+                        }
+                        // Paste any color the Color Code interpreter understands.
+                        let input = ui.add(
+                            egui::TextEdit::singleline(&mut self.accent_input)
+                                .hint_text("#5aaaff")
+                                .desired_width(90.0),
+                        );
+                        if input.changed() {
+                            if let Some(parsed) = color::parse_rgba(self.accent_input.trim()) {
+                                *accent = parsed;
+                                accent_changed = true;
+                            }
+                        }
+                    }
+                });
+                changed |= self.draw_timestamp_settings(ui);
+                egui::CollapsingHeader::new("Interpreters").show(ui, |ui| {
+                    changed |= self.draw_interpreter_toggles(ui);
+                });
+                ui.heading("History");
+                ui.horizontal(|ui| {
+                    ui.label("Double-click action:");
+                    egui::ComboBox::from_id_salt("double_click_action")
+                        .selected_text(self.settings.double_click_action.label())
+                        .show_ui(ui, |ui| {
+                            for action in DoubleClickAction::ALL {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.settings.double_click_action,
+                                        action,
+                                        action.label(),
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Keep at most:");
+                    let size = egui::DragValue::new(&mut self.settings.max_history)
+                        .range(settings::MIN_HISTORY_SIZE..=settings::MAX_HISTORY_SIZE)
+                        .suffix(" entries");
+                    let response = ui.add(size).on_hover_text("Pinned entries are never evicted");
+                    // Apply drags on release so sliding down and back up doesn't
+                    // evict entries along the way.
+                    if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                        self.history.set_max_size(self.settings.max_history);
+                        self.save_history();
+                        changed = true;
+                    }
+                });
+                changed |= ui
+                    .checkbox(&mut self.settings.attention_on_capture, "Flag the window on capture")
+                    .on_hover_text(
+                        "Ask for attention (e.g. a flashing taskbar entry) when a capture \
+                         adds an entry while the window is in the background",
+                    )
+                    .changed();
+                let dedup = ui
+                    .checkbox(&mut self.settings.dedup_full, "Move re-copied entries to the top")
+                    .on_hover_text("Off: every copy is kept, even if it's already in history");
+                if dedup.changed() {
+                    self.history.set_dedup_full(self.settings.dedup_full);
+                    changed = true;
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Auto-clear after idle:");
+                    let minutes = egui::DragValue::new(&mut self.settings.auto_clear_idle_minutes)
+                        .range(0..=24 * 60)
+                        .suffix(" min");
+                    changed |= ui.add(minutes).on_hover_text("0 = never").changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Watch mode interval:");
+                    let interval = egui::DragValue::new(&mut self.settings.watch_interval_ms)
+                        .range(100..=10_000)
+                        .speed(10)
+                        .suffix(" ms");
+                    if ui.add(interval).changed() {
+                        self.watcher.set_interval_ms(self.settings.watch_interval_ms);
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Watch mode shortcut:");
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.settings.watch_shortcut)
+                            .desired_width(110.0),
+                    );
+                    changed |= edit.changed();
+                    if parse_shortcut(&self.settings.watch_shortcut).is_none() {
+                        ui.colored_label(CHIP_WARN, "not a valid shortcut");
+                    }
+                });
+                ui.heading("Secrets");
+                changed |= self.draw_secret_settings(ui);
+                ui.heading("Encryption");
+                self.draw_encryption_settings(ui);
+                ui.heading("Hotkey");
+                ui.horizontal(|ui| {
+                    ui.label("Capture:");
+                    ui.code(self.hotkey.display());
+                    let label = if self.recording_hotkey {
+                        "Press Ctrl or Alt + a key… (Esc cancels)"
+                    } else {
+                        "Record…"
+                    };
+                    if ui.selectable_label(self.recording_hotkey, label).clicked() {
+                        self.recording_hotkey = !self.recording_hotkey;
+                    }
+                });
+                if !self.hotkey.is_global_capable() {
+                    ui.colored_label(CHIP_WARN, "This key only works in-app, not globally.");
+                }
+                ui.label(
+                    RichText::new("The global hotkey picks up changes after a restart.")
+                        .color(Color32::GRAY)
+                        .small(),
+                );
+                ui.heading("Trigger file");
+                ui.label(
+                    RichText::new(format!("Capture on: touch {TRIGGER_FILE}"))
+                        .color(Color32::GRAY)
+                        .small(),
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Write result to:");
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut self.settings.trigger_result_path)
+                                .hint_text(TRIGGER_RESULT_HINT),
+                        )
+                        .changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Result content:");
+                    egui::ComboBox::from_id_salt("trigger_result")
+                        .selected_text(self.settings.trigger_result.label())
+                        .show_ui(ui, |ui| {
+                            for mode in TriggerResult::ALL {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.settings.trigger_result,
+                                        mode,
+                                        mode.label(),
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                #[cfg(unix)]
+                {
+                    ui.heading("Control socket");
+                    changed |= ui
+                        .checkbox(&mut self.settings.control_socket, "Enable (restart to apply)")
+                        .changed();
+                    ui.label(
+                        RichText::new(control::socket_path().display().to_string())
+                            .color(Color32::GRAY)
+                            .small(),
+                    );
+                }
+                ui.separator();
+                ui.label(
+                    RichText::new(format!(
+                        "Zoom: {:.0}%  (Ctrl+= / Ctrl+- / Ctrl+0)",
+                        self.settings.zoom_factor * 100.0
+                    ))
+                    .color(Color32::GRAY),
+                );
+            });
+        self.show_settings = open;
+        if accent_changed {
+            apply_accent(ctx, self.settings.accent_color);
+            changed = true;
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    /// Interpreters in display order, each with a drag handle to move it and
+    /// a checkbox to leave it out of the detail panel. Returns true if
+    /// anything changed.
+    fn draw_interpreter_toggles(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let order = interpreter_order(&self.interpreters, &self.settings.interpreter_order);
+        // (from, to) positions in `order` of a finished drag.
+        let mut moved: Option<(usize, usize)> = None;
+        ui.label(
+            RichText::new("Drag ☰ to change the order in the detail panel.")
+                .color(Color32::GRAY)
+                .small(),
+        );
+        ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+            for (pos, &i) in order.iter().enumerate() {
+                let name = self.interpreters[i].name();
+                let row = ui
+                    .horizontal(|ui| {
+                        ui.dnd_drag_source(egui::Id::new(("interp_drag", name)), pos, |ui| {
+                            ui.label("☰");
+                        });
+                        let disabled = &mut self.settings.disabled_interpreters;
+                        let mut enabled = !disabled.iter().any(|d| d == name);
+                        if ui.checkbox(&mut enabled, name).changed() {
+                            if enabled {
+                                disabled.retain(|d| d != name);
+                            } else {
+                                disabled.push(name.to_string());
+                            }
+                            changed = true;
+                        }
+                    })
+                    .response;
+                // Mark where the dragged row will land.
+                if let Some(from) = row.dnd_hover_payload::<usize>() {
+                    let y = if *from < pos { row.rect.bottom() } else { row.rect.top() };
+                    let stroke = ui.visuals().selection.stroke;
+                    let stroke = egui::Stroke::new(stroke.width, ui.visuals().selection.bg_fill);
+                    ui.painter().hline(row.rect.x_range(), y, stroke);
+                }
+                if let Some(from) = row.dnd_release_payload::<usize>() {
+                    moved = Some((*from, pos));
+                }
+            }
+        });
+        if let Some((from, to)) = moved.filter(|(from, to)| from != to) {
+            let mut names: Vec<String> =
+                order.iter().map(|&i| self.interpreters[i].name().to_string()).collect();
+            let name = names.remove(from);
+            names.insert(to, name);
+            self.settings.interpreter_order = names;
+            changed = true;
+        }
+        if !self.settings.interpreter_order.is_empty() && ui.button("Reset order").clicked() {
+            self.settings.interpreter_order.clear();
+            changed = true;
+        }
+        changed
+    }
+
+    /// Format and timezone of capture times, with presets and a preview.
+    /// Returns true if a setting changed.
+    fn draw_timestamp_settings(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Timestamps:");
+            let current = TIMESTAMP_PRESETS
+                .iter()
+                .find(|(format, _)| *format == self.settings.timestamp_format)
+                .map_or("Custom", |(_, label)| *label);
+            egui::ComboBox::from_id_salt("timestamp_preset")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (format, label) in TIMESTAMP_PRESETS {
+                        let selected = self.settings.timestamp_format == format;
+                        if ui.selectable_label(selected, label).clicked() {
+                            self.settings.timestamp_format = format.to_string();
+                            changed = true;
+                        }
+                    }
+                });
+            let edit = egui::TextEdit::singleline(&mut self.settings.timestamp_format)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(150.0);
+            changed |= ui.add(edit).on_hover_text("strftime format, e.g. %Y-%m-%d %H:%M").changed();
+            if !history::is_valid_format(&self.settings.timestamp_format) {
+                ui.colored_label(CHIP_WARN, "not a valid format");
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Timezone:");
+            for zone in ["Local", "UTC"] {
+                if ui.selectable_label(self.settings.timezone == zone, zone).clicked() {
+                    self.settings.timezone = zone.to_string();
+                    changed = true;
+                }
+            }
+            let edit = egui::TextEdit::singleline(&mut self.settings.timezone)
+                .hint_text("Europe/Berlin")
+                .desired_width(130.0);
+            changed |= ui.add(edit).on_hover_text("Local, UTC or an IANA zone name").changed();
+            if DisplayZone::parse(&self.settings.timezone).is_none() {
+                ui.colored_label(CHIP_WARN, "unknown timezone");
+            }
+        });
+        if changed {
+            self.timestamp_style =
+                TimestampStyle::new(&self.settings.timestamp_format, &self.settings.timezone);
+        }
+        ui.label(
+            RichText::new(format!(
+                "Preview: {}",
+                self.timestamp_style.format_time(std::time::SystemTime::now())
+            ))
+            .color(Color32::GRAY)
+            .small(),
+        );
+        changed
+    }
+
+    /// Policy for entries that look like secrets. Returns true if a setting
+    /// changed; the history is then re-checked and saved.
+    fn draw_secret_settings(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("When an entry holds a secret:");
+            egui::ComboBox::from_id_salt("secret_policy")
+                .selected_text(self.settings.secret_policy.label())
+                .show_ui(ui, |ui| {
+                    for policy in SecretPolicy::ALL {
+                        changed |= ui
+                            .selectable_value(
+                                &mut self.settings.secret_policy,
+                                policy,
+                                policy.label(),
+                            )
+                            .changed();
+                    }
+                });
+        })
+        .response
+        .on_hover_text("Entries stay usable until the app quits either way");
+        ui.add_enabled_ui(self.settings.secret_policy != SecretPolicy::Keep, |ui| {
+            let rules = &mut self.settings.secret_rules;
+            changed |= ui.checkbox(&mut rules.private_keys, "Private keys (PEM)").changed();
+            changed |= ui
+                .checkbox(&mut rules.known_tokens, "API tokens (AWS, GitHub, Slack, …)")
+                .changed();
+            changed |= ui
+                .checkbox(&mut rules.random_strings, "Random-looking strings")
+                .on_hover_text("Also matches generated IDs and other non-secrets")
+                .changed();
+        });
+        if changed {
+            self.history.reset_persist();
+            self.save_history();
+        }
+        changed
+    }
+
+    fn draw_encryption_settings(&mut self, ui: &mut Ui) {
+        if self.history_locked {
+            ui.label("Unlock the history first.");
+            return;
+        }
+        let encrypted = self.history.is_encrypted();
+        ui.label(if encrypted {
+            "History is encrypted with a passphrase."
+        } else {
+            "History is stored as plain JSON."
+        });
+        if self.deriving_key.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Deriving the key…");
+            });
+            return;
+        }
+        let mut apply: Option<Option<String>> = None;
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.passphrase_input)
+                    .password(true)
+                    .hint_text("New passphrase")
+                    .desired_width(160.0),
+            );
+            let label = if encrypted { "Change passphrase" } else { "Encrypt" };
+            let can_apply = !self.passphrase_input.is_empty();
+            if ui.add_enabled(can_apply, egui::Button::new(label)).clicked() {
+                apply = Some(Some(std::mem::take(&mut self.passphrase_input)));
+            }
+            if encrypted && ui.button("Remove encryption").clicked() {
+                apply = Some(None);
+            }
+        });
+        ui.label(
+            RichText::new(
+                "There is no way to recover a forgotten passphrase. \
+                 Images are kept for this session only while encrypted.",
+            )
+                .color(Color32::GRAY)
+                .small(),
+        );
+        match apply {
+            Some(Some(passphrase)) => {
+                let (tx, rx) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let _ = tx.send(HistoryKey::new(&passphrase));
+                });
+                self.deriving_key = Some(rx);
+            }
+            Some(None) => self.apply_history_key(None),
+            None => {}
+        }
+    }
+
+    fn apply_history_key(&mut self, key: Option<HistoryKey>) {
+        let encrypt = key.is_some();
+        match self.history.set_key(key) {
+            Ok(()) => {
+                self.save_history();
+                let done =
+                    if encrypt { "History encrypted." } else { "History encryption removed." };
+                self.push_toast(ToastKind::Success, done);
+            }
+            Err(e) => self.push_toast(ToastKind::Error, format!("Error: {e}")),
+        }
+    }
+
+    /// Pick up the results of background key derivation and decryption.
+    fn poll_key_jobs(&mut self) {
+        if let Some(rx) = &self.deriving_key {
+            match rx.try_recv() {
+                Ok(Ok(key)) => {
+                    self.deriving_key = None;
+                    self.apply_history_key(Some(key));
+                }
+                Ok(Err(e)) => {
+                    self.deriving_key = None;
+                    self.push_toast(ToastKind::Error, format!("Error: {e}"));
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.deriving_key = None,
+            }
+        }
+        if let Some(rx) = &self.unlocking {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.unlocking = None;
+                    self.finish_unlock(result);
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.unlocking = None,
+            }
+        }
+    }
+
+    /// Passphrase prompt shown while the encrypted history is locked.
+    fn draw_unlock_window(&mut self, ctx: &egui::Context) {
+        let mut unlock = false;
+        let mut start_over = false;
+        egui::Window::new("🔒 History is encrypted")
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Enter the passphrase to load your history. Until then nothing is saved.");
+                if self.unlocking.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Unlocking…");
+                    });
+                    return;
+                }
+                if self.confirm_start_over {
+                    ui.colored_label(
+                        CHIP_WARN,
+                        "The encrypted history will be replaced by a new, unencrypted one. \
+                         It can't be recovered afterwards.",
+                    );
+                    ui.horizontal(|ui| {
+                        start_over = ui.button("Replace it").clicked();
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_start_over = false;
+                        }
+                    });
+                    return;
+                }
+                let input = ui.add(
+                    egui::TextEdit::singleline(&mut self.passphrase_input)
+                        .password(true)
+                        .hint_text("Passphrase"),
+                );
+                let entered = input.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                ui.horizontal(|ui| {
+                    unlock = ui.button("Unlock").clicked() || entered;
+                    if ui
+                        .button("Start a new history")
+                        .on_hover_text("Replaces the encrypted history; asks first")
+                        .clicked()
+                    {
+                        self.confirm_start_over = true;
+                    }
+                });
+            });
+        if unlock {
+            self.unlock_history();
+        } else if start_over {
+            self.confirm_start_over = false;
+            self.history_locked = false;
+            self.save_history();
+            self.push_toast(ToastKind::Info, "Started a new, unencrypted history.");
+        }
+    }
+
+    /// Decrypt the history on a background thread; `poll_key_jobs` picks up
+    /// the result.
+    fn unlock_history(&mut self) {
+        let passphrase = std::mem::take(&mut self.passphrase_input);
+        let path = self.history_path.clone();
+        let max = self.settings.max_history;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(ClipboardHistory::load(&path, max, Some(&passphrase)));
+        });
+        self.unlocking = Some(rx);
+    }
+
+    fn finish_unlock(&mut self, result: Result<ClipboardHistory, String>) {
+        let mut loaded = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.push_toast(ToastKind::Error, format!("Error: {e}"));
+                return;
+            }
+        };
+        loaded.set_dedup_full(self.settings.dedup_full);
+        // Keep what was captured while the history was locked.
+        let captured = std::mem::replace(&mut self.history, ClipboardHistory::new(0));
+        loaded.add_newer(captured);
+        self.history = loaded;
+        self.history_locked = false;
+        self.selected_index =
+            self.saved_window_state.selected_hash.and_then(|h| self.history.position(h));
+        self.restore_scroll = Some(self.saved_window_state.history_scroll);
+        self.clear_multi_selection();
+        self.save_history();
+        self.push_toast(ToastKind::Success, "History unlocked.");
+    }
+
+    /// First-run welcome explaining the ways to capture.
+    fn draw_onboarding_window(&mut self, ctx: &egui::Context) {
+        let mut dismissed = false;
+        egui::Window::new("Welcome to Clipboard Hack")
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.set_max_width(480.0);
+                ui.label(
+                    "Captured clipboard text shows up in the history on the left; select an \
+                     entry to see what it decodes to. There are three ways to capture:",
+                );
+                ui.add_space(6.0);
+                egui::Grid::new("onboarding_paths")
+                    .num_columns(2)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Global hotkey").strong());
+                        ui.label(format!(
+                            "{} anywhere (X11 / macOS; macOS needs Accessibility permission)",
+                            self.hotkey.display()
+                        ));
+                        ui.end_row();
+                        ui.label(RichText::new("In-app").strong());
+                        ui.label(format!(
+                            "'Capture Now' or {} while this window is focused",
+                            self.hotkey.display()
+                        ));
+                        ui.end_row();
+                        ui.label(RichText::new("Trigger file").strong());
+                        ui.label(format!(
+                            "touch {TRIGGER_FILE}  (bind this to a key in your Wayland compositor)"
+                        ));
+                        ui.end_row();
+                    });
+                ui.add_space(6.0);
+                ui.label(
+                    RichText::new("The toolbar status chips show which of these are working.")
+                        .color(Color32::GRAY),
+                );
+                ui.add_space(6.0);
+                dismissed = ui.button("Got it").clicked();
+            });
+        if dismissed {
+            self.show_onboarding = false;
+            self.settings.seen_onboarding = true;
+            self.save_settings();
+        }
+    }
+
+    fn draw_export_window(&mut self, ctx: &egui::Context) {
+        let mut export_clicked = false;
+        let mut import_clicked = false;
+        let mut overwrite: Option<bool> = None;
+        egui::Window::new("Import / export history")
+            .open(&mut self.show_export)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(
+                        "Export as .json, .csv, or any other extension for plain text. \
+                         Import reads .json or text files.",
+                    )
+                        .color(Color32::GRAY)
+                        .small(),
+                );
+                if let Some(path) = &self.export_overwrite {
+                    ui.colored_label(CHIP_WARN, format!("{} already exists.", path.display()));
+                    ui.horizontal(|ui| {
+                        if ui.button("Replace it").clicked() {
+                            overwrite = Some(true);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            overwrite = Some(false);
+                        }
+                    });
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    export_clicked = ui
+                        .button("Export…")
+                        .on_hover_text("Save the history as .json, .csv or plain text")
+                        .clicked();
+                    import_clicked = ui
+                        .button("Import…")
+                        .on_hover_text("Merge entries from a JSON or text file into history")
+                        .clicked();
+                });
+            });
+        if export_clicked {
+            if let Some(path) = pick_export_file() {
+                // Native dialogs differ in whether they confirm replacing a
+                // file, so always ask here.
+                if path.exists() {
+                    self.export_overwrite = Some(path);
+                } else {
+                    self.export_history(&path, false);
+                }
+            }
+        }
+        match overwrite {
+            Some(true) => {
+                if let Some(path) = self.export_overwrite.take() {
+                    self.export_history(&path, true);
+                }
+            }
+            Some(false) => self.export_overwrite = None,
+            None => {}
+        }
+        if let Some(path) = import_clicked.then(pick_import_file).flatten() {
+            match import::read_file(&path) {
+                Ok(entries) => {
+                    let (added, skipped) = self.history.merge(entries);
+                    if added > 0 {
+                        self.save_history();
+                        // Indices shifted; don't leave a different entry selected.
+                        self.selected_index = None;
+                        self.clear_multi_selection();
+                    }
+                    let done = format!("Imported {added} entries ({skipped} skipped).");
+                    self.push_toast(ToastKind::Success, done);
+                }
+                Err(e) => self.push_toast(ToastKind::Error, format!("Error: import failed: {e}")),
+            }
+        }
+    }
+
+    fn export_history(&mut self, path: &std::path::Path, overwrite: bool) {
+        match export::export(self.history.entries(), path, overwrite) {
+            Ok(n) => {
+                let done = format!("Exported {n} entries to {}.", path.display());
+                self.push_toast(ToastKind::Success, done);
+            }
+            Err(e) => self.push_toast(ToastKind::Error, format!("Error: export failed: {e}")),
+        }
+    }
+
+    fn draw_info_window(&mut self, ctx: &egui::Context) {
+        let stats = self.history.capture_stats();
+        egui::Window::new("Info")
+            .open(&mut self.show_info)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.heading("Capture stats");
+                egui::Grid::new("capture_stats")
+                    .num_columns(2)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Today").color(Color32::GRAY));
+                        ui.label(stats.today.to_string());
+                        ui.end_row();
+                        ui.label(RichText::new("This week").color(Color32::GRAY));
+                        ui.label(stats.this_week.to_string());
+                        ui.end_row();
+                        ui.label(RichText::new("Total").color(Color32::GRAY));
+                        ui.label(stats.total.to_string());
+                        ui.end_row();
+                        ui.label(RichText::new("Busiest hour").color(Color32::GRAY));
+                        ui.label(match stats.busiest_hour {
+                            Some((hour, n)) => format!("{hour:02}:00–{hour:02}:59 ({n} captures)"),
+                            None => "—".to_string(),
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
+
+    /// Filter for the current search query. In regex mode the pattern is
+    /// compiled once per change; a compile error is returned for display.
+    fn search_filter(&mut self, query: &str) -> Result<SearchFilter, String> {
+        if query.is_empty() {
+            return Ok(SearchFilter::All);
+        }
+        if !self.settings.search_regex {
+            return Ok(SearchFilter::Fuzzy(query.to_string()));
+        }
+        let ignore_case = self.settings.search_ignore_case;
+        let stale = self
+            .compiled_search
+            .as_ref()
+            .is_none_or(|(pattern, ic, _)| pattern != query || *ic != ignore_case);
+        if stale {
+            let compiled = RegexBuilder::new(query)
+                .case_insensitive(ignore_case)
+                .build()
+                .map_err(|e| e.to_string());
+            self.compiled_search = Some((query.to_string(), ignore_case, compiled));
+        }
+        match &self.compiled_search {
+            Some((_, _, Ok(re))) => Ok(SearchFilter::Regex(re.clone())),
+            Some((_, _, Err(e))) => Err(e.clone()),
+            None => Ok(SearchFilter::All),
+        }
+    }
+
+    fn draw_history_panel(&mut self, ui: &mut Ui) {
+        let scroll_to_new = std::mem::take(&mut self.scroll_to_new);
+        ui.heading("History");
+        ui.label(
+            RichText::new(format!("{} item(s)", self.history.len()))
+                .color(Color32::GRAY)
+                .small(),
+        );
+        ui.separator();
+
+        if self.history.is_empty() {
+            ui.colored_label(
+                Color32::GRAY,
+                "No history yet.\nPress 'Capture Now' or use the hotkey.",
+            );
+            return;
+        }
+
+        let mut search_changed = false;
+        ui.horizontal(|ui| {
+            let toggles_w = if self.settings.search_regex { 84.0 } else { 56.0 };
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search_query)
+                    .hint_text(if self.settings.search_regex { "🔍 Regex" } else { "🔍 Search" })
+                    .desired_width(ui.available_width() - toggles_w),
+            );
+            if !self.search_query.is_empty() && ui.small_button("×").clicked() {
+                self.search_query.clear();
+            }
+            search_changed |= ui
+                .toggle_value(&mut self.settings.search_regex, ".*")
+                .on_hover_text("Regex search")
+                .changed();
+            if self.settings.search_regex {
+                search_changed |= ui
+                    .toggle_value(&mut self.settings.search_ignore_case, "Aa")
+                    .on_hover_text("Ignore case")
+                    .changed();
+            }
+        });
+        if search_changed {
+            self.save_settings();
+        }
+
+        let query = self.search_query.trim().to_string();
+        let filter = match self.search_filter(&query) {
+            Ok(filter) => filter,
+            Err(e) => {
+                ui.colored_label(Color32::from_rgb(230, 80, 80), e);
+                SearchFilter::All
+            }
+        };
+        self.draw_tag_bar(ui);
+        let tag_filter = &self.tag_filter;
+        let has_tags = |tags: &[String]| {
+            let mut wanted = tag_filter.iter();
+            if self.tag_match_all {
+                wanted.all(|t| tags.contains(t))
+            } else {
+                tag_filter.is_empty() || wanted.any(|t| tags.contains(t))
+            }
+        };
+
+        // Rows carry their real history index, so selection and deletion
+        // stay correct while the list is filtered.
+        let mut items: Vec<(usize, bool, ContentKind, String, String, i32)> = self
+            .history
+            .entries()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| {
+                if !has_tags(e.tags()) {
+                    return None;
+                }
+                let note_score = e.note().and_then(|note| filter.score(note));
+                let app_score = e.source_app().and_then(|app| filter.score(app));
+                let score = filter.score(e.content()).max(note_score).max(app_score)?;
+                let mut ts = e.timestamp_str(&self.timestamp_style);
+                if e.note().is_some() {
+                    ts.push_str("  📝");
+                }
+                if let Some(app) = e.source_app() {
+                    let short: String = app.chars().take(ROW_APP_CHARS).collect();
+                    let more = if app.chars().count() > ROW_APP_CHARS { "…" } else { "" };
+                    ts.push_str(&format!("  · {short}{more}"));
+                }
+                Some((i, e.pinned(), e.kind(), ts, e.preview(45), score))
+            })
+            .collect();
+        // Drop textures of image entries that have left the history.
+        let live: Vec<u64> = self.history.entries().iter().map(ClipboardEntry::hash).collect();
+        self.textures.retain(|hash, _| live.contains(hash));
+        // Pinned first, then best match; the stable sort keeps ties in history order.
+        items.sort_by_key(|&(_, pinned, .., score)| (!pinned, std::cmp::Reverse(score)));
+        if items.is_empty() {
+            let message = if query.is_empty() {
+                "No entries carry the selected tags.".to_string()
+            } else {
+                format!("No entries match \"{}\".", self.search_query)
+            };
+            ui.colored_label(Color32::GRAY, message);
+            return;
+        }
+
+        let mut to_delete: Option<usize> = None;
+        let mut clicked: Option<(usize, egui::Modifiers)> = None;
+        let order: Vec<usize> = items.iter().map(|&(i, ..)| i).collect();
+        let mut double_clicked: Option<usize> = None;
+        let mut pin_toggled: Option<(usize, bool)> = None;
+
+        let mut scroll_area = ScrollArea::vertical();
+        if let Some(offset) = self.restore_scroll.take() {
+            scroll_area = scroll_area.vertical_scroll_offset(offset);
+        }
+        let scroll_output = scroll_area.show(ui, |ui| {
+            // Row height: 2 lines of button-style text + vertical padding.
+            // Measured in points, so it follows pixels_per_point (monitor scale × zoom);
+            // snapped to whole physical pixels to keep row edges crisp on HiDPI.
+            let font_id = egui::TextStyle::Button.resolve(ui.style());
+            let line_h = ui.fonts(|f| f.row_height(&font_id));
+            let row_h = ui
+                .painter()
+                .round_to_pixel(line_h * 2.0 + ui.spacing().button_padding.y * 2.0);
+
+            let highlight = accent_color32(self.settings.accent_color)
+                .unwrap_or(Color32::from_rgb(255, 190, 60));
+
+            let mut prev_pinned = false;
+            for (i, pinned, kind, ts, preview, _) in items {
+                if prev_pinned && !pinned {
+                    ui.separator();
+                }
+                prev_pinned = pinned;
+                let selected = self.selected_index == Some(i) || self.multi_selection.contains(&i);
+                let label = row_label(&ts, &preview, &filter, &font_id, highlight);
+                let thumbnail = self.image_texture(ui.ctx(), i);
+
+                let (sel_clicked, dbl_clicked, pin_clicked, del_clicked) = ui.horizontal(|ui| {
+                    let avail = ui.available_width();
+                    let btn_w = 20.0;
+                    let icon_w = if thumbnail.is_some() { row_h } else { 18.0 };
+                    let gap = ui.spacing().item_spacing.x;
+                    let label_w = (avail - icon_w - btn_w * 2.0 - gap * 3.0).max(0.0);
+
+                    match &thumbnail {
+                        Some(texture) => {
+                            let image =
+                                egui::Image::new(texture).max_size(egui::vec2(icon_w, row_h));
+                            ui.add_sized([icon_w, row_h], image);
+                        }
+                        None => draw_kind_icon(ui, kind, egui::vec2(icon_w, row_h)),
+                    }
+
+                    // allocate_ui_with_layout で top_down(LEFT) コンテキストを作る。
+                    // SelectableLabel はこのコンテキストの h_align() = LEFT を参照して
+                    // テキストを左寄せに配置する。
+                    let sel = ui.allocate_ui_with_layout(
+                        egui::vec2(label_w, row_h),
+                        egui::Layout::top_down_justified(egui::Align::LEFT),
+                        |ui| ui.selectable_label(selected, label),
+                    ).inner;
+                    let sel = sel.on_hover_ui(|ui| {
+                        if let Some(entry) = self.history.get(i) {
+                            let style = &self.timestamp_style;
+                            let disabled = &self.settings.disabled_interpreters;
+                            draw_entry_tooltip(ui, entry, &self.interpreters, disabled, style);
+                        }
+                    });
+                    if scroll_to_new && i == 0 {
+                        sel.scroll_to_me(Some(egui::Align::Center));
+                    }
+
+                    let pin = ui
+                        .add_sized(
+                            [btn_w, row_h],
+                            egui::Button::new("📌").small().selected(pinned),
+                        )
+                        .on_hover_text(if pinned { "Unpin" } else { "Pin (kept on clear)" });
+                    let del = ui.add_sized([btn_w, row_h], egui::Button::new("×").small());
+                    (sel.clicked(), sel.double_clicked(), pin.clicked(), del.clicked())
+                }).inner;
+
+                if sel_clicked {
+                    clicked = Some((i, ui.input(|input| input.modifiers)));
+                }
+                if dbl_clicked {
+                    double_clicked = Some(i);
+                }
+                if pin_clicked {
+                    pin_toggled = Some((i, !pinned));
+                }
+                if del_clicked {
+                    to_delete = Some(i);
+                }
+            }
+        });
+        self.history_scroll = scroll_output.state.offset.y;
+
+        if let Some((idx, modifiers)) = clicked {
+            self.click_row(idx, modifiers, &order);
+        }
+
+        if let Some((idx, pinned)) = pin_toggled {
+            self.history.set_pinned(idx, pinned);
+            self.save_history();
+        }
+
+        if let Some(idx) = double_clicked {
+            self.run_double_click_action(ui.ctx(), idx);
+        }
+        if let Some(idx) = to_delete {
+            self.delete_history_entry(idx);
+        }
+    }
+
+    fn run_double_click_action(&mut self, ctx: &egui::Context, idx: usize) {
+        let Some(entry) = self.history.get(idx) else {
+            return;
+        };
+        let content = entry.content().to_string();
+        let is_image = entry.image().is_some();
+        match self.settings.double_click_action {
+            DoubleClickAction::Copy => self.copy_entry(idx),
+            DoubleClickAction::CopyAndHide => {
+                self.copy_entry(idx);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            }
+            DoubleClickAction::OpenInEditor if is_image => {
+                self.push_toast(ToastKind::Error, "Error: images can't be opened in an editor");
+            }
+            DoubleClickAction::OpenInEditor => {
+                match open_in_editor(&content) {
+                    Ok((path, temp)) => {
+                        self.editor_files.extend(temp);
+                        self.push_toast(ToastKind::Info, format!("Opened {}", path.display()));
+                    }
+                    Err(e) => self.push_toast(ToastKind::Error, format!("Error: {e}")),
+                }
+            }
+        }
+    }
+
+    fn clear_history(&mut self) {
+        let cleared = self.history.clear();
+        self.save_history();
+        self.selected_index = None;
+        self.clear_multi_selection();
+        if !cleared.is_empty() {
+            self.cleared = Some((cleared, Instant::now()));
+        }
+        self.push_toast(ToastKind::Info, "History cleared.");
+    }
+
+    /// Privacy auto-clear: wipe history once the app has been idle for the
+    /// configured time. Unlike the toolbar button, nothing is kept for undo.
+    fn check_idle_clear(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| !i.events.is_empty()) {
+            self.last_activity = Instant::now();
+        }
+        let minutes = self.settings.auto_clear_idle_minutes;
+        if minutes == 0 || self.history.is_empty() {
+            return;
+        }
+        if self.last_activity.elapsed() >= Duration::from_secs(u64::from(minutes) * 60) {
+            self.history.clear();
+            self.save_history();
+            self.cleared = None;
+            self.selected_index = None;
+            self.clear_multi_selection();
+            let message = format!("History auto-cleared after {minutes} min idle.");
+            self.push_toast(ToastKind::Info, message);
+        }
+    }
+
+    /// While recording, take the next key press as the new hotkey (Escape
+    /// cancels). Returns true if a key press was consumed this frame.
+    fn record_hotkey(&mut self, ctx: &egui::Context) -> bool {
+        if !self.recording_hotkey {
+            return false;
+        }
+        let pressed = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            })
+        });
+        let Some((key, modifiers)) = pressed else {
+            return false;
+        };
+        if key == Key::Escape {
+            self.recording_hotkey = false;
+            return true;
+        }
+        let hotkey = HotkeyConfig {
+            ctrl: modifiers.ctrl,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+            key: key.name().to_string(),
+        };
+        // Keep recording so another combination can be tried.
+        if let Err(e) = hotkey.validate() {
+            self.push_toast(ToastKind::Error, format!("Error: {e}"));
+            return true;
+        }
+        self.recording_hotkey = false;
+        self.hotkey = hotkey;
+        match hotkey::save(&self.hotkey, &self.hotkey_path) {
+            Ok(()) => {
+                let done = format!("Hotkey set to {}.", self.hotkey.display());
+                self.push_toast(ToastKind::Success, done);
+            }
+            Err(e) => {
+                self.push_toast(ToastKind::Error, format!("Error: saving hotkey failed: {e}"));
+            }
+        }
+        true
+    }
 
-/// Returns the path where history is persisted.
-/// Linux/others: $XDG_DATA_HOME/clipboard-hack/history.json
-/// macOS:        ~/Library/Application Support/clipboard-hack/history.json
-fn history_file_path() -> PathBuf {
-    #[cfg(target_os = "macos")]
-    {
-        let home = std::env::var("HOME").unwrap_or_default();
-        PathBuf::from(home)
-            .join("Library")
-            .join("Application Support")
-            .join("clipboard-hack")
-            .join("history.json")
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        let base = std::env::var("XDG_DATA_HOME")
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| {
-                let home = std::env::var("HOME").unwrap_or_default();
-                PathBuf::from(home).join(".local").join("share")
-            });
-        base.join("clipboard-hack").join("history.json")
+    /// One flag per interpreter, false for those disabled in Settings.
+    fn interpreters_enabled(&self) -> Vec<bool> {
+        let disabled = &self.settings.disabled_interpreters;
+        self.interpreters.iter().map(|i| !disabled.iter().any(|d| d == i.name())).collect()
     }
-}
 
-impl App {
-    pub fn new(cc: &eframe::CreationContext) -> Self {
-        setup_japanese_font(&cc.egui_ctx);
+    fn toggle_watch_mode(&mut self) {
+        self.settings.watch_mode = !self.settings.watch_mode;
+        self.watcher.set_enabled(self.settings.watch_mode);
+        let state = if self.settings.watch_mode { "Watch mode on." } else { "Watch mode off." };
+        self.push_toast(ToastKind::Info, state);
+        self.save_settings();
+    }
 
-        let (tx, rx) = mpsc::channel();
-        start_hotkey_listener(tx);
+    fn undo_clear_available(&self) -> bool {
+        self.cleared
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() < UNDO_CLEAR_WINDOW)
+    }
 
-        let clipboard = Clipboard::new().ok();
+    fn undo_clear(&mut self) {
+        let Some((cleared, _)) = self.cleared.take() else {
+            return;
+        };
+        let n = cleared.len();
+        self.history.restore(cleared);
+        self.save_history();
+        self.push_toast(ToastKind::Success, format!("Restored {n} entries."));
+    }
 
-        let history_path = history_file_path();
-        let history = ClipboardHistory::load(&history_path, 50);
-        eprintln!("[history] Loaded {} entries from {}", history.len(), history_path.display());
+    /// Plain click selects the row for the detail view. Ctrl/Cmd-click toggles
+    /// it in the multi-selection; Shift-click selects the rows between the
+    /// last clicked one and this, in the order the list shows them.
+    fn click_row(&mut self, idx: usize, modifiers: egui::Modifiers, order: &[usize]) {
+        let anchor = self.selection_anchor.filter(|a| order.contains(a));
+        if modifiers.shift {
+            if let Some(anchor) = anchor {
+                let from = order.iter().position(|&i| i == anchor).unwrap_or(0);
+                let to = order.iter().position(|&i| i == idx).unwrap_or(0);
+                let range = &order[from.min(to)..=from.max(to)];
+                if !modifiers.command {
+                    self.multi_selection.clear();
+                }
+                self.multi_selection.extend(range);
+                return;
+            }
+        } else if modifiers.command {
+            // The row open in the detail view is part of the selection too.
+            if self.multi_selection.is_empty() {
+                self.multi_selection.extend(self.selected_index);
+            }
+            if !self.multi_selection.remove(&idx) {
+                self.multi_selection.insert(idx);
+            }
+            self.selection_anchor = Some(idx);
+            return;
+        }
+        self.selected_index = Some(idx);
+        self.clear_multi_selection();
+        self.selection_anchor = Some(idx);
+    }
 
-        let window_state_path = window_state::window_state_file_path();
+    fn clear_multi_selection(&mut self) {
+        self.multi_selection.clear();
+        self.selection_anchor = None;
+    }
 
-        let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
-        let status = if is_wayland {
-            format!(
-                "Wayland detected. In-app hotkey: {}  |  Global: touch {}",
-                hotkey_display(),
-                TRIGGER_FILE
-            )
-        } else {
-            format!("Ready. Hotkey: {}", hotkey_display())
+    /// Remove every multi-selected entry and save once. Indices are removed
+    /// high to low so the ones still to go stay valid.
+    fn delete_selected_entries(&mut self) {
+        let mut indices: Vec<usize> = self.multi_selection.drain().collect();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        for &idx in &indices {
+            self.history.remove(idx);
+        }
+        self.save_history();
+        self.selected_index = self.selected_index.and_then(|sel| {
+            (!indices.contains(&sel)).then(|| sel - indices.iter().filter(|&&i| i < sel).count())
+        });
+        self.selection_anchor = None;
+        let n = indices.len();
+        self.push_toast(ToastKind::Info, format!("Deleted {n} entries."));
+    }
+
+    fn delete_history_entry(&mut self, idx: usize) {
+        self.history.remove(idx);
+        self.save_history();
+        self.selected_index = match self.selected_index {
+            Some(sel) if sel == idx => None,
+            Some(sel) if sel > idx => Some(sel - 1),
+            other => other,
         };
+        self.multi_selection = self
+            .multi_selection
+            .iter()
+            .filter(|&&sel| sel != idx)
+            .map(|&sel| if sel > idx { sel - 1 } else { sel })
+            .collect();
+        self.selection_anchor = None;
+    }
 
-        Self {
-            history,
-            history_path,
-            selected_index: None,
-            rx,
-            clipboard,
-            interpreters: get_interpreters(),
-            status_message: status,
-            trigger_path: PathBuf::from(TRIGGER_FILE),
-            window_state_path,
-            last_outer_rect: None,
+    /// Multi-line content as numbered, selectable lines. Click selects a line,
+    /// Shift+click extends the selection; the chosen range can be copied.
+    fn draw_content_lines(&mut self, ui: &mut Ui, content: &str, entry_hash: u64) {
+        let lines: Vec<&str> = content.lines().collect();
+        let selection = self.line_selection.filter(|s| s.entry == entry_hash);
+        let number_width = lines.len().to_string().len();
+        let row_h = ui.text_style_height(&egui::TextStyle::Monospace)
+            + ui.spacing().button_padding.y * 2.0;
+
+        let mut clicked: Option<(usize, bool)> = None;
+        ScrollArea::vertical()
+            .id_salt("content_scroll")
+            .max_height(120.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_h, lines.len(), |ui, rows| {
+                for n in rows {
+                    let selected = selection.is_some_and(|s| s.range().contains(&n));
+                    let text = RichText::new(format!("{:>number_width$}  {}", n + 1, lines[n]))
+                        .monospace();
+                    if ui.selectable_label(selected, text).clicked() {
+                        clicked = Some((n, ui.input(|i| i.modifiers.shift)));
+                    }
+                }
+            });
+
+        if let Some((n, extend)) = clicked {
+            self.line_selection = Some(match selection {
+                Some(s) if extend => LineSelection { cursor: n, ..s },
+                _ => LineSelection { entry: entry_hash, anchor: n, cursor: n },
+            });
         }
+
+        ui.horizontal(|ui| {
+            let range = self.line_selection.filter(|s| s.entry == entry_hash).map(|s| s.range());
+            match &range {
+                Some(r) => ui.label(
+                    RichText::new(format!("Lines {}–{} selected", r.start() + 1, r.end() + 1))
+                        .color(Color32::GRAY)
+                        .small(),
+                ),
+                None => ui.label(
+                    RichText::new("Click a line to select, Shift+click to extend")
+                        .color(Color32::GRAY)
+                        .small(),
+                ),
+            };
+            if let Some(r) = range {
+                if ui.small_button("📋 Copy selected lines").clicked() {
+                    let text = lines[r.clone()].join("\n");
+                    let what = format!("lines {}–{}", r.start() + 1, r.end() + 1);
+                    self.copy_to_clipboard(&what, &text);
+                }
+                if ui.small_button("Clear").clicked() {
+                    self.line_selection = None;
+                }
+            }
+        });
     }
 
-    fn save_history(&mut self) {
-        if let Err(e) = self.history.save(&self.history_path) {
-            eprintln!("[history] Save failed: {e}");
+    /// Add each non-empty line of entry `idx` as a separate entry, trimmed if
+    /// `split_trim` is set. The first line ends up selected at the top.
+    fn split_entry(&mut self, idx: usize) {
+        let Some(entry) = self.history.get(idx) else {
+            return;
+        };
+        let source = entry.source();
+        let lines: Vec<String> = entry
+            .content()
+            .lines()
+            .map(|line| if self.split_trim { line.trim() } else { line })
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        let total = lines.len();
+        let added = self.history.add_batch(lines, source);
+        if added > 0 {
+            self.save_history();
+            self.notify_capture();
         }
+        self.selected_index = Some(0);
+        self.clear_multi_selection();
+        let done = match total - added {
+            0 => format!("Split into {added} entries."),
+            skipped => format!("Split into {added} entries ({skipped} already in history)."),
+        };
+        self.push_toast(ToastKind::Success, done);
     }
 
-    fn save_window_state(&mut self) {
-        if let Some(rect) = self.last_outer_rect {
-            let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
-            let state = window_state::WindowState {
-                x: if is_wayland { 0.0 } else { rect.min.x },
-                y: if is_wayland { 0.0 } else { rect.min.y },
-                width: rect.size().x,
-                height: rect.size().y,
-            };
-            if let Err(e) = window_state::save(&state, &self.window_state_path) {
-                eprintln!("[window_state] Save failed: {e}");
-            }
+    /// Single-line note editor for the selected entry; saves on every change.
+    fn draw_note_field(&mut self, ui: &mut Ui, idx: usize) {
+        let mut note = self
+            .history
+            .get(idx)
+            .and_then(ClipboardEntry::note)
+            .unwrap_or_default()
+            .to_string();
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut note)
+                .hint_text("📝 Add a note…")
+                .desired_width(f32::INFINITY),
+        );
+        if edit.changed() && self.history.set_note(idx, &note) {
+            self.save_history();
         }
     }
 
-    fn capture_clipboard(&mut self) {
-        match clipboard_backend::get_text(&mut self.clipboard) {
-            Ok(text) => {
-                if self.history.add(text) {
-                    self.save_history();
+    /// The selected entry's tags as removable chips, followed by a field for
+    /// new ones. A comma or Enter adds what was typed.
+    fn draw_tags_field(&mut self, ui: &mut Ui, idx: usize) {
+        let Some(entry) = self.history.get(idx) else {
+            return;
+        };
+        let hash = entry.hash();
+        let mut tags = entry.tags().to_vec();
+        let mut input = match &self.tag_input {
+            Some((h, text)) if *h == hash => text.clone(),
+            _ => String::new(),
+        };
+        ui.horizontal_wrapped(|ui| {
+            let mut remove = None;
+            for (i, tag) in tags.iter().enumerate() {
+                let chip = RichText::new(format!("{tag} ×")).small();
+                if ui.add(egui::Button::new(chip).rounding(8.0)).on_hover_text("Remove tag").clicked() {
+                    remove = Some(i);
                 }
-                self.status_message = "Captured.".to_string();
-                self.selected_index = Some(0);
             }
-            Err(e) => {
-                self.status_message = format!("Error: {e}");
+            if let Some(i) = remove {
+                tags.remove(i);
+            }
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut input)
+                    .hint_text("🏷 Add tags…")
+                    .desired_width(140.0),
+            );
+            let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if submitted || input.contains(',') {
+                // Everything up to the last comma is done; Enter finishes the rest too.
+                let (done, rest) = match input.rsplit_once(',') {
+                    Some((done, rest)) if !submitted => (done.to_string(), rest.trim_start()),
+                    _ => (input.clone(), ""),
+                };
+                tags.extend(done.split(',').map(String::from));
+                input = rest.to_string();
+                if submitted {
+                    edit.request_focus();
+                }
             }
+        });
+        self.tag_input = Some((hash, input));
+        if self.history.set_tags(idx, tags) {
+            self.save_history();
         }
     }
 
-    fn draw_toolbar(&mut self, ui: &mut Ui) {
-        ui.horizontal(|ui| {
-            if ui.button("📋 Capture Now").clicked() {
-                self.capture_clipboard();
+    /// Toggle chips for every tag in the history, filtering the list to
+    /// entries that carry the selected ones.
+    fn draw_tag_bar(&mut self, ui: &mut Ui) {
+        let all_tags = self.history.all_tags();
+        // Forget selections whose last entry was untagged or deleted.
+        self.tag_filter.retain(|t| all_tags.contains(t));
+        if all_tags.is_empty() {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new("🏷").color(Color32::GRAY));
+            for tag in all_tags {
+                let selected = self.tag_filter.contains(&tag);
+                if ui.selectable_label(selected, RichText::new(&tag).small()).clicked() {
+                    if selected {
+                        self.tag_filter.retain(|t| *t != tag);
+                    } else {
+                        self.tag_filter.push(tag);
+                    }
+                }
             }
-            if ui.button("🗑 Clear History").clicked() {
-                self.history.clear();
-                self.save_history();
-                self.selected_index = None;
-                self.status_message = "History cleared.".to_string();
+            if self.tag_filter.len() > 1 {
+                let mode = if self.tag_match_all { "Match all" } else { "Match any" };
+                let toggle = ui
+                    .small_button(mode)
+                    .on_hover_text("Entries need all of the selected tags, or any one of them");
+                if toggle.clicked() {
+                    self.tag_match_all = !self.tag_match_all;
+                }
             }
-            ui.separator();
-            ui.label(
-                RichText::new(format!("Hotkey: {}", hotkey_display()))
-                    .color(Color32::GRAY),
-            );
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.label(
-                    RichText::new(&self.status_message)
-                        .color(Color32::from_rgb(180, 180, 180))
-                        .italics(),
-                );
-            });
         });
     }
 
-    fn draw_history_panel(&mut self, ui: &mut Ui) {
-        ui.heading("History");
+    /// Detail view for an image entry: a preview and its metadata instead of
+    /// the text views and interpreters.
+    fn draw_image_detail(&mut self, ui: &mut Ui, idx: usize) {
+        let Some(texture) = self.image_texture(ui.ctx(), idx) else {
+            let image = self.history.get(idx).and_then(ClipboardEntry::image);
+            if let Some(Err(e)) = image.map(ClipboardImage::rgba) {
+                ui.colored_label(CHIP_ERROR, format!("Can't read this image: {e}"));
+            }
+            return;
+        };
+        let Some(entry) = self.history.get(idx) else {
+            return;
+        };
+        let captured_at = entry.timestamp_str(&self.timestamp_style);
+        let info = entry.image().map(interpret_image);
+
+        let mut copy = false;
+        ui.horizontal(|ui| {
+            ui.heading("Image");
+            copy = ui.button("📋 Copy to Clipboard").clicked();
+            self.draw_popout_button(ui);
+        });
         ui.label(
-            RichText::new(format!("{} item(s)", self.history.len()))
+            RichText::new(format!("Captured at {captured_at}"))
                 .color(Color32::GRAY)
                 .small(),
         );
+        if self.history.is_encrypted() {
+            let note = "🔒 Not saved to disk while the history is encrypted";
+            ui.label(RichText::new(note).color(CHIP_WARN).small());
+        }
+        self.draw_note_field(ui, idx);
+        self.draw_tags_field(ui, idx);
         ui.separator();
 
-        if self.history.is_empty() {
-            ui.colored_label(
-                Color32::GRAY,
-                "No history yet.\nPress 'Capture Now' or use the hotkey.",
+        ScrollArea::vertical().id_salt("image_scroll").show(ui, |ui| {
+            ui.add(
+                egui::Image::new(&texture)
+                    .max_width(ui.available_width())
+                    .max_height(320.0),
             );
-            return;
-        }
-
-        let mut to_delete: Option<usize> = None;
-
-        ScrollArea::vertical().show(ui, |ui| {
-            let items: Vec<(usize, String, String)> = self
-                .history
-                .entries()
-                .iter()
-                .enumerate()
-                .map(|(i, e)| (i, e.timestamp_str(), e.preview(45)))
-                .collect();
-
-            // Row height: 2 lines of button-style text + vertical padding
-            let font_id = egui::TextStyle::Button.resolve(ui.style());
-            let line_h = ui.fonts(|f| f.row_height(&font_id));
-            let row_h = line_h * 2.0 + ui.spacing().button_padding.y * 2.0;
-
-            for (i, ts, preview) in items {
-                let selected = self.selected_index == Some(i);
-                let label = format!("{}\n{}", ts, preview);
-
-                let (sel_clicked, del_clicked) = ui.horizontal(|ui| {
-                    let avail = ui.available_width();
-                    let btn_w = 20.0;
-                    let gap = ui.spacing().item_spacing.x;
-                    let label_w = (avail - btn_w - gap).max(0.0);
+            ui.add_space(8.0);
+            ui.separator();
+            if let Some(info) = info {
+                egui::Grid::new("image_info")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([8.0, 4.0])
+                    .show(ui, |ui| {
+                        for item in &info.items {
+                            ui.label(RichText::new(&item.label).color(Color32::GRAY));
+                            ui.label(":");
+                            ui.label(&item.value);
+                            ui.end_row();
+                        }
+                    });
+            }
+        });
 
-                    // allocate_ui_with_layout で top_down(LEFT) コンテキストを作る。
-                    // SelectableLabel はこのコンテキストの h_align() = LEFT を参照して
-                    // テキストを左寄せに配置する。
-                    let sel = ui.allocate_ui_with_layout(
-                        egui::vec2(label_w, row_h),
-                        egui::Layout::top_down_justified(egui::Align::LEFT),
-                        |ui| ui.selectable_label(selected, &label),
-                    ).inner;
+        if copy {
+            self.copy_entry(idx);
+        }
+    }
 
-                    let del = ui.add_sized([btn_w, row_h], egui::Button::new("×").small());
-                    (sel.clicked(), del.clicked())
-                }).inner;
+    /// "Pop out" button for the detail header; hidden once popped out.
+    fn draw_popout_button(&mut self, ui: &mut Ui) {
+        if self.popout.is_some() {
+            return;
+        }
+        let button = ui.button("⧉ Pop out").on_hover_text("Show the details in their own window");
+        if button.clicked() {
+            self.popout = Some(PopoutTarget::Selection);
+        }
+    }
 
-                if sel_clicked {
-                    self.selected_index = Some(i);
-                }
-                if del_clicked {
-                    to_delete = Some(i);
-                }
+    /// The central panel while the details are popped out.
+    fn draw_popout_placeholder(&mut self, ui: &mut Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() / 3.0);
+            ui.colored_label(Color32::GRAY, "The details are shown in a separate window.");
+            if ui.button("Bring back").clicked() {
+                self.popout = None;
             }
         });
-
-        if let Some(idx) = to_delete {
-            self.delete_history_entry(idx);
-        }
     }
 
-    fn delete_history_entry(&mut self, idx: usize) {
-        self.history.remove(idx);
-        self.save_history();
-        self.selected_index = match self.selected_index {
-            Some(sel) if sel == idx => None,
-            Some(sel) if sel > idx => Some(sel - 1),
-            other => other,
+    /// The detail view in its own OS window. Rendered with an immediate
+    /// viewport so it shares `self` (caches, note editing) with the main one.
+    /// Closing the window puts the details back into the central panel.
+    fn show_popout(&mut self, ctx: &egui::Context) {
+        let Some(target) = self.popout else {
+            return;
         };
+        let viewport = egui::ViewportBuilder::default()
+            .with_title(format!("{WINDOW_TITLE} — Details"))
+            .with_app_id("clipboard-hack")
+            .with_inner_size([640.0, 720.0])
+            .with_min_inner_size([320.0, 240.0]);
+        let id = egui::ViewportId::from_hash_of("detail_popout");
+        ctx.show_viewport_immediate(id, viewport, |ctx, _class| {
+            if ctx.input(|i| i.viewport().close_requested()) {
+                self.popout = None;
+                return;
+            }
+            egui::TopBottomPanel::top("popout_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut follow = target == PopoutTarget::Selection;
+                    ui.selectable_value(&mut follow, true, "Follow selection");
+                    let selected_hash =
+                        self.selected_index.and_then(|i| self.history.get(i)).map(|e| e.hash());
+                    let pinned_hash = match target {
+                        PopoutTarget::Entry(hash) => Some(hash),
+                        PopoutTarget::Selection => selected_hash,
+                    };
+                    ui.add_enabled_ui(pinned_hash.is_some(), |ui| {
+                        ui.selectable_value(&mut follow, false, "📌 Keep this entry")
+                            .on_hover_text("Stop following the selection in the history list");
+                    });
+                    self.popout = Some(match (follow, pinned_hash) {
+                        (false, Some(hash)) => PopoutTarget::Entry(hash),
+                        _ => PopoutTarget::Selection,
+                    });
+                });
+            });
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let index = match target {
+                    PopoutTarget::Selection => self.selected_index,
+                    PopoutTarget::Entry(hash) => self.history.position(hash),
+                };
+                match (target, index) {
+                    (PopoutTarget::Entry(_), None) => {
+                        ui.centered_and_justified(|ui| {
+                            ui.colored_label(Color32::GRAY, "This entry is no longer in history.");
+                        });
+                    }
+                    _ => self.draw_detail(ui, index),
+                }
+            });
+        });
     }
 
-    fn draw_detail_panel(&mut self, ui: &mut Ui) {
-        if let Some(idx) = self.selected_index {
+    /// Content and interpretations of entry `index`, in the central panel or
+    /// the popped-out window.
+    fn draw_detail(&mut self, ui: &mut Ui, index: Option<usize>) {
+        if let Some(idx) = index {
+            if self.history.get(idx).is_some_and(|e| e.image().is_some()) {
+                self.draw_image_detail(ui, idx);
+                return;
+            }
             if let Some(entry) = self.history.get(idx) {
                 let content = entry.content().to_string();
-                let captured_at = entry.timestamp_str();
+                let captured_at = match entry.source() {
+                    Source::Clipboard => entry.timestamp_str(&self.timestamp_style),
+                    Source::Primary => format!(
+                        "{} from the primary selection",
+                        entry.timestamp_str(&self.timestamp_style)
+                    ),
+                };
+                let entry_hash = entry.hash();
+                let persist_note = match entry.persist() {
+                    Some(Persist::Redacted(_)) => Some("Saved with secrets redacted"),
+                    Some(Persist::Skip) => Some("Holds a secret; not saved to disk"),
+                    Some(Persist::Full) | None => None,
+                };
 
-                ui.heading("Content");
+                let mut split_request = false;
+                ui.horizontal(|ui| {
+                    ui.heading("Content");
+                    let wrap = ui
+                        .checkbox(&mut self.settings.wrap_content, "Wrap")
+                        .on_hover_text("Wrap long lines (line selection needs wrapping off)");
+                    if wrap.changed() {
+                        self.save_settings();
+                    }
+                    if ui.button("📋 Copy to Clipboard").clicked() {
+                        self.copy_to_clipboard("entry", &content);
+                    }
+                    self.draw_popout_button(ui);
+                    if content.lines().nth(1).is_some() {
+                        ui.menu_button("✂ Split", |ui| {
+                            ui.checkbox(&mut self.split_trim, "Trim each line");
+                            if ui.button("Split into entries").clicked() {
+                                split_request = true;
+                                ui.close_menu();
+                            }
+                        })
+                        .response
+                        .on_hover_text("Add each non-empty line as its own entry");
+                    }
+                });
+                if split_request {
+                    self.split_entry(idx);
+                    return;
+                }
+                let stats = match &self.stats_cache {
+                    Some((hash, stats)) if *hash == entry_hash => stats.clone(),
+                    _ => {
+                        let stats = content_stats(&content);
+                        self.stats_cache = Some((entry_hash, stats.clone()));
+                        stats
+                    }
+                };
+                ui.label(RichText::new(stats).color(Color32::GRAY).small());
+                let kind = match self.kind_cache {
+                    Some((hash, kind)) if hash == entry_hash => kind,
+                    _ => {
+                        let kind = detect::detect_kind(&content);
+                        self.kind_cache = Some((entry_hash, kind));
+                        kind
+                    }
+                };
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Looks like:").color(Color32::GRAY));
+                    ui.label(RichText::new(kind).strong());
+                });
                 ui.label(
                     RichText::new(format!("Captured at {captured_at}"))
                         .color(Color32::GRAY)
                         .small(),
                 );
+                if let Some(note) = persist_note {
+                    ui.label(RichText::new(format!("🔒 {note}")).color(CHIP_WARN).small());
+                }
+                self.draw_note_field(ui, idx);
+                self.draw_tags_field(ui, idx);
                 ui.separator();
 
-                ScrollArea::vertical()
-                    .id_salt("content_scroll")
-                    .max_height(120.0)
+                if self.settings.wrap_content {
+                    ScrollArea::vertical()
+                        .id_salt("content_scroll")
+                        .max_height(120.0)
+                        .show(ui, |ui| {
+                            // A `&str` buffer makes the TextEdit read-only but still selectable.
+                            ui.add(
+                                egui::TextEdit::multiline(&mut content.as_str())
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(1),
+                            );
+                        });
+                } else if content.lines().nth(1).is_some() {
+                    self.draw_content_lines(ui, &content, entry_hash);
+                } else {
+                    ScrollArea::vertical()
+                        .id_salt("content_scroll")
+                        .max_height(120.0)
+                        .show(ui, |ui| {
+                            ui.code(&content);
+                        });
+                }
+
+                egui::CollapsingHeader::new("Byte histogram")
+                    .id_salt("byte_histogram")
+                    .default_open(false)
                     .show(ui, |ui| {
-                        ui.code(&content);
+                        let counts = match self.histogram_cache {
+                            Some((hash, counts)) if hash == entry_hash => counts,
+                            _ => {
+                                let counts = hex::byte_histogram(content.as_bytes());
+                                self.histogram_cache = Some((entry_hash, counts));
+                                counts
+                            }
+                        };
+                        draw_byte_histogram(ui, &counts);
                     });
 
                 ui.add_space(8.0);
@@ -311,70 +3020,199 @@ impl App {
                 ui.heading("Interpretations");
                 ui.add_space(4.0);
 
-                let results: Vec<(&str, Option<Vec<InterpretItem>>)> = self
-                    .interpreters
-                    .iter()
-                    .map(|interp| {
-                        let name = interp.name();
-                        let result = interp.interpret(&content).map(|r| r.items);
-                        (name, result)
-                    })
-                    .collect();
+                let interp_ctx = InterpretContext {
+                    max_dump_lines: if self.expanded_entry == Some(entry_hash) {
+                        None
+                    } else {
+                        Some(self.settings.hex_dump_max_lines)
+                    },
+                    hex_uppercase: self.settings.hex_uppercase,
+                };
+                self.interpret_cache.set_enabled(self.interpreters_enabled());
+                let Some(cached) = self.interpret_cache.results(&content, entry_hash, interp_ctx)
+                else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(RichText::new("Interpreting…").color(Color32::GRAY));
+                    });
+                    return;
+                };
+                let disabled = &self.settings.disabled_interpreters;
+                let mut results: Vec<(&str, Option<&InterpretResult>)> =
+                    interpreter_order(&self.interpreters, &self.settings.interpreter_order)
+                        .into_iter()
+                        .map(|i| (self.interpreters[i].name(), cached[i].as_ref()))
+                        .filter(|(name, _)| !disabled.iter().any(|d| d == name))
+                        .collect();
+                // Pinned sections first (applicable before not), then the saved order.
+                let is_pinned =
+                    |name: &str| self.settings.pinned_interpreters.iter().any(|p| p == name);
+                results.sort_by_key(|(name, result)| {
+                    let pinned = is_pinned(name);
+                    (!pinned, pinned && result.is_none())
+                });
+
+                // Quick links to the sections that matched; clicking one opens it
+                // and scrolls it to the top of the list below.
+                let mut jump_to: Option<&str> = None;
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(RichText::new("Matched:").color(Color32::GRAY).small());
+                    for (name, _) in results.iter().filter(|(_, r)| r.is_some()) {
+                        let chip = egui::Button::new(RichText::new(*name).small()).rounding(8.0);
+                        if ui.add(chip).on_hover_text("Jump to section").clicked() {
+                            jump_to = Some(name);
+                        }
+                    }
+                });
+                ui.add_space(4.0);
+
+                // Deferred until after drawing: `results` borrows `self.interpreters`
+                // and `self.interpret_cache`.
+                let mut copy_request: Option<(String, String)> = None;
+                let mut expand_request = false;
+                let mut pin_toggle: Option<String> = None;
 
                 ScrollArea::vertical()
                     .id_salt("interp_scroll")
                     .show(ui, |ui| {
-                        for (name, maybe_items) in results {
-                            let header_text = if maybe_items.is_some() {
-                                RichText::new(name).strong()
+                        for (name, maybe_result) in results {
+                            let header_text = if maybe_result.is_some() {
+                                match accent_color32(self.settings.accent_color) {
+                                    Some(accent) => RichText::new(name).strong().color(accent),
+                                    None => RichText::new(name).strong(),
+                                }
                             } else {
                                 RichText::new(format!("{name}  (not applicable)"))
                                     .color(Color32::from_rgb(120, 120, 120))
                             };
 
-                            egui::CollapsingHeader::new(header_text)
-                                .default_open(maybe_items.is_some())
-                                .show(ui, |ui| {
-                                    if let Some(items) = maybe_items {
-                                        egui::Grid::new(format!("grid_{name}"))
-                                            .num_columns(3)
-                                            .striped(true)
-                                            .spacing([8.0, 4.0])
-                                            .show(ui, |ui| {
-                                                for item in &items {
-                                                    ui.label(
-                                                        RichText::new(&item.label)
-                                                            .color(Color32::GRAY),
-                                                    );
-                                                    ui.label(":");
-                                                    ui.horizontal(|ui| {
-                                                        if let Some(rgba) = item.color {
-                                                            let color =
-                                                                Color32::from_rgba_unmultiplied(
-                                                                    rgba[0], rgba[1], rgba[2],
-                                                                    rgba[3],
-                                                                );
-                                                            let (rect, _) = ui.allocate_exact_size(
-                                                                egui::vec2(16.0, 16.0),
-                                                                egui::Sense::hover(),
+                            let id = ui.make_persistent_id(("interp_section", name));
+                            let jump = jump_to == Some(name);
+                            let mut header =
+                                egui::collapsing_header::CollapsingState::load_with_default_open(
+                                    ui.ctx(),
+                                    id,
+                                    maybe_result.is_some(),
+                                )
+                                .show_header(ui, |ui| {
+                                    ui.label(header_text);
+                                    let pinned = is_pinned(name);
+                                    let pin = ui
+                                        .selectable_label(pinned, "📌")
+                                        .on_hover_text(if pinned { "Unpin" } else { "Pin to top" });
+                                    if pin.clicked() {
+                                        pin_toggle = Some(name.to_string());
+                                    }
+                                });
+                            if jump {
+                                header.set_open(true);
+                            }
+                            let (_, header_response, _) = header.body(|ui| {
+                                if let Some(result) = maybe_result {
+                                    egui::Grid::new(format!("grid_{name}"))
+                                        .num_columns(4)
+                                        .striped(true)
+                                        .spacing([8.0, 4.0])
+                                        .show(ui, |ui| {
+                                            for item in &result.items {
+                                                ui.label(
+                                                    RichText::new(&item.label)
+                                                        .color(Color32::GRAY),
+                                                );
+                                                ui.label(":");
+                                                ui.horizontal(|ui| {
+                                                    if let Some(rgba) = item.color {
+                                                        let color =
+                                                            Color32::from_rgba_unmultiplied(
+                                                                rgba[0], rgba[1], rgba[2],
+                                                                rgba[3],
+                                                            );
+                                                        let (rect, _) = ui.allocate_exact_size(
+                                                            egui::vec2(16.0, 16.0),
+                                                            egui::Sense::hover(),
+                                                        );
+                                                        ui.painter()
+                                                            .rect_filled(rect, 3.0, color);
+                                                    }
+                                                    if let Some(series) = &item.series {
+                                                        draw_sparkline(ui, series);
+                                                    }
+                                                    match &item.line_colors {
+                                                        Some(colors) => {
+                                                            let job = colored_lines(
+                                                                ui,
+                                                                &item.value,
+                                                                colors,
                                                             );
-                                                            ui.painter()
-                                                                .rect_filled(rect, 3.0, color);
+                                                            ui.label(job);
                                                         }
-                                                        ui.code(&item.value);
-                                                    });
-                                                    ui.end_row();
+                                                        None => {
+                                                            ui.code(&item.value);
+                                                        }
+                                                    }
+                                                });
+                                                let copy = ui
+                                                    .small_button("📋")
+                                                    .on_hover_text(format!("Copy {}", item.label));
+                                                if copy.clicked() {
+                                                    copy_request = Some((
+                                                        item.label.clone(),
+                                                        item.value.clone(),
+                                                    ));
                                                 }
-                                            });
-                                    } else {
-                                        ui.colored_label(
-                                            Color32::from_rgb(120, 120, 120),
-                                            "—",
-                                        );
+                                                ui.end_row();
+                                            }
+                                        });
+                                    if result.truncated && ui.button("Show full").clicked() {
+                                        expand_request = true;
                                     }
-                                });
+                                    if !result.actions.is_empty() {
+                                        ui.menu_button("📋 Copy as…", |ui| {
+                                            for action in &result.actions {
+                                                let label = format!(
+                                                    "{}  {}",
+                                                    action.label,
+                                                    menu_preview(&action.text)
+                                                );
+                                                if ui.button(label).clicked() {
+                                                    copy_request = Some((
+                                                        action.label.clone(),
+                                                        action.text.clone(),
+                                                    ));
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                        });
+                                    }
+                                } else {
+                                    ui.colored_label(
+                                        Color32::from_rgb(120, 120, 120),
+                                        "—",
+                                    );
+                                }
+                            });
+                            if jump {
+                                header_response.response.scroll_to_me(Some(egui::Align::TOP));
+                            }
                         }
                     });
+
+                if let Some((label, text)) = copy_request {
+                    self.copy_to_clipboard(&label, &text);
+                }
+                if expand_request {
+                    self.expanded_entry = Some(entry_hash);
+                }
+                if let Some(name) = pin_toggle {
+                    let pinned = &mut self.settings.pinned_interpreters;
+                    match pinned.iter().position(|p| *p == name) {
+                        Some(i) => {
+                            pinned.remove(i);
+                        }
+                        None => pinned.push(name),
+                    }
+                    self.save_settings();
+                }
             }
         } else {
             ui.centered_and_justified(|ui| {
@@ -389,7 +3227,15 @@ impl eframe::App for App {
         let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
         ctx.input(|i| {
             let vp = i.viewport();
-            let is_normal = vp.minimized != Some(true) && vp.maximized != Some(true);
+            if let Some(maximized) = vp.maximized {
+                self.maximized = maximized;
+            }
+            if let Some(fullscreen) = vp.fullscreen {
+                self.fullscreen = fullscreen;
+            }
+            // Only the normal geometry is recorded, so un-maximizing after a
+            // restart returns to it.
+            let is_normal = vp.minimized != Some(true) && !self.maximized && !self.fullscreen;
             if is_normal {
                 if is_wayland {
                     // Wayland: outer_rect は常に None のため screen_rect でサイズのみ記録
@@ -402,23 +3248,82 @@ impl eframe::App for App {
             }
         });
 
-        // 1. rdev-based global hotkey (works on X11 / macOS)
-        while self.rx.try_recv().is_ok() {
-            self.capture_clipboard();
+        self.autosave_window_state();
+        self.handle_zoom_shortcuts(ctx);
+        self.check_idle_clear(ctx);
+
+        if let Some(shortcut) = parse_shortcut(&self.settings.watch_shortcut) {
+            if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                self.toggle_watch_mode();
+            }
+        }
+
+        // 1. Global hotkey (rdev on X11 / macOS, the GlobalShortcuts portal on
+        //    Wayland), plus control socket commands
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                AppEvent::Hotkey => {
+                    let _ = self.capture_clipboard();
+                }
+                AppEvent::PrimaryHotkey => {
+                    let _ = self.capture_primary();
+                }
+                #[cfg(target_os = "linux")]
+                AppEvent::HotkeyPortal(Ok(trigger)) => {
+                    let trigger = if trigger.is_empty() { self.hotkey.display() } else { trigger };
+                    let done =
+                        format!("Global hotkey registered via the desktop portal ({trigger}).");
+                    self.push_toast(ToastKind::Success, done);
+                    self.hotkey_portal = Some(trigger);
+                }
+                #[cfg(target_os = "linux")]
+                AppEvent::HotkeyPortal(Err(e)) => {
+                    let message = format!("Error: global shortcuts portal: {e}; using rdev");
+                    self.push_toast(ToastKind::Error, message);
+                }
+                AppEvent::ClipboardChanged(text, backend) => {
+                    let _ = self.add_capture(
+                        Ok((text, backend)),
+                        Source::Clipboard,
+                        "Captured (watch).",
+                    );
+                }
+                #[cfg(unix)]
+                AppEvent::Control(cmd, reply) => {
+                    let _ = reply.send(self.handle_control_command(cmd));
+                }
+            }
         }
 
-        // 2. In-app keyboard shortcut: Ctrl+Shift+H (works on Wayland when app is focused)
-        if ctx.input(|i| {
-            i.modifiers == Modifiers::CTRL | Modifiers::SHIFT && i.key_pressed(Key::H)
-        }) {
-            self.capture_clipboard();
+        // 2. In-app keyboard shortcut, same combination as the global hotkey
+        //    (works on Wayland when app is focused)
+        if !self.record_hotkey(ctx) {
+            let modifiers = self.hotkey.modifiers();
+            let pressed = |key: Key| {
+                ctx.input(|i| i.modifiers.matches_exact(modifiers) && i.key_pressed(key))
+            };
+            if self.hotkey.egui_key().is_some_and(pressed) {
+                let _ = self.capture_clipboard();
+            }
+            if pressed(Key::P) {
+                let _ = self.capture_primary();
+            }
         }
 
         // 3. File-based trigger: `touch /tmp/clipboard-hack-trigger`
         //    Works with any Wayland compositor hotkey binding.
         if self.trigger_path.exists() {
             let _ = std::fs::remove_file(&self.trigger_path);
-            self.capture_clipboard();
+            let outcome = self.capture_clipboard();
+            self.write_trigger_result(outcome);
+        }
+
+        if std::mem::take(&mut self.attention_pending)
+            && !ctx.input(|i| i.viewport().focused.unwrap_or(true))
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                egui::UserAttentionType::Informational,
+            ));
         }
 
         egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
@@ -432,14 +3337,150 @@ impl eframe::App for App {
                 self.draw_history_panel(ui);
             });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.draw_detail_panel(ui);
-        });
+        let detail_rect = egui::CentralPanel::default()
+            .show(ctx, |ui| {
+                if self.popout.is_some() {
+                    self.draw_popout_placeholder(ui);
+                } else {
+                    self.draw_detail(ui, self.selected_index);
+                }
+            })
+            .response
+            .rect;
+        self.show_popout(ctx);
+
+        if self.show_settings {
+            self.draw_settings_window(ctx);
+        }
+        if self.show_info {
+            self.draw_info_window(ctx);
+        }
+        if self.show_export {
+            self.draw_export_window(ctx);
+        }
+        if self.show_onboarding {
+            self.draw_onboarding_window(ctx);
+        }
+        self.poll_key_jobs();
+        if self.history_locked {
+            self.draw_unlock_window(ctx);
+        }
+        self.draw_toasts(ctx, detail_rect);
 
-        ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        // Keeps polling the trigger file, hotkey events and background jobs.
+        ctx.request_repaint_after(Duration::from_millis(50));
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         self.save_window_state();
+        // Deletes the files handed to the desktop editor.
+        self.editor_files.clear();
+        if let Some(path) = &self.control_socket {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard_backend::MockClipboard;
+
+    fn app_with(contents: Option<&str>, dir: &tempfile::TempDir) -> App {
+        let clipboard =
+            MockClipboard { contents: contents.map(str::to_string), ..Default::default() };
+        let (_tx, rx) = mpsc::channel();
+        let paths = AppPaths {
+            settings: dir.path().join("settings.json"),
+            history: dir.path().join("history.json"),
+            window_state: dir.path().join("window_state.json"),
+            hotkey: dir.path().join("hotkey.json"),
+            trigger: dir.path().join("trigger"),
+        };
+        App::with_paths(&egui::Context::default(), Box::new(clipboard), rx, paths)
+    }
+
+    #[test]
+    fn capture_reads_the_clipboard_into_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(Some("hello"), &dir);
+        assert_eq!(app.capture_clipboard(), Ok(()));
+        assert_eq!(app.history.get(0).map(ClipboardEntry::content), Some("hello"));
+        assert_eq!(app.selected_index, Some(0));
+        assert_eq!(app.backend, Some(Backend::Arboard));
+
+        // The same text again is not a new entry.
+        assert_eq!(app.capture_clipboard(), Ok(()));
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.status_message, "Already the newest entry.");
+
+        // Captures are saved as they happen.
+        let saved = ClipboardHistory::load(&app.history_path, 10, None).unwrap();
+        assert_eq!(saved.get(0).map(ClipboardEntry::content), Some("hello"));
+    }
+
+    #[test]
+    fn capture_from_an_empty_clipboard_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(None, &dir);
+        assert!(app.capture_clipboard().is_err());
+        assert!(app.history.is_empty());
+        assert!(app.status_message.starts_with("Error: "));
+    }
+
+    #[test]
+    fn copy_entry_writes_it_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(Some("first"), &dir);
+        app.capture_clipboard().unwrap();
+        app.clipboard.set_text("second").unwrap();
+        app.capture_clipboard().unwrap();
+
+        app.copy_entry(1);
+        assert_eq!(app.clipboard.get_text().map(|(text, _)| text), Ok("first".to_string()));
+        assert_eq!(app.status_message, "Copied entry.");
+    }
+
+    #[test]
+    fn zoom_from_disk_is_clamped() {
+        let dir = tempfile::tempdir().unwrap();
+        let cases = [
+            ("0", settings::MIN_ZOOM),
+            ("-2.5", settings::MIN_ZOOM),
+            ("1e9", settings::MAX_ZOOM),
+            ("1.5", 1.5),
+        ];
+        for (saved, loaded) in cases {
+            let json = format!("{{\"zoom_factor\": {saved}}}");
+            std::fs::write(dir.path().join("settings.json"), json).unwrap();
+            assert_eq!(app_with(None, &dir).settings.zoom_factor, loaded, "{saved}");
+        }
+    }
+
+    #[test]
+    fn duplicate_image_capture_is_not_reported_as_new() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(None, &dir);
+        let image = ClipboardImage::new(1, 1, vec![0, 0, 255, 255]);
+        app.add_image_capture(image.clone());
+        assert_eq!(app.status_message, "Captured image.");
+
+        app.add_image_capture(image);
+        assert_eq!(app.history.len(), 1);
+        assert_eq!(app.status_message, "Already the newest entry.");
+    }
+
+    #[test]
+    fn control_copy_of_an_image_copies_the_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(Some("text"), &dir);
+        app.history.add_image(ClipboardImage::new(1, 1, vec![255, 0, 0, 255]));
+
+        assert_eq!(app.handle_control_command(ControlCommand::Copy(0)), "ok");
+        let copied = app.clipboard.get_image().unwrap();
+        assert_eq!((copied.width, copied.height), (1, 1));
+        assert_eq!(copied.rgba(), Ok(&[255, 0, 0, 255][..]));
+        // The image's placeholder text never reaches the clipboard.
+        assert_eq!(app.clipboard.get_text().map(|(text, _)| text), Ok("text".to_string()));
     }
 }
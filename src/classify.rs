@@ -0,0 +1,118 @@
+use crate::interpreter::color;
+
+/// Coarse content type of a history entry, used for the list icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    Link,
+    Color([u8; 4]),
+    Json,
+    Code,
+    Number,
+    Path,
+    MultiLine,
+    Text,
+    /// A captured image rather than text; never produced by `classify`.
+    Image,
+}
+
+impl ContentKind {
+    /// Short glyph shown at the start of a history row. Colors draw a swatch instead.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Link => "🔗",
+            Self::Color(_) => "■",
+            Self::Json => "{}",
+            Self::Code => "#",
+            Self::Number => "№",
+            Self::Path => "📁",
+            Self::MultiLine => "¶",
+            Self::Text => "Aa",
+            Self::Image => "🖼",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Link => "Link",
+            Self::Color(_) => "Color",
+            Self::Json => "JSON",
+            Self::Code => "Code",
+            Self::Number => "Number",
+            Self::Path => "Path",
+            Self::MultiLine => "Multi-line text",
+            Self::Text => "Text",
+            Self::Image => "Image",
+        }
+    }
+}
+
+/// Classify `content` with cheap heuristics; more specific kinds win.
+pub fn classify(content: &str) -> ContentKind {
+    let trimmed = content.trim();
+    let single_line = !trimmed.contains('\n');
+
+    if let Some(rgba) = color::parse_rgba(trimmed) {
+        return ContentKind::Color(rgba);
+    }
+    if single_line && !trimmed.contains(char::is_whitespace) && is_link(trimmed) {
+        return ContentKind::Link;
+    }
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde::de::IgnoredAny>(trimmed).is_ok()
+    {
+        return ContentKind::Json;
+    }
+    if single_line && is_number(trimmed) {
+        return ContentKind::Number;
+    }
+    if single_line && (trimmed.starts_with('/') || trimmed.starts_with("~/")) {
+        return ContentKind::Path;
+    }
+    if !single_line {
+        return if looks_like_code(trimmed) { ContentKind::Code } else { ContentKind::MultiLine };
+    }
+    ContentKind::Text
+}
+
+pub fn is_link(s: &str) -> bool {
+    ["http://", "https://", "ftp://", "www."]
+        .iter()
+        .any(|p| s.len() > p.len() && s.get(..p.len()).is_some_and(|h| h.eq_ignore_ascii_case(p)))
+}
+
+pub fn is_number(s: &str) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit() || c == '_');
+    }
+    !s.is_empty() && s.parse::<f64>().is_ok()
+}
+
+/// At least a third of the non-blank lines end like a statement or block.
+fn looks_like_code(s: &str) -> bool {
+    let lines: Vec<&str> = s.lines().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+    let code_lines = lines
+        .iter()
+        .filter(|l| l.ends_with([';', '{', '}', ')', ':']) || l.trim_start().starts_with("//"))
+        .count();
+    code_lines * 3 >= lines.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_ascii_text_is_not_a_link() {
+        assert!(!is_link("café"));
+        assert!(!is_link("日本語のテキスト"));
+        assert!(matches!(classify("café"), ContentKind::Text));
+    }
+
+    #[test]
+    fn links_match_case_insensitively() {
+        assert!(is_link("HTTPS://example.com"));
+        assert!(is_link("www.example.com/é"));
+        assert!(!is_link("https://"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Headless commands on the stored history, for scripts:
+//!   clipboard-hack list              index, timestamp and preview per line
+//!   clipboard-hack get <index>       full content of one entry
+//!   clipboard-hack add -             append stdin as a new entry
+//!   clipboard-hack add <text…>       append the arguments as a new entry
+//!
+//! An encrypted history is opened with `$CLIPBOARD_HACK_PASSPHRASE`. These
+//! work on the file directly; while the GUI is running, prefer the control
+//! socket, since the GUI's next save would overwrite an `add`.
+
+use std::io::{Read as _, Write as _};
+
+use crate::history::{self, ClipboardHistory, Source, TimestampStyle};
+use crate::settings;
+
+const PASSPHRASE_VAR: &str = "CLIPBOARD_HACK_PASSPHRASE";
+
+const USAGE: &str = "usage: clipboard-hack [list | get <index> | add - | add <text…>]\n\
+                     Without arguments the GUI is started.";
+
+/// Run the command in `args` (without the program name).
+pub fn run(args: &[String]) -> Result<(), String> {
+    let settings = settings::load(&settings::settings_file_path());
+    let max = settings.max_history.clamp(settings::MIN_HISTORY_SIZE, settings::MAX_HISTORY_SIZE);
+    let path = history::history_file_path();
+    let passphrase = std::env::var(PASSPHRASE_VAR).ok();
+    let mut history = ClipboardHistory::load(&path, max, passphrase.as_deref())
+        .map_err(|e| format!("{e} (set {PASSPHRASE_VAR})"))?;
+    history.set_dedup_full(settings.dedup_full);
+
+    let mut out = std::io::stdout().lock();
+    let write_err = |e: std::io::Error| e.to_string();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => {
+            // Fixed format so scripts don't depend on the display settings.
+            let style = TimestampStyle::default();
+            for (i, e) in history.entries().iter().enumerate() {
+                writeln!(out, "{i}\t{}\t{}", e.timestamp_str(&style), e.preview(80))
+                    .map_err(write_err)?;
+            }
+        }
+        ["get", index] => {
+            let index: usize = index.parse().map_err(|_| format!("not an index: {index}"))?;
+            let entry = history.get(index).ok_or(format!("no entry at index {index}"))?;
+            if entry.image().is_some() {
+                return Err(format!("entry {index} is an image"));
+            }
+            out.write_all(entry.content().as_bytes()).map_err(write_err)?;
+        }
+        ["add", "-"] => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text).map_err(|e| e.to_string())?;
+            // `echo foo | clipboard-hack add -` shouldn't store the newline.
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            let text = text.strip_suffix('\r').unwrap_or(text);
+            add(&mut history, text.to_string(), &path, &settings)?;
+        }
+        ["add", words @ ..] if !words.is_empty() => {
+            add(&mut history, words.join(" "), &path, &settings)?;
+        }
+        ["help" | "-h" | "--help"] => println!("{USAGE}"),
+        _ => return Err(USAGE.to_string()),
+    }
+    Ok(())
+}
+
+fn add(
+    history: &mut ClipboardHistory,
+    text: String,
+    path: &std::path::Path,
+    settings: &settings::Settings,
+) -> Result<(), String> {
+    if text.is_empty() {
+        return Err("nothing to add".to_string());
+    }
+    if history.add(text, Source::Clipboard) {
+        history.check_secrets(settings.secret_policy, &settings.secret_rules);
+        history.save(path)?;
+    }
+    Ok(())
+}
@@ -1,14 +1,148 @@
-/// Platform-aware clipboard reader.
+use crate::history::ClipboardImage;
+
+/// The mechanism used to read the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Arboard,
+    WlPaste,
+    Xclip,
+    Xsel,
+    Pbpaste,
+}
+
+impl Backend {
+    pub fn label(self) -> &'static str {
+        match self {
+            Backend::Arboard => "arboard",
+            Backend::WlPaste => "wl-paste",
+            Backend::Xclip => "xclip",
+            Backend::Xsel => "xsel",
+            Backend::Pbpaste => "pbpaste",
+        }
+    }
+}
+
+/// Clipboard operations the app needs, so the real system clipboard can be
+/// swapped for a mock.
+pub trait ClipboardAccess: Send {
+    /// Read the current clipboard text and report which backend served it.
+    fn get_text(&mut self) -> Result<(String, Backend), String>;
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+    /// Read the PRIMARY selection (highlighted text) without touching CLIPBOARD.
+    fn get_primary(&mut self) -> Result<(String, Backend), String> {
+        Err("PRIMARY selection is not supported here".to_string())
+    }
+    /// Read an image from the clipboard.
+    fn get_image(&mut self) -> Result<ClipboardImage, String> {
+        Err("Image capture is not supported here".to_string())
+    }
+    fn set_image(&mut self, _image: &ClipboardImage) -> Result<(), String> {
+        Err("Copying images is not supported here".to_string())
+    }
+    /// The backend expected to serve reads, shown before the first capture.
+    fn backend_hint(&self) -> Option<Backend>;
+}
+
+/// The real clipboard: arboard with CLI-tool fallbacks.
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn new() -> Self {
+        Self { clipboard: arboard::Clipboard::new().ok() }
+    }
+}
+
+impl ClipboardAccess for SystemClipboard {
+    fn get_text(&mut self) -> Result<(String, Backend), String> {
+        get_text(&mut self.clipboard)
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        set_text(&mut self.clipboard, text)
+    }
+
+    fn get_primary(&mut self) -> Result<(String, Backend), String> {
+        get_primary(&mut self.clipboard)
+    }
+
+    fn get_image(&mut self) -> Result<ClipboardImage, String> {
+        get_image(&mut self.clipboard)
+    }
+
+    fn set_image(&mut self, image: &ClipboardImage) -> Result<(), String> {
+        set_image(&mut self.clipboard, image)
+    }
+
+    fn backend_hint(&self) -> Option<Backend> {
+        detect(&self.clipboard)
+    }
+}
+
+/// In-memory clipboard for tests: reads return `contents` (or `image`),
+/// writes replace it.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MockClipboard {
+    pub contents: Option<String>,
+    pub image: Option<ClipboardImage>,
+}
+
+#[cfg(test)]
+impl ClipboardAccess for MockClipboard {
+    fn get_text(&mut self) -> Result<(String, Backend), String> {
+        match &self.contents {
+            Some(text) => Ok((text.clone(), Backend::Arboard)),
+            None => Err("Mock clipboard is empty".to_string()),
+        }
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        self.contents = Some(text.to_string());
+        Ok(())
+    }
+
+    fn get_image(&mut self) -> Result<ClipboardImage, String> {
+        self.image.clone().ok_or_else(|| "Mock clipboard has no image".to_string())
+    }
+
+    fn set_image(&mut self, image: &ClipboardImage) -> Result<(), String> {
+        self.image = Some(image.clone());
+        Ok(())
+    }
+
+    fn backend_hint(&self) -> Option<Backend> {
+        Some(Backend::Arboard)
+    }
+}
+
+/// Best guess of the backend a read will use, before any read has happened.
+pub fn detect(clipboard: &Option<arboard::Clipboard>) -> Option<Backend> {
+    if clipboard.is_some() {
+        Some(Backend::Arboard)
+    } else if std::env::var("WAYLAND_DISPLAY").is_ok() {
+        Some(Backend::WlPaste)
+    } else if std::env::var("DISPLAY").is_ok() {
+        Some(Backend::Xclip)
+    } else if cfg!(target_os = "macos") {
+        Some(Backend::Pbpaste)
+    } else {
+        None
+    }
+}
+
+/// Platform-aware clipboard reader. Returns the text and the backend that produced it.
 ///
 /// Tries arboard first, then falls back to CLI tools:
 /// - Wayland: `wl-paste`
 /// - X11:     `xclip` or `xsel`
 /// - macOS:   `pbpaste`
-pub fn get_text(clipboard: &mut Option<arboard::Clipboard>) -> Result<String, String> {
+pub fn get_text(clipboard: &mut Option<arboard::Clipboard>) -> Result<(String, Backend), String> {
     // 1. Try arboard
     if let Some(cb) = clipboard {
         match cb.get_text() {
-            Ok(text) if !text.is_empty() => return Ok(text),
+            Ok(text) if !text.is_empty() => return Ok((text, Backend::Arboard)),
             Ok(_) => {} // empty – try other methods
             Err(_) => {} // failed – try other methods
         }
@@ -18,7 +152,7 @@ pub fn get_text(clipboard: &mut Option<arboard::Clipboard>) -> Result<String, St
     if std::env::var("WAYLAND_DISPLAY").is_ok() {
         if let Ok(text) = run_cmd("wl-paste", &["--no-newline"]) {
             if !text.is_empty() {
-                return Ok(text);
+                return Ok((text, Backend::WlPaste));
             }
         }
     }
@@ -27,13 +161,13 @@ pub fn get_text(clipboard: &mut Option<arboard::Clipboard>) -> Result<String, St
     if std::env::var("DISPLAY").is_ok() {
         if let Ok(text) = run_cmd("xclip", &["-selection", "clipboard", "-out"]) {
             if !text.is_empty() {
-                return Ok(text);
+                return Ok((text, Backend::Xclip));
             }
         }
         // xsel fallback
         if let Ok(text) = run_cmd("xsel", &["--clipboard", "--output"]) {
             if !text.is_empty() {
-                return Ok(text);
+                return Ok((text, Backend::Xsel));
             }
         }
     }
@@ -42,13 +176,143 @@ pub fn get_text(clipboard: &mut Option<arboard::Clipboard>) -> Result<String, St
     #[cfg(target_os = "macos")]
     if let Ok(text) = run_cmd("pbpaste", &[]) {
         if !text.is_empty() {
-            return Ok(text);
+            return Ok((text, Backend::Pbpaste));
         }
     }
 
     Err("Could not read clipboard (arboard failed and no CLI tool available)".to_string())
 }
 
+/// Read the X11/Wayland PRIMARY selection. This is a separate buffer from
+/// CLIPBOARD, so reading it leaves the user's copied content untouched.
+///
+/// Tries arboard first, then `wl-paste --primary`, `xclip` or `xsel`.
+#[cfg(target_os = "linux")]
+pub fn get_primary(
+    clipboard: &mut Option<arboard::Clipboard>,
+) -> Result<(String, Backend), String> {
+    use arboard::{GetExtLinux as _, LinuxClipboardKind};
+
+    if let Some(cb) = clipboard {
+        if let Ok(text) = cb.get().clipboard(LinuxClipboardKind::Primary).text() {
+            if !text.is_empty() {
+                return Ok((text, Backend::Arboard));
+            }
+        }
+    }
+
+    if std::env::var("WAYLAND_DISPLAY").is_ok() {
+        if let Ok(text) = run_cmd("wl-paste", &["--primary", "--no-newline"]) {
+            if !text.is_empty() {
+                return Ok((text, Backend::WlPaste));
+            }
+        }
+    }
+
+    if std::env::var("DISPLAY").is_ok() {
+        if let Ok(text) = run_cmd("xclip", &["-selection", "primary", "-out"]) {
+            if !text.is_empty() {
+                return Ok((text, Backend::Xclip));
+            }
+        }
+        if let Ok(text) = run_cmd("xsel", &["--primary", "--output"]) {
+            if !text.is_empty() {
+                return Ok((text, Backend::Xsel));
+            }
+        }
+    }
+
+    Err("Could not read the PRIMARY selection (nothing highlighted?)".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_primary(
+    _clipboard: &mut Option<arboard::Clipboard>,
+) -> Result<(String, Backend), String> {
+    Err("PRIMARY selection is only available on Linux".to_string())
+}
+
+/// Title of the focused window, to note where a capture came from. X11
+/// only, through `xdotool`; Wayland has no portable way to ask, so this is
+/// `None` there, elsewhere, and when xdotool isn't installed.
+#[cfg(target_os = "linux")]
+pub fn active_window_name() -> Option<String> {
+    if std::env::var("WAYLAND_DISPLAY").is_ok() || std::env::var("DISPLAY").is_err() {
+        return None;
+    }
+    let name = run_cmd("xdotool", &["getactivewindow", "getwindowname"]).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn active_window_name() -> Option<String> {
+    None
+}
+
+/// Read an image from the clipboard. Only arboard can do this; there is no
+/// CLI fallback.
+pub fn get_image(clipboard: &mut Option<arboard::Clipboard>) -> Result<ClipboardImage, String> {
+    let Some(cb) = clipboard else {
+        return Err("Clipboard unavailable (arboard failed to initialise)".to_string());
+    };
+    let data = cb.get_image().map_err(|e| e.to_string())?;
+    Ok(ClipboardImage::new(data.width, data.height, data.bytes.into_owned()))
+}
+
+pub fn set_image(
+    clipboard: &mut Option<arboard::Clipboard>,
+    image: &ClipboardImage,
+) -> Result<(), String> {
+    let Some(cb) = clipboard else {
+        return Err("Clipboard unavailable (arboard failed to initialise)".to_string());
+    };
+    cb.set_image(arboard::ImageData {
+        width: image.width,
+        height: image.height,
+        bytes: std::borrow::Cow::Borrowed(image.rgba()?),
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Platform-aware clipboard writer.
+///
+/// Tries arboard first, then falls back to CLI tools:
+/// - Wayland: `wl-copy`
+/// - X11:     `xclip` or `xsel`
+/// - macOS:   `pbcopy`
+pub fn set_text(clipboard: &mut Option<arboard::Clipboard>, text: &str) -> Result<(), String> {
+    // 1. Try arboard
+    if let Some(cb) = clipboard {
+        if cb.set_text(text.to_string()).is_ok() {
+            return Ok(());
+        }
+    }
+
+    // 2. Wayland: wl-copy
+    if std::env::var("WAYLAND_DISPLAY").is_ok() && run_cmd_with_input("wl-copy", &[], text).is_ok() {
+        return Ok(());
+    }
+
+    // 3. X11: xclip, then xsel
+    if std::env::var("DISPLAY").is_ok() {
+        if run_cmd_with_input("xclip", &["-selection", "clipboard", "-in"], text).is_ok() {
+            return Ok(());
+        }
+        if run_cmd_with_input("xsel", &["--clipboard", "--input"], text).is_ok() {
+            return Ok(());
+        }
+    }
+
+    // 4. macOS: pbcopy
+    #[cfg(target_os = "macos")]
+    if run_cmd_with_input("pbcopy", &[], text).is_ok() {
+        return Ok(());
+    }
+
+    Err("Could not write clipboard (arboard failed and no CLI tool available)".to_string())
+}
+
 fn run_cmd(program: &str, args: &[&str]) -> Result<String, String> {
     let out = std::process::Command::new(program)
         .args(args)
@@ -60,3 +324,27 @@ fn run_cmd(program: &str, args: &[&str]) -> Result<String, String> {
         Err(String::from_utf8_lossy(&out.stderr).to_string())
     }
 }
+
+fn run_cmd_with_input(program: &str, args: &[&str], input: &str) -> Result<(), String> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    // stdout/stderr are discarded: wl-copy and xclip fork a process that keeps
+    // serving the selection, and it would hold any captured pipe open forever.
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} exited with {status}"))
+    }
+}
@@ -0,0 +1,151 @@
+//! Optional Unix domain socket for scripting the app.
+//!
+//! One command per connection: the client writes a single line, the app
+//! replies and closes the connection, e.g.
+//!   echo list | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/clipboard-hack.sock
+//!
+//! Compositor keybindings can run `echo capture | socat …` instead of touching
+//! the trigger file, and scripts can read the newest entry with `get-latest`.
+
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use crate::event::AppEvent;
+
+/// How long a client waits for the UI thread to answer.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Clients are served one at a time, so one that stops sending or reading
+/// is dropped after this long instead of blocking the others.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest command line accepted, `interpret <text>` included.
+const MAX_LINE_BYTES: u64 = 1024 * 1024;
+
+/// A parsed control-socket command.
+pub enum ControlCommand {
+    Capture,
+    CapturePrimary,
+    /// Reply with the full content of the newest entry.
+    GetLatest,
+    List,
+    Copy(usize),
+    Interpret(String),
+}
+
+impl ControlCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (cmd, arg) = line.split_once(' ').unwrap_or((line, ""));
+        match cmd {
+            "capture" => Ok(Self::Capture),
+            "capture-primary" => Ok(Self::CapturePrimary),
+            "get-latest" => Ok(Self::GetLatest),
+            "list" => Ok(Self::List),
+            "copy" => arg
+                .trim()
+                .parse()
+                .map(Self::Copy)
+                .map_err(|_| format!("copy expects an index, got {arg:?}")),
+            "interpret" => Ok(Self::Interpret(arg.to_string())),
+            _ => Err(format!(
+                "unknown command {cmd:?} (try capture, capture-primary, get-latest, list, \
+                 copy <index>, interpret <text>)"
+            )),
+        }
+    }
+}
+
+/// Returns the control socket path.
+/// $XDG_RUNTIME_DIR/clipboard-hack.sock, or the temp dir when that is unset.
+pub fn socket_path() -> PathBuf {
+    std::env::var("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join("clipboard-hack.sock")
+}
+
+/// Binds the control socket and spawns a thread serving it. Commands are
+/// forwarded on `tx`; `ctx` is woken so they're handled even while idle.
+/// The caller removes the returned socket file on exit.
+pub fn start_control_listener(tx: Sender<AppEvent>, ctx: egui::Context) -> Result<PathBuf, String> {
+    let path = socket_path();
+    // A previous instance that crashed leaves the socket file behind.
+    if path.exists() && UnixStream::connect(&path).is_err() {
+        let _ = std::fs::remove_file(&path);
+    }
+    let listener = UnixListener::bind(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = handle_client(stream, &tx, &ctx) {
+                        eprintln!("[control] Client error: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[control] Accept error: {e}"),
+            }
+        }
+    });
+    Ok(path)
+}
+
+fn handle_client(
+    stream: UnixStream,
+    tx: &Sender<AppEvent>,
+    ctx: &egui::Context,
+) -> Result<(), String> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT)).map_err(|e| e.to_string())?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_LINE_BYTES))
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+
+    let reply = match ControlCommand::parse(&line) {
+        Ok(cmd) => {
+            let (reply_tx, reply_rx) = mpsc::channel();
+            tx.send(AppEvent::Control(cmd, reply_tx)).map_err(|e| e.to_string())?;
+            ctx.request_repaint();
+            reply_rx
+                .recv_timeout(REPLY_TIMEOUT)
+                .unwrap_or_else(|_| "error: app did not respond".to_string())
+        }
+        Err(e) => format!("error: {e}"),
+    };
+
+    let mut stream = stream;
+    stream.write_all(reply.as_bytes()).map_err(|e| e.to_string())?;
+    if !reply.ends_with('\n') {
+        stream.write_all(b"\n").map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn silent_client_times_out() {
+        let (_client, server) = UnixStream::pair().unwrap();
+        let (tx, _rx) = mpsc::channel();
+        let started = Instant::now();
+        assert!(handle_client(server, &tx, &egui::Context::default()).is_err());
+        assert!(started.elapsed() < CLIENT_TIMEOUT + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn bad_command_gets_an_error_reply() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        client.write_all(b"frobnicate\n").unwrap();
+        let (tx, _rx) = mpsc::channel();
+        handle_client(server, &tx, &egui::Context::default()).unwrap();
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("error: unknown command \"frobnicate\""), "{reply}");
+    }
+}
@@ -0,0 +1,140 @@
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine;
+
+use crate::classify;
+
+/// Shortest unpadded token taken for Base64; shorter ones are usually words.
+const MIN_BASE64_LEN: usize = 16;
+
+/// One-word guess at what `content` is, shown above the raw content before
+/// the interpreters have run. Only cheap checks; the first match wins.
+pub fn detect_kind(content: &str) -> &'static str {
+    let s = content.trim();
+    let single_token = !s.is_empty() && !s.contains(char::is_whitespace);
+
+    if s.starts_with(['{', '[']) && serde_json::from_str::<serde::de::IgnoredAny>(s).is_ok() {
+        return "JSON";
+    }
+    if single_token && is_uuid(s) {
+        return "UUID";
+    }
+    if single_token && is_hex_color(s) {
+        return "Hex color";
+    }
+    if single_token && (classify::is_link(s) || url::Url::parse(s).is_ok_and(|u| u.has_host())) {
+        return "URL";
+    }
+    // `f64` parsing also takes `inf` and `NaN`, which are words here.
+    if single_token && classify::is_number(s) && s.contains(|c: char| c.is_ascii_digit()) {
+        return "Number";
+    }
+    if !s.contains('\n') && is_path(s) {
+        return "File path";
+    }
+    if single_token && is_base64(s) {
+        return "Base64";
+    }
+    "Plain text"
+}
+
+/// Hyphenated, braced or `urn:uuid:` form; 32 bare hex digits are more
+/// often a hash.
+fn is_uuid(s: &str) -> bool {
+    let hyphenated = s.len() == 36 || s.len() == 38 || s.starts_with("urn:uuid:");
+    hyphenated && uuid::Uuid::try_parse(s).is_ok()
+}
+
+/// `#` followed by 3, 4, 6 or 8 hex digits.
+fn is_hex_color(s: &str) -> bool {
+    s.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// Absolute, home-relative, `./`-relative, Windows drive or UNC path.
+fn is_path(s: &str) -> bool {
+    let b = s.as_bytes();
+    let windows_drive =
+        b.len() > 2 && b[0].is_ascii_alphabetic() && b[1] == b':' && matches!(b[2], b'\\' | b'/');
+    windows_drive
+        || (s.starts_with('/') && s.len() > 1)
+        || ["~/", "./", "../", "\\\\"].iter().any(|p| s.starts_with(p))
+}
+
+/// Decodes as standard or URL-safe Base64 and is either padded or long
+/// enough not to be a word. All-hex strings are left alone, being hashes or
+/// hex dumps far more often.
+fn is_base64(s: &str) -> bool {
+    if !s.len().is_multiple_of(4) || s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    if !s.ends_with('=') && s.len() < MIN_BASE64_LEN {
+        return false;
+    }
+    STANDARD.decode(s).is_ok() || URL_SAFE.decode(s).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        assert_eq!(detect_kind(r#"{"name": "café", "n": [1, 2]}"#), "JSON");
+        assert_ne!(detect_kind("{not json"), "JSON");
+    }
+
+    #[test]
+    fn uuid() {
+        assert_eq!(detect_kind("67e55044-10b1-426f-9247-bb680e5fe0c8"), "UUID");
+        // 32 bare hex digits are more likely a hash.
+        assert_ne!(detect_kind("67e5504410b1426f9247bb680e5fe0c8"), "UUID");
+    }
+
+    #[test]
+    fn hex_color() {
+        assert_eq!(detect_kind("#ff5500"), "Hex color");
+        assert_ne!(detect_kind("#ggg"), "Hex color");
+        assert_ne!(detect_kind("#éa"), "Hex color");
+    }
+
+    #[test]
+    fn url() {
+        assert_eq!(detect_kind("https://example.com/path?q=1"), "URL");
+        assert_eq!(detect_kind("www.example.com"), "URL");
+        assert_ne!(detect_kind("example dot com"), "URL");
+        assert_ne!(detect_kind("café"), "URL");
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(detect_kind("-12.5e3"), "Number");
+        assert_eq!(detect_kind("0xff"), "Number");
+        assert_ne!(detect_kind("inf"), "Number");
+        assert_ne!(detect_kind("１２３"), "Number");
+    }
+
+    #[test]
+    fn file_path() {
+        assert_eq!(detect_kind("/usr/local/bin"), "File path");
+        assert_eq!(detect_kind("C:\\Users\\me"), "File path");
+        assert_eq!(detect_kind("~/ドキュメント/メモ.txt"), "File path");
+        assert_ne!(detect_kind("/"), "File path");
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(detect_kind("aGVsbG8gd29ybGQ="), "Base64");
+        assert_ne!(detect_kind("deadbeefdeadbeef"), "Base64");
+        assert_ne!(detect_kind("password"), "Base64");
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(detect_kind("hello world"), "Plain text");
+        assert_eq!(detect_kind("café"), "Plain text");
+        assert_eq!(detect_kind("日本語のテキスト"), "Plain text");
+        assert_eq!(detect_kind(""), "Plain text");
+        assert_ne!(detect_kind("{}"), "Plain text");
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(unix)]
+use std::sync::mpsc::Sender;
+
+use crate::clipboard_backend::Backend;
+#[cfg(unix)]
+use crate::control::ControlCommand;
+
+/// Messages delivered from background threads to the UI thread.
+pub enum AppEvent {
+    /// The global hotkey was pressed.
+    Hotkey,
+    /// The PRIMARY-selection hotkey was pressed.
+    PrimaryHotkey,
+    #[cfg(target_os = "linux")]
+    /// GlobalShortcuts portal registration finished: the trigger the desktop
+    /// assigned, or why the rdev listener is used instead.
+    HotkeyPortal(Result<String, String>),
+    /// Watch mode saw new clipboard text.
+    ClipboardChanged(String, Backend),
+    #[cfg(unix)]
+    /// A command from the control socket; the reply text goes back on the sender.
+    Control(ControlCommand, Sender<String>),
+}
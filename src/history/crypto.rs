@@ -0,0 +1,136 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+/// Start of an encrypted history file. The byte after it is the format version.
+const MAGIC: &[u8] = b"CLIPHACK-ENC";
+const VERSION: u8 = 2;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+/// Argon2id cost (19 MiB, 2 passes, 1 lane). Fixed here rather than stored
+/// in the file, so a tampered header can't make unlocking take forever.
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_PASSES: u32 = 2;
+const ARGON2_LANES: u32 = 1;
+
+/// Key derived from a passphrase. Derivation is slow on purpose, so this is
+/// computed once, off the UI thread, and reused for every save.
+pub struct HistoryKey {
+    salt: [u8; SALT_LEN],
+    key: [u8; 32],
+}
+
+impl HistoryKey {
+    /// Derive a key for encrypting with a fresh random salt.
+    pub fn new(passphrase: &str) -> Result<Self, String> {
+        let mut salt = [0u8; SALT_LEN];
+        getrandom::getrandom(&mut salt).map_err(|e| e.to_string())?;
+        Self::derive(passphrase, salt)
+    }
+
+    fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self, String> {
+        let params = Params::new(ARGON2_MEMORY_KIB, ARGON2_PASSES, ARGON2_LANES, Some(32))
+            .map_err(|e| e.to_string())?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| e.to_string())?;
+        Ok(Self { salt, key })
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.key.into())
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// File layout: MAGIC, version, salt, nonce, then the XChaCha20-Poly1305
+/// ciphertext and tag. The header is authenticated as associated data.
+pub fn encrypt(plaintext: &[u8], key: &HistoryKey) -> Result<Vec<u8>, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut nonce).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::with_capacity(header_len() + plaintext.len() + TAG_LEN);
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&key.salt);
+    out.extend_from_slice(&nonce);
+    let ciphertext = key
+        .cipher()
+        .encrypt(XNonce::from_slice(&nonce), Payload { msg: plaintext, aad: &out })
+        .map_err(|_| "encryption failed".to_string())?;
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a file written by `encrypt`. Also returns the key so later saves
+/// can reuse it without deriving again.
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<(Vec<u8>, HistoryKey), String> {
+    if data.len() < header_len() + TAG_LEN || !is_encrypted(data) {
+        return Err("not an encrypted history file".to_string());
+    }
+    let version = data[MAGIC.len()];
+    if version != VERSION {
+        return Err(format!("unsupported encryption version {version}"));
+    }
+    let (header, ciphertext) = data.split_at(header_len());
+    let salt_start = MAGIC.len() + 1;
+    let salt: [u8; SALT_LEN] = header[salt_start..salt_start + SALT_LEN].try_into().unwrap();
+    let nonce = XNonce::from_slice(&header[salt_start + SALT_LEN..]);
+
+    let key = HistoryKey::derive(passphrase, salt)?;
+    let plaintext = key
+        .cipher()
+        .decrypt(nonce, Payload { msg: ciphertext, aad: header })
+        .map_err(|_| "wrong passphrase (or the file is damaged)".to_string())?;
+    Ok((plaintext, key))
+}
+
+fn header_len() -> usize {
+    MAGIC.len() + 1 + SALT_LEN + NONCE_LEN
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let key = HistoryKey::new("correct horse").unwrap();
+        let data = encrypt(b"[\"secret\"]", &key).unwrap();
+        assert!(is_encrypted(&data));
+        let (plaintext, _) = decrypt(&data, "correct horse").unwrap();
+        assert_eq!(plaintext, b"[\"secret\"]");
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let key = HistoryKey::new("correct horse").unwrap();
+        let data = encrypt(b"[]", &key).unwrap();
+        assert!(decrypt(&data, "battery staple").is_err());
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let key = HistoryKey::new("pass").unwrap();
+        let data = encrypt(b"[]", &key).unwrap();
+        // A flipped bit in the header (salt) or the ciphertext.
+        for at in [MAGIC.len() + 1, data.len() - 1] {
+            let mut tampered = data.clone();
+            tampered[at] ^= 1;
+            assert!(decrypt(&tampered, "pass").is_err());
+        }
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let key = HistoryKey::new("pass").unwrap();
+        let mut data = encrypt(b"[]", &key).unwrap();
+        data[MAGIC.len()] = 1;
+        assert_eq!(decrypt(&data, "pass").err().unwrap(), "unsupported encryption version 1");
+    }
+}
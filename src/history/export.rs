@@ -0,0 +1,151 @@
+use std::io::Write as _;
+use std::path::Path;
+
+use super::ClipboardEntry;
+
+/// Write `entries` to `path`, choosing the format from the extension:
+/// `.json`, `.csv`, anything else is plain text. An existing file is only
+/// replaced with `overwrite`. Returns how many entries were written.
+pub fn export<'a>(
+    entries: impl IntoIterator<Item = &'a ClipboardEntry>,
+    path: &Path,
+    overwrite: bool,
+) -> Result<usize, String> {
+    let entries: Vec<&ClipboardEntry> = entries.into_iter().collect();
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let body = match extension.as_str() {
+        "json" => to_json(&entries)?,
+        "csv" => to_csv(&entries),
+        _ => to_text(&entries),
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(!overwrite)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => format!("{} already exists", path.display()),
+            _ => e.to_string(),
+        })?;
+    file.write_all(body.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(entries.len())
+}
+
+/// ISO-8601 capture time with the local UTC offset.
+fn iso_timestamp(entry: &ClipboardEntry) -> String {
+    entry.captured_local().map(|dt| dt.to_rfc3339()).unwrap_or_default()
+}
+
+/// An array of `{ "captured_at", "kind", "pinned", "content" }` objects, newest first.
+pub fn to_json(entries: &[&ClipboardEntry]) -> Result<String, String> {
+    let values: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "captured_at": iso_timestamp(e),
+                "kind": e.kind().label(),
+                "pinned": e.pinned(),
+                "content": e.content(),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&values).map_err(|e| e.to_string())
+}
+
+/// Each entry under a `--- <timestamp> ---` delimiter line.
+pub fn to_text(entries: &[&ClipboardEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| format!("--- {} ---\n{}\n", iso_timestamp(e), e.content()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// RFC 4180 CSV with `timestamp,content` columns.
+pub fn to_csv(entries: &[&ClipboardEntry]) -> String {
+    let mut out = String::from("timestamp,content\r\n");
+    for e in entries {
+        out.push_str(&csv_field(&iso_timestamp(e)));
+        out.push(',');
+        out.push_str(&csv_field(e.content()));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Quote a field if it contains a comma, quote or line break, doubling
+/// embedded quotes. A leading `=`, `+`, `-` or `@` gets a `'` in front so
+/// spreadsheets show the text instead of evaluating it as a formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_fields_are_left_alone() {
+        assert_eq!(csv_field("hello world"), "hello world");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn commas_are_quoted() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn quotes_are_doubled() {
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn line_breaks_are_quoted() {
+        assert_eq!(csv_field("one\ntwo"), "\"one\ntwo\"");
+        assert_eq!(csv_field("one\r\ntwo"), "\"one\r\ntwo\"");
+    }
+
+    #[test]
+    fn formulas_are_neutralized() {
+        assert_eq!(csv_field("=SUM(A1:A9)"), "'=SUM(A1:A9)");
+        assert_eq!(csv_field("+1 555 0100"), "'+1 555 0100");
+        assert_eq!(csv_field("@cmd"), "'@cmd");
+        assert_eq!(csv_field("=HYPERLINK(\"x\",\"y\")"), "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\"");
+    }
+
+    #[test]
+    fn rows_end_with_crlf() {
+        let entry = ClipboardEntry::new("a,b".to_string());
+        let csv = to_csv(&[&entry]);
+        let mut lines = csv.split("\r\n");
+        assert_eq!(lines.next(), Some("timestamp,content"));
+        assert!(lines.next().unwrap().ends_with(",\"a,b\""));
+        assert_eq!(lines.next(), Some(""));
+    }
+
+    #[test]
+    fn existing_files_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.txt");
+        std::fs::write(&path, "keep me").unwrap();
+        let entry = ClipboardEntry::new("new".to_string());
+        assert!(export([&entry], &path, false).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+        assert_eq!(export([&entry], &path, true), Ok(1));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("new"));
+    }
+}
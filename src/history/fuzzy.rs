@@ -0,0 +1,143 @@
+/// A successful match: higher `score` is better; `positions` are the char
+/// indices in the haystack that matched the query, in order.
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub positions: Vec<usize>,
+}
+
+const SCORE_MATCH: i32 = 16;
+/// Extra for a match directly after the previous one.
+const BONUS_CONSECUTIVE: i32 = 8;
+/// Extra for a match at the start of a word (after a separator or a
+/// lower→upper camelCase step).
+const BONUS_BOUNDARY: i32 = 10;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+
+/// Match `query` against `text`, case-insensitively. Every query character
+/// must appear in `text` in order; `None` otherwise. Whitespace in the query
+/// is ignored, so `"foo bar"` also finds `foo_bar`.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let needle: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if needle.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+
+    // Forward pass: the earliest position where the whole query has matched.
+    // Runs without allocating, so non-matches are rejected cheaply.
+    let mut qi = 0;
+    let mut end = 0;
+    for (i, c) in text.chars().enumerate() {
+        if fold(c) == needle[qi] {
+            qi += 1;
+            if qi == needle.len() {
+                end = i;
+                break;
+            }
+        }
+    }
+    if qi < needle.len() {
+        return None;
+    }
+    // Nothing past `end` takes part in the match.
+    let hay: Vec<char> = text.chars().take(end + 1).collect();
+    let lower: Vec<char> = hay.iter().map(|&c| fold(c)).collect();
+
+    // Backward pass from `end`: the latest start, giving the tightest window.
+    let mut qi = needle.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if lower[i] == needle[qi - 1] {
+            qi -= 1;
+            if qi == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    // Score the window greedily, left to right.
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut score = 0;
+    let mut qi = 0;
+    let mut prev: Option<usize> = None;
+    for (i, &c) in lower.iter().enumerate().skip(start) {
+        if qi == needle.len() {
+            break;
+        }
+        if c != needle[qi] {
+            continue;
+        }
+        score += SCORE_MATCH;
+        if is_boundary(&hay, i) {
+            score += BONUS_BOUNDARY;
+        }
+        match prev {
+            Some(p) if p + 1 == i => score += BONUS_CONSECUTIVE,
+            Some(p) => {
+                let gap = (i - p - 1) as i32;
+                score -= PENALTY_GAP_START + (gap - 1) * PENALTY_GAP_EXTENSION;
+            }
+            None => {}
+        }
+        positions.push(i);
+        prev = Some(i);
+        qi += 1;
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+fn is_boundary(hay: &[char], i: usize) -> bool {
+    let Some(&before) = i.checked_sub(1).and_then(|p| hay.get(p)) else {
+        return true;
+    };
+    let c = hay[i];
+    !before.is_alphanumeric() || (before.is_lowercase() && c.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_match() {
+        let m = fuzzy_match("hel", "Hello world").unwrap();
+        assert_eq!(m.positions, [0, 1, 2]);
+    }
+
+    #[test]
+    fn middle_match() {
+        let m = fuzzy_match("wrd", "hello world").unwrap();
+        assert_eq!(m.positions, [6, 8, 10]);
+        // Whitespace in the query is ignored.
+        assert!(fuzzy_match("foo bar", "x_foo_bar").is_some());
+    }
+
+    #[test]
+    fn no_match() {
+        assert!(fuzzy_match("xyz", "hello world").is_none());
+        // Characters must appear in order.
+        assert!(fuzzy_match("olh", "hello").is_none());
+    }
+
+    #[test]
+    fn tighter_and_boundary_matches_score_higher() {
+        let tight = fuzzy_match("cat", "xcatx").unwrap().score;
+        let loose = fuzzy_match("cat", "xcxaxtx").unwrap().score;
+        assert!(tight > loose);
+        let camel = fuzzy_match("fb", "fooBar").unwrap().score;
+        let inner = fuzzy_match("fb", "fxxbxx").unwrap().score;
+        assert!(camel > inner);
+    }
+
+    #[test]
+    fn non_ascii_positions_are_char_indices() {
+        let m = fuzzy_match("fé", "café").unwrap();
+        assert_eq!(m.positions, [2, 3]);
+    }
+}
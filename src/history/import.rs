@@ -0,0 +1,133 @@
+use std::path::Path;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+/// One entry read from an import file.
+pub struct ImportedEntry {
+    pub content: String,
+    /// Original capture time, when the file records one.
+    pub captured_at: Option<SystemTime>,
+    pub pinned: bool,
+}
+
+/// Element of a JSON export; extra fields (`kind`, …) are ignored.
+#[derive(Deserialize)]
+struct JsonEntry {
+    content: String,
+    #[serde(default)]
+    captured_at: Option<String>,
+    #[serde(default)]
+    pinned: bool,
+}
+
+/// Read entries from a `.json` export (an array of objects with `content`
+/// and optional ISO-8601 `captured_at`) or a text file. Text written by the
+/// plain-text export keeps its `--- <timestamp> ---` sections; any other text
+/// file gives one entry per non-empty line.
+pub fn read_file(path: &Path) -> Result<Vec<ImportedEntry>, String> {
+    let body = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_json = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    if is_json {
+        from_json(&body)
+    } else {
+        Ok(from_text(&body))
+    }
+}
+
+fn from_json(body: &str) -> Result<Vec<ImportedEntry>, String> {
+    let entries: Vec<JsonEntry> = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    Ok(entries
+        .into_iter()
+        .map(|e| ImportedEntry {
+            captured_at: e.captured_at.as_deref().and_then(parse_timestamp),
+            content: e.content,
+            pinned: e.pinned,
+        })
+        .collect())
+}
+
+fn from_text(body: &str) -> Vec<ImportedEntry> {
+    if section_header(body.lines().next().unwrap_or("")).is_none() {
+        return body
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| ImportedEntry { content: l.to_string(), captured_at: None, pinned: false })
+            .collect();
+    }
+    // (timestamp, lines) per `--- <timestamp> ---` section.
+    let mut sections: Vec<(Option<SystemTime>, Vec<&str>)> = Vec::new();
+    for line in body.lines() {
+        match section_header(line) {
+            Some(ts) => sections.push((ts, Vec::new())),
+            None => {
+                if let Some((_, lines)) = sections.last_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    sections
+        .into_iter()
+        .map(|(captured_at, mut lines)| {
+            // The export puts a blank line between sections.
+            while lines.last().is_some_and(|l| l.is_empty()) {
+                lines.pop();
+            }
+            ImportedEntry { content: lines.join("\n"), captured_at, pinned: false }
+        })
+        .collect()
+}
+
+/// `--- 2024-05-01T12:00:00+09:00 ---` → `Some(Some(time))`.
+fn section_header(line: &str) -> Option<Option<SystemTime>> {
+    let inner = line.strip_prefix("--- ")?.strip_suffix(" ---")?;
+    Some(parse_timestamp(inner))
+}
+
+fn parse_timestamp(s: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc3339(s.trim()).ok().map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_keeps_timestamps_and_pins() {
+        let body = r#"[
+            {"captured_at": "2024-05-01T12:00:00+09:00", "content": "a", "pinned": true},
+            {"content": "b"}
+        ]"#;
+        let entries = from_json(body).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].pinned);
+        assert_eq!(
+            entries[0].captured_at,
+            Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_714_532_400))
+        );
+        assert!(!entries[1].pinned);
+        assert_eq!(entries[1].captured_at, None);
+    }
+
+    #[test]
+    fn plain_text_gives_one_entry_per_line() {
+        let entries = from_text("one\n\n  \ntwo\n");
+        let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, ["one", "two"]);
+    }
+
+    #[test]
+    fn text_export_sections_round_trip() {
+        let body = "--- 2024-05-01T12:00:00+09:00 ---\nline 1\nline 2\n\n--- bad ---\nx\n";
+        let entries = from_text(body);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].content, "line 1\nline 2");
+        assert!(entries[0].captured_at.is_some());
+        assert_eq!(entries[1].content, "x");
+        assert_eq!(entries[1].captured_at, None);
+    }
+}
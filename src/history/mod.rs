@@ -0,0 +1,1254 @@
+mod crypto;
+pub mod export;
+pub mod fuzzy;
+pub mod import;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike as _, Local, TimeZone as _, Timelike as _, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::classify::{classify, ContentKind};
+use crate::interpreter::secret;
+use crate::settings::{SecretPolicy, SecretRules};
+
+pub use crypto::HistoryKey;
+
+/// 64-bit FNV-1a hash of `content`. Stable across runs and builds, so it can
+/// be compared against values computed in an earlier session.
+pub fn content_hash(content: &str) -> u64 {
+    bytes_hash(content.bytes())
+}
+
+fn bytes_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes
+        .into_iter()
+        .fold(OFFSET_BASIS, |hash, b| (hash ^ u64::from(b)).wrapping_mul(PRIME))
+}
+
+/// Pixels of an image entry: RGBA8, row-major, no padding. Images loaded
+/// from disk read their PNG on first use, so startup doesn't decode every
+/// screenshot in the history. Clones share the pixels.
+#[derive(Clone)]
+pub struct ClipboardImage {
+    pub width: usize,
+    pub height: usize,
+    pixels: Arc<OnceLock<Result<Vec<u8>, String>>>,
+    /// Where the pixels are read from when they aren't loaded yet.
+    png: Option<PathBuf>,
+}
+
+impl ClipboardImage {
+    pub fn new(width: usize, height: usize, rgba: Vec<u8>) -> Self {
+        Self { width, height, pixels: Arc::new(OnceLock::from(Ok(rgba))), png: None }
+    }
+
+    /// The image in the PNG at `path`. Only the header is read for now.
+    fn open_png(path: &Path) -> Result<Self, String> {
+        let (width, height) = image::image_dimensions(path).map_err(|e| e.to_string())?;
+        Ok(Self {
+            width: width as usize,
+            height: height as usize,
+            pixels: Arc::default(),
+            png: Some(path.to_path_buf()),
+        })
+    }
+
+    /// The pixels, decoded from the PNG on first use. A failure is kept, so
+    /// an unreadable file isn't retried on every frame.
+    pub fn rgba(&self) -> Result<&[u8], String> {
+        let pixels = self.pixels.get_or_init(|| {
+            let path = self.png.as_deref().ok_or("image has no pixels")?;
+            let img = image::open(path).map_err(|e| e.to_string())?.into_rgba8();
+            Ok(img.into_raw())
+        });
+        pixels.as_deref().map_err(String::clone)
+    }
+
+    /// Hash of the size and pixels, fed to FNV-1a piece by piece rather
+    /// than copied into one buffer.
+    fn hash(&self) -> u64 {
+        let dims = format!("{}x{}:", self.width, self.height);
+        let pixels = self.rgba().unwrap_or_default();
+        bytes_hash(dims.bytes().chain(pixels.iter().copied()))
+    }
+
+    fn write_png(&self, path: &Path) -> Result<(), String> {
+        image::save_buffer_with_format(
+            path,
+            self.rgba()?,
+            self.width as u32,
+            self.height as u32,
+            image::ExtendedColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// How `ClipboardHistory::save` writes an entry.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Persist {
+    Full,
+    /// Saved with this content in place of the real one.
+    Redacted(String),
+    /// Kept in memory only.
+    Skip,
+}
+
+/// strftime format of `TimestampStyle::default`.
+pub const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Timezone capture times are shown in.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DisplayZone {
+    Local,
+    Utc,
+    Named(chrono_tz::Tz),
+}
+
+impl DisplayZone {
+    /// `Local`, `UTC` (any case) or an IANA name such as `Europe/Berlin`.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if name.is_empty() || name.eq_ignore_ascii_case("local") {
+            Some(Self::Local)
+        } else if name.eq_ignore_ascii_case("utc") {
+            Some(Self::Utc)
+        } else {
+            name.parse().ok().map(Self::Named)
+        }
+    }
+}
+
+/// How `ClipboardEntry::timestamp_str` renders capture times.
+#[derive(Clone, PartialEq)]
+pub struct TimestampStyle {
+    format: String,
+    zone: DisplayZone,
+}
+
+impl TimestampStyle {
+    /// Style from the settings strings. An invalid format or unknown zone
+    /// falls back to the default for that part.
+    pub fn new(format: &str, zone: &str) -> Self {
+        let format = if is_valid_format(format) { format } else { DEFAULT_TIMESTAMP_FORMAT };
+        Self {
+            format: format.to_string(),
+            zone: DisplayZone::parse(zone).unwrap_or(DisplayZone::Local),
+        }
+    }
+
+    pub fn format_time(&self, time: SystemTime) -> String {
+        let utc = DateTime::<Utc>::from(time);
+        match self.zone {
+            DisplayZone::Local => utc.with_timezone(&Local).format(&self.format).to_string(),
+            DisplayZone::Utc => utc.format(&self.format).to_string(),
+            DisplayZone::Named(tz) => utc.with_timezone(&tz).format(&self.format).to_string(),
+        }
+    }
+}
+
+impl Default for TimestampStyle {
+    fn default() -> Self {
+        Self { format: DEFAULT_TIMESTAMP_FORMAT.to_string(), zone: DisplayZone::Local }
+    }
+}
+
+/// Whether chrono can render `format`. Formatting with a bad specifier
+/// panics in `to_string`, so formats are checked before use.
+pub fn is_valid_format(format: &str) -> bool {
+    !format.trim().is_empty()
+        && StrftimeItems::new(format).all(|item| !matches!(item, Item::Error))
+}
+
+/// Where an entry was captured from.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// The regular copy/paste clipboard (also used for imported entries).
+    #[default]
+    Clipboard,
+    /// The X11/Wayland PRIMARY selection (highlighted text).
+    Primary,
+}
+
+impl Source {
+    pub fn label(self) -> &'static str {
+        match self {
+            Source::Clipboard => "Clipboard",
+            Source::Primary => "Primary selection",
+        }
+    }
+
+    fn is_clipboard(&self) -> bool {
+        *self == Source::Clipboard
+    }
+}
+
+pub struct ClipboardEntry {
+    /// For image entries, a short description such as `Image 800×600`.
+    content: String,
+    hash: u64,
+    kind: ContentKind,
+    captured_at: SystemTime,
+    /// Pinned entries are never evicted and survive `clear`.
+    pinned: bool,
+    image: Option<ClipboardImage>,
+    source: Source,
+    /// User annotation shown in the detail panel and matched by search.
+    note: Option<String>,
+    /// Trimmed, non-empty and unique, in the order they were added.
+    tags: Vec<String>,
+    /// Title of the window that was focused at capture time, when known.
+    source_app: Option<String>,
+    /// Set once the app has checked the entry for secrets; unchecked entries
+    /// are saved in full.
+    persist: Option<Persist>,
+    /// Row id in the SQLite database.
+    #[cfg(feature = "sqlite")]
+    id: u64,
+}
+
+impl ClipboardEntry {
+    pub fn new(content: String) -> Self {
+        Self {
+            hash: content_hash(&content),
+            kind: classify(&content),
+            content,
+            captured_at: SystemTime::now(),
+            pinned: false,
+            image: None,
+            source: Source::Clipboard,
+            note: None,
+            tags: Vec::new(),
+            source_app: None,
+            persist: None,
+            #[cfg(feature = "sqlite")]
+            id: sqlite::next_id(),
+        }
+    }
+
+    pub fn new_image(image: ClipboardImage) -> Self {
+        let hash = image.hash();
+        Self::with_image(image, hash)
+    }
+
+    /// An image entry whose hash is already known, so the pixels needn't be read.
+    fn with_image(image: ClipboardImage, hash: u64) -> Self {
+        Self {
+            content: format!("Image {}×{}", image.width, image.height),
+            hash,
+            kind: ContentKind::Image,
+            captured_at: SystemTime::now(),
+            pinned: false,
+            image: Some(image),
+            source: Source::Clipboard,
+            note: None,
+            tags: Vec::new(),
+            source_app: None,
+            persist: None,
+            #[cfg(feature = "sqlite")]
+            id: sqlite::next_id(),
+        }
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// `content_hash` of the content, computed once at construction.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.pinned
+    }
+
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn source_app(&self) -> Option<&str> {
+        self.source_app.as_deref()
+    }
+
+    pub fn persist(&self) -> Option<&Persist> {
+        self.persist.as_ref()
+    }
+
+    /// What `save` writes for this entry, if anything.
+    fn stored(&self) -> Option<StoredEntry> {
+        let mut stored = StoredEntry::from(self);
+        match &self.persist {
+            Some(Persist::Skip) => return None,
+            Some(Persist::Redacted(content)) => stored.content = content.clone(),
+            Some(Persist::Full) | None => {}
+        }
+        Some(stored)
+    }
+
+    pub fn image(&self) -> Option<&ClipboardImage> {
+        self.image.as_ref()
+    }
+
+    /// File name of the PNG backing an image entry, inside `images/`.
+    fn image_file_name(&self) -> Option<String> {
+        self.image.as_ref().map(|_| format!("{:016x}.png", self.hash))
+    }
+
+    /// Content type, classified once at construction.
+    pub fn kind(&self) -> ContentKind {
+        self.kind
+    }
+
+    /// Cheap equality check: compares hashes first and only falls back to the
+    /// full string when they match.
+    pub fn has_content(&self, content: &str, hash: u64) -> bool {
+        self.hash == hash && self.content == content
+    }
+
+    /// Returns a truncated preview for display in the history list.
+    pub fn preview(&self, max_chars: usize) -> String {
+        let trimmed = self.content.trim();
+        let single_line: String = trimmed
+            .chars()
+            .map(|c| if c == '\n' || c == '\r' || c == '\t' { ' ' } else { c })
+            .collect();
+        if single_line.chars().count() > max_chars {
+            format!("{}…", single_line.chars().take(max_chars).collect::<String>())
+        } else {
+            single_line
+        }
+    }
+
+    /// A longer, multi-line excerpt for tooltips: at most `max_lines` lines and
+    /// `max_chars` characters, with `…` marking anything cut off.
+    pub fn excerpt(&self, max_chars: usize, max_lines: usize) -> String {
+        let lines: Vec<&str> = self.content.trim_end().lines().collect();
+        let joined = lines[..lines.len().min(max_lines)].join("\n");
+        let mut out: String = joined.chars().take(max_chars).collect();
+        if lines.len() > max_lines || out.len() < joined.len() {
+            out.push('…');
+        }
+        out
+    }
+
+    /// Capture time formatted with `style`.
+    pub fn timestamp_str(&self, style: &TimestampStyle) -> String {
+        style.format_time(self.captured_at)
+    }
+
+    /// Capture time in the system local timezone.
+    fn captured_local(&self) -> Option<DateTime<Local>> {
+        let unix_secs = self
+            .captured_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Local.timestamp_opt(unix_secs, 0).single()
+    }
+}
+
+/// Usage summary derived from the capture timestamps.
+pub struct CaptureStats {
+    pub today: usize,
+    /// Captures since Monday 00:00 of the current (local) week.
+    pub this_week: usize,
+    pub total: usize,
+    /// Local hour of day (0–23) with the most captures, and its count.
+    pub busiest_hour: Option<(u32, usize)>,
+}
+
+/// History file name; a database when built with the `sqlite` feature.
+#[cfg(not(feature = "sqlite"))]
+const HISTORY_FILE_NAME: &str = "history.json";
+#[cfg(feature = "sqlite")]
+const HISTORY_FILE_NAME: &str = "history.db";
+
+/// Returns the path where history is persisted.
+/// Linux/others: $XDG_DATA_HOME/clipboard-hack/history.json
+/// macOS:        ~/Library/Application Support/clipboard-hack/history.json
+/// With the `sqlite` feature the file is `history.db` instead.
+pub fn history_file_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        let home = std::env::var("HOME").unwrap_or_default();
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("clipboard-hack")
+            .join(HISTORY_FILE_NAME)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let base = std::env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_default();
+                PathBuf::from(home).join(".local").join("share")
+            });
+        base.join("clipboard-hack").join(HISTORY_FILE_NAME)
+    }
+}
+
+// ── Serialization helpers ─────────────────────────────────────────────────────
+
+/// JSON-friendly representation of a single history entry.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+struct StoredEntry {
+    content: String,
+    unix_secs: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pinned: bool,
+    /// PNG file in the `images/` directory next to the history file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(default, skip_serializing_if = "Source::is_clipboard")]
+    source: Source,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_app: Option<String>,
+}
+
+impl From<&ClipboardEntry> for StoredEntry {
+    fn from(e: &ClipboardEntry) -> Self {
+        let unix_secs = e
+            .captured_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        StoredEntry {
+            content: e.content.clone(),
+            unix_secs,
+            pinned: e.pinned,
+            image: e.image_file_name(),
+            source: e.source,
+            note: e.note.clone(),
+            tags: e.tags.clone(),
+            source_app: e.source_app.clone(),
+        }
+    }
+}
+
+impl StoredEntry {
+    /// Rebuild the entry, opening its PNG in `images_dir` for image entries.
+    /// Returns `None` if the image file is missing or unreadable.
+    fn into_entry(self, images_dir: &Path) -> Option<ClipboardEntry> {
+        let captured_at = SystemTime::UNIX_EPOCH + Duration::from_secs(self.unix_secs);
+        let entry = match self.image {
+            Some(file) => match ClipboardImage::open_png(&images_dir.join(&file)) {
+                // The file is named after the hash (see `image_file_name`).
+                Ok(image) => match file.strip_suffix(".png").map(|h| u64::from_str_radix(h, 16)) {
+                    Some(Ok(hash)) => ClipboardEntry::with_image(image, hash),
+                    _ => ClipboardEntry::new_image(image),
+                },
+                Err(e) => {
+                    eprintln!("[history] Dropping image entry {file}: {e}");
+                    return None;
+                }
+            },
+            None => ClipboardEntry::new(self.content),
+        };
+        Some(ClipboardEntry {
+            captured_at,
+            pinned: self.pinned,
+            source: self.source,
+            note: self.note,
+            tags: normalize_tags(self.tags),
+            source_app: self.source_app,
+            ..entry
+        })
+    }
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !out.iter().any(|t| t == tag) {
+            out.push(tag.to_string());
+        }
+    }
+    out
+}
+
+/// Directory holding the PNGs of image entries for the history at `path`.
+fn images_dir(path: &Path) -> PathBuf {
+    path.with_file_name("images")
+}
+
+/// Salvage well-formed entries from a history file that failed to parse as a
+/// whole (e.g. truncated by a crash mid-write). Array elements are parsed one
+/// at a time; malformed ones are skipped by resyncing at the next `{`.
+fn salvage_entries(json: &str) -> Vec<StoredEntry> {
+    let mut entries = Vec::new();
+    let Some(start) = json.find('[') else {
+        return entries;
+    };
+    let mut rest = &json[start + 1..];
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() || rest.starts_with(']') {
+            break;
+        }
+        let mut stream =
+            serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+        if let Some(Ok(value)) = stream.next() {
+            let consumed = stream.byte_offset();
+            if let Ok(entry) = serde_json::from_value::<StoredEntry>(value) {
+                entries.push(entry);
+            }
+            rest = &rest[consumed..];
+        } else {
+            match rest.char_indices().skip(1).find(|&(_, c)| c == '{') {
+                Some((i, _)) => rest = &rest[i..],
+                None => break,
+            }
+        }
+    }
+    entries
+}
+
+/// Copy an unparseable history file aside so the next save can't destroy it.
+fn backup_corrupt_file(path: &Path) {
+    let backup = path.with_extension("json.corrupt");
+    match std::fs::copy(path, &backup) {
+        Ok(_) => eprintln!("[history] Backed up corrupt file to {}", backup.display()),
+        Err(e) => eprintln!("[history] Backup of corrupt file failed: {e}"),
+    }
+}
+
+// ── ClipboardHistory ──────────────────────────────────────────────────────────
+
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardEntry>,
+    max_size: usize,
+    /// `add` moves an existing copy anywhere in the history to the front
+    /// instead of only checking the newest entry.
+    dedup_full: bool,
+    /// Set when the history file is encrypted; `save` encrypts with it.
+    key: Option<crypto::HistoryKey>,
+    /// Set when the history lives in an SQLite database; `save` syncs to it.
+    #[cfg(feature = "sqlite")]
+    db: Option<sqlite::Database>,
+    /// Names of PNGs being written on a worker thread.
+    png_writes: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ClipboardHistory {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_size,
+            dedup_full: false,
+            key: None,
+            #[cfg(feature = "sqlite")]
+            db: None,
+            png_writes: Arc::default(),
+        }
+    }
+
+    pub fn set_dedup_full(&mut self, dedup_full: bool) {
+        self.dedup_full = dedup_full;
+    }
+
+    /// Load history from a JSON file, or from an SQLite database for a `.db`
+    /// path with the `sqlite` feature. Returns an empty history if the file is
+    /// missing or unreadable. An encrypted file needs `passphrase`; without it,
+    /// or with the wrong one, this is an error.
+    pub fn load(path: &Path, max_size: usize, passphrase: Option<&str>) -> Result<Self, String> {
+        #[cfg(feature = "sqlite")]
+        if path.extension().is_some_and(|ext| ext == "db") {
+            return Self::load_database(path, max_size, passphrase);
+        }
+        let mut history = Self::new(max_size);
+        let Ok(mut bytes) = std::fs::read(path) else {
+            return Ok(history);
+        };
+        if crypto::is_encrypted(&bytes) {
+            let passphrase = passphrase.ok_or("history is encrypted; passphrase required")?;
+            let (plaintext, key) = crypto::decrypt(&bytes, passphrase)?;
+            bytes = plaintext;
+            history.key = Some(key);
+        }
+        let Ok(json) = String::from_utf8(bytes) else {
+            return Ok(history);
+        };
+        let stored = match serde_json::from_str::<Vec<StoredEntry>>(&json) {
+            Ok(stored) => stored,
+            Err(e) => {
+                eprintln!("[history] Failed to parse {}: {e}", path.display());
+                backup_corrupt_file(path);
+                let salvaged = salvage_entries(&json);
+                eprintln!("[history] Recovered {} entries from corrupt file", salvaged.len());
+                salvaged
+            }
+        };
+        // File is stored newest-first; rebuild the deque in the same order.
+        let images = images_dir(path);
+        history.entries.extend(stored.into_iter().filter_map(|s| s.into_entry(&images)));
+        history.evict_unpinned();
+        Ok(history)
+    }
+
+    /// Load from the SQLite database at `path`. If there is no database yet
+    /// but a `history.json` next to it, that is imported once and renamed to
+    /// `history.json.migrated`.
+    #[cfg(feature = "sqlite")]
+    fn load_database(
+        path: &Path,
+        max_size: usize,
+        passphrase: Option<&str>,
+    ) -> Result<Self, String> {
+        let json_path = path.with_extension("json");
+        if !path.exists() && json_path.exists() {
+            let mut history = Self::load(&json_path, max_size, passphrase)?;
+            if history.is_encrypted() {
+                return Err("encrypted history can't be moved to SQLite; \
+                            remove the passphrase first"
+                    .to_string());
+            }
+            history.db = Some(sqlite::Database::open(path)?);
+            history.save(path)?;
+            let migrated = json_path.with_extension("json.migrated");
+            if let Err(e) = std::fs::rename(&json_path, &migrated) {
+                eprintln!("[history] Could not rename {}: {e}", json_path.display());
+            }
+            eprintln!("[history] Migrated {} entries into {}", history.len(), path.display());
+            return Ok(history);
+        }
+
+        let db = sqlite::Database::open(path)?;
+        let images = images_dir(path);
+        let mut history = Self::new(max_size);
+        for (id, stored) in db.load()? {
+            if let Some(entry) = stored.into_entry(&images) {
+                history.entries.push_back(ClipboardEntry { id, ..entry });
+            }
+        }
+        history.db = Some(db);
+        history.evict_unpinned();
+        Ok(history)
+    }
+
+    /// Encrypt future saves with `key`, or go back to plaintext with `None`.
+    /// Derive the key with `HistoryKey::new` off the UI thread; it's slow.
+    pub fn set_key(&mut self, key: Option<HistoryKey>) -> Result<(), String> {
+        #[cfg(feature = "sqlite")]
+        if self.db.is_some() && key.is_some() {
+            return Err("encryption isn't available with SQLite storage".to_string());
+        }
+        self.key = key;
+        Ok(())
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Persist the history to a JSON file, creating parent directories as needed.
+    /// The JSON is encrypted if a passphrase is set. Image entries are then
+    /// kept in memory only, since their PNGs would be readable by anyone. A
+    /// history loaded from a database writes just its changes there instead.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        self.save_images(&images_dir(path))?;
+        #[cfg(feature = "sqlite")]
+        if let Some(db) = &self.db {
+            let stored = self.entries.iter().filter_map(|e| Some((e.id, e.stored()?)));
+            return db.sync(stored.collect());
+        }
+        let stored: Vec<StoredEntry> = self
+            .entries
+            .iter()
+            .filter(|e| self.key.is_none() || e.image.is_none())
+            .filter_map(ClipboardEntry::stored)
+            .collect();
+        let json = serde_json::to_string_pretty(&stored).map_err(|e| e.to_string())?;
+        let data = match &self.key {
+            Some(key) => crypto::encrypt(json.as_bytes(), key)?,
+            None => json.into_bytes(),
+        };
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+
+    /// Write PNGs for image entries that don't have one yet and delete the
+    /// ones no entry refers to any more. An encrypted history keeps none.
+    ///
+    /// Encoding a large screenshot takes a while, so new PNGs are written on
+    /// a worker thread, to a `.part` file that is renamed once complete.
+    fn save_images(&self, dir: &Path) -> Result<(), String> {
+        let mut referenced = HashSet::new();
+        for entry in &self.entries {
+            let (Some(image), Some(name)) = (entry.image(), entry.image_file_name()) else {
+                continue;
+            };
+            if self.key.is_some() {
+                // Its PNG is about to go; read it first if that hasn't happened.
+                let _ = image.rgba();
+                continue;
+            }
+            let file = dir.join(&name);
+            referenced.insert(name.clone());
+            if file.exists() || !self.png_writes.lock().unwrap().insert(name.clone()) {
+                continue;
+            }
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            let image = image.clone();
+            let png_writes = Arc::clone(&self.png_writes);
+            std::thread::spawn(move || {
+                let part = file.with_extension("png.part");
+                let written = image
+                    .write_png(&part)
+                    .and_then(|()| std::fs::rename(&part, &file).map_err(|e| e.to_string()));
+                if let Err(e) = written {
+                    eprintln!("[history] Writing {} failed: {e}", file.display());
+                    let _ = std::fs::remove_file(&part);
+                }
+                png_writes.lock().unwrap().remove(&name);
+            });
+        }
+        let Ok(existing) = std::fs::read_dir(dir) else {
+            return Ok(());
+        };
+        let writing = self.png_writes.lock().unwrap();
+        for file in existing.flatten() {
+            let name = file.file_name().to_string_lossy().into_owned();
+            // Left behind by a write that was cut short.
+            let png = name.strip_suffix(".part").unwrap_or(&name);
+            if png.ends_with(".png") && !referenced.contains(&name) && !writing.contains(png) {
+                let _ = std::fs::remove_file(file.path());
+            }
+        }
+        Ok(())
+    }
+
+    /// Block until the PNGs started by `save` are on disk.
+    #[cfg(test)]
+    fn wait_for_images(&self) {
+        while !self.png_writes.lock().unwrap().is_empty() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Add a new entry captured from `source`. A copy of the newest entry is
+    /// ignored; with `dedup_full`, a copy of an older entry moves that entry to
+    /// the front with a fresh timestamp. Returns true if the history changed.
+    pub fn add(&mut self, content: String, source: Source) -> bool {
+        self.push(ClipboardEntry { source, ..ClipboardEntry::new(content) })
+    }
+
+    /// Add an image entry, deduplicating against the most recent like `add`.
+    pub fn add_image(&mut self, image: ClipboardImage) -> bool {
+        self.push(ClipboardEntry::new_image(image))
+    }
+
+    /// Add several entries at once, deduplicated like `add`. The first item
+    /// ends up newest, so a split list reads top to bottom in the history.
+    /// Empty items are skipped. Returns how many changed the history.
+    pub fn add_batch(&mut self, contents: Vec<String>, source: Source) -> usize {
+        let mut added = 0;
+        for content in contents.into_iter().rev().filter(|c| !c.is_empty()) {
+            if self.add(content, source) {
+                added += 1;
+            }
+        }
+        added
+    }
+
+    fn push(&mut self, entry: ClipboardEntry) -> bool {
+        if let Some(front) = self.entries.front() {
+            if front.has_content(&entry.content, entry.hash) {
+                return false;
+            }
+        }
+        if self.dedup_full {
+            let existing = self
+                .entries
+                .iter()
+                .position(|e| e.has_content(&entry.content, entry.hash))
+                .and_then(|i| self.entries.remove(i));
+            if let Some(existing) = existing {
+                let moved = ClipboardEntry {
+                    captured_at: entry.captured_at,
+                    source: entry.source,
+                    ..existing
+                };
+                self.entries.push_front(moved);
+                return true;
+            }
+        }
+        self.entries.push_front(entry);
+        self.evict_unpinned();
+        true
+    }
+
+    /// Drop the oldest unpinned entries until the history fits `max_size`.
+    /// Pinned entries alone may exceed the limit.
+    fn evict_unpinned(&mut self) {
+        while self.entries.len() > self.max_size {
+            let Some(oldest) = self.entries.iter().rposition(|e| !e.pinned) else {
+                break;
+            };
+            self.entries.remove(oldest);
+        }
+    }
+
+    /// Merge imported entries, skipping any whose content is already present
+    /// (or repeated in the import). Entries without a timestamp get the
+    /// current time, and imported pins are kept. Existing entries are never
+    /// evicted: unpinned imports only fill the room left under `max_size`,
+    /// newest first, and the rest count as skipped. The result stays ordered
+    /// newest-first. Returns `(added, skipped)`.
+    pub fn merge(&mut self, imported: Vec<import::ImportedEntry>) -> (usize, usize) {
+        let mut seen: HashSet<u64> = self.entries.iter().map(|e| e.hash).collect();
+        let mut new_entries: Vec<ClipboardEntry> = Vec::new();
+        let mut skipped = 0;
+        for item in imported {
+            let entry = ClipboardEntry::new(item.content);
+            let is_dup = seen.contains(&entry.hash)
+                && self
+                    .entries
+                    .iter()
+                    .chain(&new_entries)
+                    .any(|e| e.has_content(&entry.content, entry.hash));
+            if is_dup || entry.content.is_empty() {
+                skipped += 1;
+                continue;
+            }
+            seen.insert(entry.hash);
+            let captured_at = item.captured_at.unwrap_or(entry.captured_at);
+            new_entries.push(ClipboardEntry { captured_at, pinned: item.pinned, ..entry });
+        }
+
+        // Pinned imports always fit, like pinned entries elsewhere.
+        let (pinned, mut unpinned): (Vec<_>, Vec<_>) =
+            new_entries.into_iter().partition(|e| e.pinned);
+        let room = self.max_size.saturating_sub(self.entries.len() + pinned.len());
+        unpinned.sort_by_key(|e| std::cmp::Reverse(e.captured_at));
+        if unpinned.len() > room {
+            skipped += unpinned.len() - room;
+            unpinned.truncate(room);
+        }
+        let added = pinned.len() + unpinned.len();
+        self.entries.extend(pinned.into_iter().chain(unpinned));
+        // Stable, so entries with equal timestamps keep their order.
+        self.entries.make_contiguous().sort_by_key(|e| std::cmp::Reverse(e.captured_at));
+        (added, skipped)
+    }
+
+    /// Put the entries of `newer`, captured while this history wasn't loaded,
+    /// in front as if they had just been captured, oldest first. They keep
+    /// their timestamps, notes and tags.
+    pub fn add_newer(&mut self, newer: ClipboardHistory) {
+        for entry in newer.entries.into_iter().rev() {
+            self.push(entry);
+        }
+    }
+
+    /// Change the capacity, evicting the oldest unpinned entries if it shrank.
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        self.evict_unpinned();
+    }
+
+    pub fn set_pinned(&mut self, index: usize, pinned: bool) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.pinned = pinned;
+        }
+    }
+
+    /// Set or, with blank `note`, remove the note on an entry. Returns true if
+    /// it changed.
+    pub fn set_note(&mut self, index: usize, note: &str) -> bool {
+        let note = (!note.trim().is_empty()).then(|| note.to_string());
+        match self.entries.get_mut(index) {
+            Some(entry) if entry.note != note => {
+                entry.note = note;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replace the tags on an entry. They are trimmed, and empty or repeated
+    /// ones dropped. Returns true if they changed.
+    pub fn set_tags(&mut self, index: usize, tags: Vec<String>) -> bool {
+        let tags = normalize_tags(tags);
+        match self.entries.get_mut(index) {
+            Some(entry) if entry.tags != tags => {
+                entry.tags = tags;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record the window an entry was copied from.
+    pub fn set_source_app(&mut self, index: usize, app: String) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.source_app = Some(app);
+        }
+    }
+
+    /// Mark every entry unchecked, e.g. after the secret policy changed.
+    pub fn reset_persist(&mut self) {
+        for entry in &mut self.entries {
+            entry.persist = None;
+        }
+    }
+
+    /// Decide how `save` writes each entry not checked since it was added or
+    /// `reset_persist` ran: entries holding a secret covered by `rules` are
+    /// redacted or skipped according to `policy`, which is logged.
+    pub fn check_secrets(&mut self, policy: SecretPolicy, rules: &SecretRules) {
+        for entry in self.entries.iter_mut().filter(|e| e.persist.is_none()) {
+            let found = match policy {
+                _ if entry.image.is_some() => None,
+                SecretPolicy::Keep => None,
+                _ => secret::redact(&entry.content, rules),
+            };
+            entry.persist = Some(match found {
+                None => Persist::Full,
+                Some((_, kinds)) if policy == SecretPolicy::Skip => {
+                    eprintln!("[history] Not saving an entry containing: {}", kinds.join(", "));
+                    Persist::Skip
+                }
+                Some((redacted, kinds)) => {
+                    eprintln!("[history] Redacted from saved entry: {}", kinds.join(", "));
+                    Persist::Redacted(redacted)
+                }
+            });
+        }
+    }
+
+    /// Every tag used in the history, sorted.
+    pub fn all_tags(&self) -> Vec<String> {
+        let tags: BTreeSet<&String> = self.entries.iter().flat_map(|e| &e.tags).collect();
+        tags.into_iter().cloned().collect()
+    }
+
+    /// Collapse duplicate entries anywhere in the history, keeping the newest
+    /// copy of each. Older versions only deduped against the front, so files
+    /// written by them can contain repeats. Returns how many were removed.
+    pub fn dedup_all(&mut self) -> usize {
+        // hash → indices of kept entries with that hash (more than one only on collision)
+        let mut kept: HashMap<u64, Vec<usize>> = HashMap::new();
+        let keep: Vec<bool> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let same_hash = kept.entry(entry.hash).or_default();
+                let is_dup = same_hash
+                    .iter()
+                    .any(|&j| self.entries[j].content == entry.content);
+                if !is_dup {
+                    same_hash.push(i);
+                }
+                !is_dup
+            })
+            .collect();
+
+        // A pin on a dropped copy carries over to the kept one.
+        let pins: Vec<usize> = self
+            .entries
+            .iter()
+            .zip(&keep)
+            .filter(|(entry, &kept_flag)| !kept_flag && entry.pinned)
+            .filter_map(|(entry, _)| {
+                kept[&entry.hash]
+                    .iter()
+                    .copied()
+                    .find(|&j| self.entries[j].content == entry.content)
+            })
+            .collect();
+        for j in pins {
+            self.entries[j].pinned = true;
+        }
+
+        let before = self.entries.len();
+        let mut flags = keep.into_iter();
+        self.entries.retain(|_| flags.next().unwrap_or(true));
+        before - self.entries.len()
+    }
+
+    pub fn entries(&self) -> &VecDeque<ClipboardEntry> {
+        &self.entries
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ClipboardEntry> {
+        self.entries.get(index)
+    }
+
+    /// Index of the newest entry whose `ClipboardEntry::hash` is `hash`.
+    pub fn position(&self, hash: u64) -> Option<usize> {
+        self.entries.iter().position(|e| e.hash() == hash)
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.entries.remove(index);
+    }
+
+    /// Remove every unpinned entry, handing them back so the caller can offer undo.
+    pub fn clear(&mut self) -> VecDeque<ClipboardEntry> {
+        let (pinned, cleared) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|e| e.pinned);
+        self.entries = pinned;
+        cleared
+    }
+
+    /// Put entries returned by `clear` back behind anything captured since.
+    pub fn restore(&mut self, cleared: VecDeque<ClipboardEntry>) {
+        self.entries.extend(cleared);
+        if self.dedup_full {
+            self.dedup_all();
+        }
+        self.evict_unpinned();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Count captures today / this week / overall and find the busiest hour.
+    pub fn capture_stats(&self) -> CaptureStats {
+        let now = Local::now();
+        let today = now.date_naive();
+        let week = now.iso_week();
+
+        let mut stats = CaptureStats {
+            today: 0,
+            this_week: 0,
+            total: self.entries.len(),
+            busiest_hour: None,
+        };
+        let mut per_hour = [0usize; 24];
+        for dt in self.entries.iter().filter_map(ClipboardEntry::captured_local) {
+            if dt.date_naive() == today {
+                stats.today += 1;
+            }
+            if dt.iso_week() == week {
+                stats.this_week += 1;
+            }
+            per_hour[dt.hour() as usize] += 1;
+        }
+        stats.busiest_hour = per_hour
+            .iter()
+            .enumerate()
+            .filter(|(_, &n)| n > 0)
+            // max_by_key keeps the last maximum; reverse so ties go to the earliest hour.
+            .rev()
+            .max_by_key(|(_, &n)| n)
+            .map(|(h, &n)| (h as u32, n));
+        stats
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use import::ImportedEntry;
+
+    fn imported(content: &str, secs: u64, pinned: bool) -> ImportedEntry {
+        ImportedEntry {
+            content: content.to_string(),
+            captured_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+            pinned,
+        }
+    }
+
+    fn contents(history: &ClipboardHistory) -> Vec<&str> {
+        history.entries().iter().map(ClipboardEntry::content).collect()
+    }
+
+    fn test_image() -> ClipboardImage {
+        ClipboardImage::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 128])
+    }
+
+    fn pngs(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .map(|files| files.flatten().map(|f| f.file_name().to_string_lossy().into()).collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn image_hash_is_unchanged_by_streaming() {
+        let image = test_image();
+        let concatenated = [b"2x1:".as_slice(), image.rgba().unwrap()].concat();
+        assert_eq!(image.hash(), bytes_hash(concatenated));
+    }
+
+    #[test]
+    fn saved_images_are_read_lazily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let mut history = ClipboardHistory::new(10);
+        history.add_image(test_image());
+        let hash = history.get(0).unwrap().hash();
+        history.save(&path).unwrap();
+        history.wait_for_images();
+        assert_eq!(pngs(&images_dir(&path)), [format!("{hash:016x}.png")]);
+
+        let loaded = ClipboardHistory::load(&path, 10, None).unwrap();
+        let entry = loaded.get(0).unwrap();
+        assert_eq!(entry.hash(), hash);
+        assert_eq!(entry.content(), "Image 2×1");
+        let image = entry.image().unwrap();
+        assert!(image.pixels.get().is_none());
+        assert_eq!(image.rgba().unwrap(), test_image().rgba().unwrap());
+    }
+
+    #[test]
+    fn encrypting_reads_images_before_deleting_their_pngs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let mut history = ClipboardHistory::new(10);
+        history.add_image(test_image());
+        history.save(&path).unwrap();
+        history.wait_for_images();
+
+        let mut loaded = ClipboardHistory::load(&path, 10, None).unwrap();
+        loaded.set_key(Some(HistoryKey::new("passphrase").unwrap())).unwrap();
+        loaded.save(&path).unwrap();
+        assert!(pngs(&images_dir(&path)).is_empty());
+        let image = loaded.get(0).unwrap().image().unwrap();
+        assert_eq!(image.rgba().unwrap(), test_image().rgba().unwrap());
+    }
+
+    #[test]
+    fn merge_skips_duplicates_and_sorts_by_time() {
+        let mut history = ClipboardHistory::new(10);
+        history.add("existing".to_string(), Source::Clipboard);
+        let (added, skipped) = history.merge(vec![
+            imported("old", 100, false),
+            imported("existing", 200, false),
+            imported("older", 50, false),
+            imported("old", 300, false),
+            imported("", 400, false),
+        ]);
+        assert_eq!((added, skipped), (2, 3));
+        assert_eq!(contents(&history), ["existing", "old", "older"]);
+    }
+
+    #[test]
+    fn merge_never_evicts_existing_entries() {
+        let mut history = ClipboardHistory::new(3);
+        history.add("a".to_string(), Source::Clipboard);
+        history.add("b".to_string(), Source::Clipboard);
+        // Newer than everything, but only one fits.
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+        let (added, skipped) = history.merge(vec![
+            imported("x", now + 10, false),
+            imported("y", now + 20, false),
+        ]);
+        assert_eq!((added, skipped), (1, 1));
+        assert_eq!(contents(&history), ["y", "b", "a"]);
+    }
+
+    #[test]
+    fn merge_counts_only_what_fits() {
+        let mut history = ClipboardHistory::new(2);
+        history.add("a".to_string(), Source::Clipboard);
+        history.add("b".to_string(), Source::Clipboard);
+        let (added, skipped) = history.merge(vec![imported("x", 1, false)]);
+        assert_eq!((added, skipped), (0, 1));
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn merge_keeps_pins_even_when_full() {
+        let mut history = ClipboardHistory::new(1);
+        history.add("a".to_string(), Source::Clipboard);
+        let (added, skipped) = history.merge(vec![imported("pinned", 1, true)]);
+        assert_eq!((added, skipped), (1, 0));
+        assert_eq!(contents(&history), ["a", "pinned"]);
+        assert!(history.get(1).unwrap().pinned());
+    }
+
+    #[test]
+    fn dedup_front_match_is_ignored() {
+        for dedup_full in [false, true] {
+            let mut history = ClipboardHistory::new(10);
+            history.set_dedup_full(dedup_full);
+            history.add("a".to_string(), Source::Clipboard);
+            assert!(!history.add("a".to_string(), Source::Clipboard));
+            assert_eq!(contents(&history), ["a"]);
+        }
+    }
+
+    #[test]
+    fn dedup_middle_match_moves_to_front() {
+        let mut history = ClipboardHistory::new(10);
+        history.set_dedup_full(true);
+        for content in ["a", "b", "c"] {
+            history.add(content.to_string(), Source::Clipboard);
+        }
+        history.set_note(1, "kept");
+        let before = history.get(1).unwrap().captured_at;
+        assert!(history.add("b".to_string(), Source::Clipboard));
+        assert_eq!(contents(&history), ["b", "c", "a"]);
+        let moved = history.get(0).unwrap();
+        assert_eq!(moved.note(), Some("kept"));
+        assert!(moved.captured_at >= before);
+    }
+
+    #[test]
+    fn dedup_middle_match_is_kept_when_off() {
+        let mut history = ClipboardHistory::new(10);
+        for content in ["a", "b", "c", "b"] {
+            history.add(content.to_string(), Source::Clipboard);
+        }
+        assert_eq!(contents(&history), ["b", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn dedup_no_match_adds_an_entry() {
+        let mut history = ClipboardHistory::new(10);
+        history.set_dedup_full(true);
+        history.add("a".to_string(), Source::Clipboard);
+        assert!(history.add("b".to_string(), Source::Clipboard));
+        assert_eq!(contents(&history), ["b", "a"]);
+    }
+
+    #[test]
+    fn add_newer_puts_entries_in_front() {
+        let mut loaded = ClipboardHistory::new(10);
+        loaded.add("saved".to_string(), Source::Clipboard);
+        let mut locked = ClipboardHistory::new(10);
+        locked.add("first".to_string(), Source::Clipboard);
+        locked.add("second".to_string(), Source::Clipboard);
+        loaded.add_newer(locked);
+        assert_eq!(contents(&loaded), ["second", "first", "saved"]);
+    }
+}
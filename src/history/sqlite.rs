@@ -0,0 +1,250 @@
+//! SQLite storage for the history, built with the `sqlite` feature. Rows
+//! mirror `StoredEntry`. `sync` only writes what changed since the last load
+//! or save, so a capture is one INSERT and a deletion one DELETE instead of a
+//! rewrite of the whole file.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rusqlite::{params, Connection, Row};
+
+use super::{Source, StoredEntry};
+
+/// Rows fetched per SELECT while loading.
+const PAGE_SIZE: usize = 500;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS entries (
+        id INTEGER PRIMARY KEY,
+        content TEXT NOT NULL,
+        unix_secs INTEGER NOT NULL,
+        pinned INTEGER NOT NULL DEFAULT 0,
+        image TEXT,
+        source TEXT NOT NULL DEFAULT 'clipboard',
+        note TEXT,
+        tags TEXT NOT NULL DEFAULT '[]',
+        source_app TEXT
+    );
+    CREATE INDEX IF NOT EXISTS entries_by_time ON entries (unix_secs DESC);
+";
+
+/// Columns added after the first release of the schema, with their types.
+/// Databases created before them get them on open.
+const ADDED_COLUMNS: &[(&str, &str)] = &[("source_app", "TEXT")];
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Row id for a new entry. Never hands out the id of a loaded row.
+pub(super) fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+pub(super) struct Database {
+    conn: Connection,
+    /// Rows as last read or written, by id; `sync` diffs against this.
+    written: RefCell<HashMap<u64, StoredEntry>>,
+}
+
+impl Database {
+    /// Open or create the database at `path`.
+    pub(super) fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let conn = Connection::open(path).map_err(|e| e.to_string())?;
+        conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        for (column, kind) in ADDED_COLUMNS {
+            let exists: bool = conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('entries') WHERE name = ?1",
+                    params![column],
+                    |row| row.get(0),
+                )
+                .map_err(|e| e.to_string())?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE entries ADD COLUMN {column} {kind}"))
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(Self { conn, written: RefCell::default() })
+    }
+
+    /// Every row with its id, newest first, read `PAGE_SIZE` rows at a time.
+    pub(super) fn load(&self) -> Result<Vec<(u64, StoredEntry)>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, content, unix_secs, pinned, image, source, note, tags, source_app
+                 FROM entries ORDER BY unix_secs DESC, id DESC LIMIT ?1 OFFSET ?2",
+            )
+            .map_err(|e| e.to_string())?;
+        let mut rows: Vec<(u64, StoredEntry)> = Vec::new();
+        loop {
+            let page = stmt
+                .query_map(params![PAGE_SIZE as i64, rows.len() as i64], read_row)
+                .and_then(|page| page.collect::<Result<Vec<_>, _>>())
+                .map_err(|e| e.to_string())?;
+            let last_page = page.len() < PAGE_SIZE;
+            rows.extend(page);
+            if last_page {
+                break;
+            }
+        }
+        let max_id = rows.iter().map(|(id, _)| *id).max().unwrap_or(0);
+        NEXT_ID.fetch_max(max_id + 1, Ordering::Relaxed);
+        *self.written.borrow_mut() = rows.iter().cloned().collect();
+        Ok(rows)
+    }
+
+    /// Make the table match `entries` in one transaction: new and changed
+    /// rows are written, rows whose id is gone are deleted.
+    pub(super) fn sync(&self, entries: Vec<(u64, StoredEntry)>) -> Result<(), String> {
+        let current: HashMap<u64, StoredEntry> = entries.into_iter().collect();
+        let mut written = self.written.borrow_mut();
+        let tx = self.conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        for id in written.keys().filter(|id| !current.contains_key(id)) {
+            tx.execute("DELETE FROM entries WHERE id = ?1", params![*id as i64])
+                .map_err(|e| e.to_string())?;
+        }
+        for (id, entry) in &current {
+            if written.get(id) == Some(entry) {
+                continue;
+            }
+            let tags = serde_json::to_string(&entry.tags).map_err(|e| e.to_string())?;
+            tx.execute(
+                "INSERT OR REPLACE INTO entries
+                 (id, content, unix_secs, pinned, image, source, note, tags, source_app)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    *id as i64,
+                    entry.content,
+                    entry.unix_secs as i64,
+                    entry.pinned,
+                    entry.image,
+                    source_name(entry.source),
+                    entry.note,
+                    tags,
+                    entry.source_app,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        *written = current;
+        Ok(())
+    }
+}
+
+fn read_row(row: &Row) -> rusqlite::Result<(u64, StoredEntry)> {
+    let source: String = row.get(5)?;
+    let tags: String = row.get(7)?;
+    let entry = StoredEntry {
+        content: row.get(1)?,
+        unix_secs: row.get::<_, i64>(2)? as u64,
+        pinned: row.get(3)?,
+        image: row.get(4)?,
+        source: serde_json::from_value(source.into()).unwrap_or_default(),
+        note: row.get(6)?,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        source_app: row.get(8)?,
+    };
+    Ok((row.get::<_, i64>(0)? as u64, entry))
+}
+
+/// The serde name of `source`, as it appears in history.json.
+fn source_name(source: Source) -> String {
+    serde_json::to_value(source)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ClipboardEntry, ClipboardHistory};
+    use super::*;
+
+    fn stored(content: &str, unix_secs: u64) -> StoredEntry {
+        StoredEntry {
+            content: content.to_string(),
+            unix_secs,
+            pinned: false,
+            image: None,
+            source: Source::Clipboard,
+            note: None,
+            tags: Vec::new(),
+            source_app: None,
+        }
+    }
+
+    fn contents(rows: &[(u64, StoredEntry)]) -> Vec<&str> {
+        rows.iter().map(|(_, e)| e.content.as_str()).collect()
+    }
+
+    #[test]
+    fn sync_inserts_updates_and_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("history.db")).unwrap();
+        db.sync(vec![(1, stored("a", 100)), (2, stored("b", 200)), (3, stored("c", 300))])
+            .unwrap();
+        assert_eq!(contents(&db.load().unwrap()), ["c", "b", "a"]);
+
+        let mut noted = stored("b", 200);
+        noted.note = Some("kept".to_string());
+        noted.tags = vec!["work".to_string()];
+        noted.source = Source::Primary;
+        db.sync(vec![(1, stored("a", 100)), (2, noted.clone())]).unwrap();
+        let rows = db.load().unwrap();
+        assert_eq!(contents(&rows), ["b", "a"]);
+        assert!(rows[0] == (2, noted));
+    }
+
+    #[test]
+    fn load_reads_every_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("history.db")).unwrap();
+        let rows: Vec<(u64, StoredEntry)> =
+            (0..PAGE_SIZE as u64 * 2 + 1).map(|i| (i + 1, stored(&i.to_string(), i))).collect();
+        db.sync(rows).unwrap();
+        let loaded = db.load().unwrap();
+        assert_eq!(loaded.len(), PAGE_SIZE * 2 + 1);
+        assert!(next_id() > PAGE_SIZE as u64 * 2 + 1);
+    }
+
+    #[test]
+    fn old_databases_gain_new_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE entries (id INTEGER PRIMARY KEY, content TEXT NOT NULL,
+                 unix_secs INTEGER NOT NULL, pinned INTEGER NOT NULL DEFAULT 0, image TEXT,
+                 source TEXT NOT NULL DEFAULT 'clipboard', note TEXT,
+                 tags TEXT NOT NULL DEFAULT '[]');
+                 INSERT INTO entries (id, content, unix_secs) VALUES (1, 'old', 5);",
+            )
+            .unwrap();
+        let db = Database::open(&path).unwrap();
+        let rows = db.load().unwrap();
+        assert!(rows == [(1, stored("old", 5))]);
+    }
+
+    #[test]
+    fn history_round_trips_through_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let mut history = ClipboardHistory::load(&path, 10, None).unwrap();
+        for content in ["a", "b", "c"] {
+            history.add(content.to_string(), Source::Clipboard);
+        }
+        history.remove(1);
+        history.save(&path).unwrap();
+
+        let loaded = ClipboardHistory::load(&path, 10, None).unwrap();
+        let contents: Vec<&str> = loaded.entries().iter().map(ClipboardEntry::content).collect();
+        assert_eq!(contents, ["c", "a"]);
+    }
+}
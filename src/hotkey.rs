@@ -1,16 +1,258 @@
 use rdev::{listen, EventType, Key};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 
-// Change these constants to customize the hotkey.
-// Current binding: Ctrl + Shift + H
-const HOTKEY_CTRL: bool = true;
-const HOTKEY_SHIFT: bool = true;
-const HOTKEY_ALT: bool = false;
-const HOTKEY_KEY: Key = Key::KeyH;
+use crate::event::AppEvent;
 
-/// Spawns a background thread that listens for the global hotkey.
-/// Sends a `()` message on `tx` whenever the hotkey is pressed.
-pub fn start_hotkey_listener(tx: Sender<()>) {
+/// Same modifiers + this key captures the PRIMARY selection (Linux).
+const PRIMARY_KEY: &str = "P";
+/// Shortcut IDs registered with the GlobalShortcuts portal.
+#[cfg(target_os = "linux")]
+const PORTAL_CAPTURE_ID: &str = "capture";
+#[cfg(target_os = "linux")]
+const PORTAL_PRIMARY_ID: &str = "capture-primary";
+
+/// The capture hotkey. `key` is an egui key name (`"H"`, `"7"`, `"F5"`, …)
+/// so the same config drives both the global and the in-app shortcut.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyConfig {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub key: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        Self { ctrl: true, shift: true, alt: false, key: "H".to_string() }
+    }
+}
+
+impl HotkeyConfig {
+    /// Human-readable description, e.g. `Ctrl+Shift+H`.
+    pub fn display(&self) -> String {
+        self.display_with_key(&self.key)
+    }
+
+    /// Description of the PRIMARY-selection hotkey (same modifiers).
+    pub fn primary_display(&self) -> String {
+        self.display_with_key(PRIMARY_KEY)
+    }
+
+    fn display_with_key(&self, key: &str) -> String {
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("Ctrl");
+        }
+        if self.shift {
+            parts.push("Shift");
+        }
+        if self.alt {
+            parts.push("Alt");
+        }
+        parts.push(key);
+        parts.join("+")
+    }
+
+    pub fn modifiers(&self) -> egui::Modifiers {
+        egui::Modifiers { alt: self.alt, ctrl: self.ctrl, shift: self.shift, ..Default::default() }
+    }
+
+    pub fn egui_key(&self) -> Option<egui::Key> {
+        egui::Key::from_name(&self.key)
+    }
+
+    /// A global hotkey needs Ctrl or Alt, or it would fire while typing, and
+    /// can't use the key reserved for the PRIMARY-selection hotkey.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.ctrl && !self.alt {
+            return Err(format!("{} needs Ctrl or Alt", self.display()));
+        }
+        if self.key == PRIMARY_KEY {
+            return Err(format!("{} is the PRIMARY-selection hotkey", self.display()));
+        }
+        Ok(())
+    }
+
+    /// Whether rdev can listen for this key globally.
+    pub fn is_global_capable(&self) -> bool {
+        rdev_key(&self.key).is_some()
+    }
+
+    /// Trigger in the XDG shortcuts syntax (`CTRL+SHIFT+h`), offered to the
+    /// portal as the preferred one. The desktop may pick another.
+    #[cfg(target_os = "linux")]
+    fn portal_trigger(&self, key: &str) -> Option<String> {
+        let keysym = match key {
+            k if k.len() == 1 && k.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                k.to_ascii_lowercase()
+            }
+            k if k.starts_with('F') && rdev_key(k).is_some() => k.to_string(),
+            "Space" => "space".to_string(),
+            "Insert" | "Home" | "End" => key.to_string(),
+            "PageUp" => "Prior".to_string(),
+            "PageDown" => "Next".to_string(),
+            _ => return None,
+        };
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("CTRL");
+        }
+        if self.shift {
+            parts.push("SHIFT");
+        }
+        if self.alt {
+            parts.push("ALT");
+        }
+        parts.push(&keysym);
+        Some(parts.join("+"))
+    }
+}
+
+/// Returns the path where the hotkey is persisted.
+/// Linux/others: $XDG_DATA_HOME/clipboard-hack/hotkey.json
+/// macOS:        ~/Library/Application Support/clipboard-hack/hotkey.json
+pub fn hotkey_file_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        let home = std::env::var("HOME").unwrap_or_default();
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("clipboard-hack")
+            .join("hotkey.json")
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let base = std::env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_default();
+                PathBuf::from(home).join(".local").join("share")
+            });
+        base.join("clipboard-hack").join("hotkey.json")
+    }
+}
+
+/// Load the hotkey from a JSON file. Returns HotkeyConfig::default() on any
+/// error, including a config that fails `validate`.
+pub fn load(path: &Path) -> HotkeyConfig {
+    let Ok(json) = std::fs::read_to_string(path) else {
+        return HotkeyConfig::default();
+    };
+    let Ok(config) = serde_json::from_str::<HotkeyConfig>(&json) else {
+        eprintln!("[hotkey] Failed to parse {}", path.display());
+        return HotkeyConfig::default();
+    };
+    if let Err(e) = config.validate() {
+        eprintln!("[hotkey] Ignoring {}: {e}", path.display());
+        return HotkeyConfig::default();
+    }
+    config
+}
+
+/// Persist the hotkey to a JSON file, creating parent directories as needed.
+pub fn save(config: &HotkeyConfig, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Spawns a background thread that listens for the global hotkey described
+/// by `config`. Sends `AppEvent::Hotkey` (or `AppEvent::PrimaryHotkey`) on
+/// `tx` whenever a hotkey is pressed.
+/// On Wayland the shortcuts are registered through the desktop's
+/// GlobalShortcuts portal, and the outcome is reported as
+/// `AppEvent::HotkeyPortal`; if that fails, rdev is used as elsewhere.
+/// The returned flag stays `true` while the listener is running and drops to
+/// `false` if rdev fails to hook the input system (e.g. on pure Wayland) or
+/// the configured key has no rdev equivalent.
+pub fn start_hotkey_listener(config: HotkeyConfig, tx: Sender<AppEvent>) -> Arc<AtomicBool> {
+    let active = Arc::new(AtomicBool::new(true));
+    #[cfg(target_os = "linux")]
+    if std::env::var("WAYLAND_DISPLAY").is_ok() {
+        start_portal_listener(config, tx, Arc::clone(&active));
+        return active;
+    }
+    start_rdev_listener(config, tx, Arc::clone(&active));
+    active
+}
+
+/// Registers both shortcuts with the portal and forwards their activations
+/// until the session ends. Falls back to rdev if registration fails.
+#[cfg(target_os = "linux")]
+fn start_portal_listener(config: HotkeyConfig, tx: Sender<AppEvent>, active: Arc<AtomicBool>) {
+    std::thread::spawn(move || {
+        let outcome = async_io::block_on(run_portal(&config, &tx));
+        match outcome {
+            Ok(()) => {
+                eprintln!("[hotkey] Portal session ended");
+                active.store(false, Ordering::Relaxed);
+            }
+            Err(e) => {
+                eprintln!("[hotkey] GlobalShortcuts portal failed: {e}; falling back to rdev");
+                let _ = tx.send(AppEvent::HotkeyPortal(Err(e)));
+                start_rdev_listener(config, tx, active);
+            }
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+async fn run_portal(config: &HotkeyConfig, tx: &Sender<AppEvent>) -> Result<(), String> {
+    use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+    use ashpd::WindowIdentifier;
+    use futures_util::StreamExt;
+
+    let portal = GlobalShortcuts::new().await.map_err(|e| e.to_string())?;
+    let session = portal.create_session().await.map_err(|e| e.to_string())?;
+    let capture_trigger = config.portal_trigger(&config.key);
+    let primary_trigger = config.portal_trigger(PRIMARY_KEY);
+    let shortcuts = [
+        NewShortcut::new(PORTAL_CAPTURE_ID, "Capture the clipboard")
+            .preferred_trigger(capture_trigger.as_deref()),
+        NewShortcut::new(PORTAL_PRIMARY_ID, "Capture the highlighted text")
+            .preferred_trigger(primary_trigger.as_deref()),
+    ];
+    let bound = portal
+        .bind_shortcuts(&session, &shortcuts, &WindowIdentifier::default())
+        .await
+        .and_then(|request| request.response())
+        .map_err(|e| e.to_string())?;
+    // The user can decline in the desktop's dialog, or the desktop may assign
+    // no trigger at all.
+    let capture = bound
+        .shortcuts()
+        .iter()
+        .find(|s| s.id() == PORTAL_CAPTURE_ID)
+        .ok_or("the capture shortcut wasn't bound")?;
+    let _ = tx.send(AppEvent::HotkeyPortal(Ok(capture.trigger_description().to_string())));
+
+    let mut activated = portal.receive_activated().await.map_err(|e| e.to_string())?;
+    while let Some(event) = activated.next().await {
+        let app_event = match event.shortcut_id() {
+            PORTAL_CAPTURE_ID => AppEvent::Hotkey,
+            PORTAL_PRIMARY_ID => AppEvent::PrimaryHotkey,
+            _ => continue,
+        };
+        if tx.send(app_event).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn start_rdev_listener(config: HotkeyConfig, tx: Sender<AppEvent>, active: Arc<AtomicBool>) {
+    let (Some(hotkey), Some(primary_key)) = (rdev_key(&config.key), rdev_key(PRIMARY_KEY)) else {
+        eprintln!("[hotkey] Key {:?} can't be used as a global hotkey", config.key);
+        active.store(false, Ordering::Relaxed);
+        return;
+    };
     std::thread::spawn(move || {
         let mut ctrl = false;
         let mut shift = false;
@@ -20,8 +262,12 @@ pub fn start_hotkey_listener(tx: Sender<()>) {
             match event.event_type {
                 EventType::KeyPress(k) => {
                     update_modifier(k, true, &mut ctrl, &mut shift, &mut alt);
-                    if is_hotkey(k, ctrl, shift, alt) {
-                        let _ = tx.send(());
+                    let modifiers_match =
+                        ctrl == config.ctrl && shift == config.shift && alt == config.alt;
+                    if modifiers_match && k == hotkey {
+                        let _ = tx.send(AppEvent::Hotkey);
+                    } else if modifiers_match && k == primary_key {
+                        let _ = tx.send(AppEvent::PrimaryHotkey);
                     }
                 }
                 EventType::KeyRelease(k) => {
@@ -34,6 +280,7 @@ pub fn start_hotkey_listener(tx: Sender<()>) {
         if let Err(e) = listen(callback) {
             eprintln!("[hotkey] Listen error: {e:?}");
         }
+        active.store(false, Ordering::Relaxed);
     });
 }
 
@@ -46,14 +293,95 @@ fn update_modifier(key: Key, pressed: bool, ctrl: &mut bool, shift: &mut bool, a
     }
 }
 
-fn is_hotkey(key: Key, ctrl: bool, shift: bool, alt: bool) -> bool {
-    key == HOTKEY_KEY
-        && ctrl == HOTKEY_CTRL
-        && shift == HOTKEY_SHIFT
-        && alt == HOTKEY_ALT
+/// Map an egui key name to the rdev key, for the keys a hotkey can sensibly use.
+fn rdev_key(name: &str) -> Option<Key> {
+    Some(match name {
+        "A" => Key::KeyA,
+        "B" => Key::KeyB,
+        "C" => Key::KeyC,
+        "D" => Key::KeyD,
+        "E" => Key::KeyE,
+        "F" => Key::KeyF,
+        "G" => Key::KeyG,
+        "H" => Key::KeyH,
+        "I" => Key::KeyI,
+        "J" => Key::KeyJ,
+        "K" => Key::KeyK,
+        "L" => Key::KeyL,
+        "M" => Key::KeyM,
+        "N" => Key::KeyN,
+        "O" => Key::KeyO,
+        "P" => Key::KeyP,
+        "Q" => Key::KeyQ,
+        "R" => Key::KeyR,
+        "S" => Key::KeyS,
+        "T" => Key::KeyT,
+        "U" => Key::KeyU,
+        "V" => Key::KeyV,
+        "W" => Key::KeyW,
+        "X" => Key::KeyX,
+        "Y" => Key::KeyY,
+        "Z" => Key::KeyZ,
+        "0" => Key::Num0,
+        "1" => Key::Num1,
+        "2" => Key::Num2,
+        "3" => Key::Num3,
+        "4" => Key::Num4,
+        "5" => Key::Num5,
+        "6" => Key::Num6,
+        "7" => Key::Num7,
+        "8" => Key::Num8,
+        "9" => Key::Num9,
+        "F1" => Key::F1,
+        "F2" => Key::F2,
+        "F3" => Key::F3,
+        "F4" => Key::F4,
+        "F5" => Key::F5,
+        "F6" => Key::F6,
+        "F7" => Key::F7,
+        "F8" => Key::F8,
+        "F9" => Key::F9,
+        "F10" => Key::F10,
+        "F11" => Key::F11,
+        "F12" => Key::F12,
+        "Space" => Key::Space,
+        "Insert" => Key::Insert,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        _ => return None,
+    })
 }
 
-/// Human-readable description of the configured hotkey.
-pub fn hotkey_display() -> &'static str {
-    "Ctrl+Shift+H"
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(ctrl: bool, shift: bool, alt: bool, key: &str) -> HotkeyConfig {
+        HotkeyConfig { ctrl, shift, alt, key: key.to_string() }
+    }
+
+    #[test]
+    fn hotkey_needs_ctrl_or_alt() {
+        assert!(HotkeyConfig::default().validate().is_ok());
+        assert!(config(false, false, true, "F5").validate().is_ok());
+        assert!(config(false, false, false, "H").validate().is_err());
+        assert!(config(false, true, false, "H").validate().is_err());
+    }
+
+    #[test]
+    fn primary_key_is_reserved() {
+        assert!(config(true, true, false, PRIMARY_KEY).validate().is_err());
+    }
+
+    #[test]
+    fn invalid_saved_hotkey_falls_back_to_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hotkey.json");
+        save(&config(false, false, false, "A"), &path).unwrap();
+        assert_eq!(load(&path), HotkeyConfig::default());
+        save(&config(false, false, true, "A"), &path).unwrap();
+        assert_eq!(load(&path), config(false, false, true, "A"));
+    }
 }
@@ -0,0 +1,257 @@
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+const ESC: char = '\x1b';
+/// Spellings of ESC seen when escape codes are pasted as source text rather
+/// than raw terminal output, e.g. from a shell script or a log line.
+const ESC_SPELLINGS: [&str; 5] = ["\\x1b", "\\x1B", "\\033", "\\u001b", "\\e"];
+
+/// Names of the 16 standard colors (codes 30–37 and 90–97).
+const NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright black",
+    "bright red",
+    "bright green",
+    "bright yellow",
+    "bright blue",
+    "bright magenta",
+    "bright cyan",
+    "bright white",
+];
+
+/// xterm's default RGB values for the 16 standard colors.
+const PALETTE: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+/// Channel levels of the 6×6×6 cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+pub struct AnsiInterpreter;
+
+impl Interpreter for AnsiInterpreter {
+    fn name(&self) -> &str {
+        "ANSI Escapes"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let normalized = normalize_esc(content);
+        if !normalized.contains(ESC) {
+            return None;
+        }
+        let parsed = parse(&normalized);
+        if parsed.sequences == 0 {
+            return None;
+        }
+
+        let mut items = vec![
+            InterpretItem::text("Plain text", parsed.plain.clone()),
+            InterpretItem::text("Escape sequences", parsed.sequences.to_string()),
+            InterpretItem::text("SGR sequences", parsed.sgr.to_string()),
+        ];
+        for (layer, color) in &parsed.colors {
+            let label = match layer {
+                Layer::Foreground => "Foreground",
+                Layer::Background => "Background",
+            };
+            items.push(InterpretItem::with_color(label, color.describe(), color.rgba()));
+        }
+        Some(
+            InterpretResult::new(items)
+                .with_actions(vec![CopyAction::new("Plain text", parsed.plain)]),
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Layer {
+    Foreground,
+    Background,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    /// Index into the 256-color palette; 0–15 are the standard colors.
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn rgba(self) -> [u8; 4] {
+        let [r, g, b] = match self {
+            Color::Indexed(n) => palette_rgb(n),
+            Color::Rgb(r, g, b) => [r, g, b],
+        };
+        [r, g, b, 255]
+    }
+
+    fn describe(self) -> String {
+        let [r, g, b, _] = self.rgba();
+        let hex = format!("#{r:02X}{g:02X}{b:02X}");
+        match self {
+            Color::Indexed(n) if n < 16 => format!("{} ({hex})", NAMES[n as usize]),
+            Color::Indexed(n) => format!("color {n} ({hex})"),
+            Color::Rgb(..) => format!("{hex} (24-bit)"),
+        }
+    }
+}
+
+fn palette_rgb(n: u8) -> [u8; 3] {
+    match n {
+        0..=15 => PALETTE[n as usize],
+        16..=231 => {
+            let i = n - 16;
+            [
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            ]
+        }
+        _ => {
+            let level = 8 + (n - 232) * 10;
+            [level, level, level]
+        }
+    }
+}
+
+struct Parsed {
+    plain: String,
+    /// All escape sequences removed, SGR or not.
+    sequences: usize,
+    sgr: usize,
+    /// Distinct colors in order of first use.
+    colors: Vec<(Layer, Color)>,
+}
+
+/// Replace textual ESC spellings with the real character, but only where they
+/// introduce a sequence, so an unrelated `\e` in a path is left alone.
+fn normalize_esc(content: &str) -> String {
+    let mut out = content.to_string();
+    for spelling in ESC_SPELLINGS {
+        for intro in ['[', ']'] {
+            out = out.replace(&format!("{spelling}{intro}"), &format!("{ESC}{intro}"));
+        }
+    }
+    out
+}
+
+/// Strip CSI (`ESC [ … final`), OSC (`ESC ] … BEL` or `ESC ] … ESC \`) and
+/// two-character escapes, collecting colors from SGR (`ESC [ … m`) sequences.
+fn parse(s: &str) -> Parsed {
+    let mut parsed = Parsed {
+        plain: String::with_capacity(s.len()),
+        sequences: 0,
+        sgr: 0,
+        colors: Vec::new(),
+    };
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            parsed.plain.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut last = None;
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        last = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if last == Some('m') {
+                    parsed.sgr += 1;
+                    for color in sgr_colors(&params) {
+                        if !parsed.colors.contains(&color) {
+                            parsed.colors.push(color);
+                        }
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == ESC && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(_) | None => {}
+        }
+        parsed.sequences += 1;
+    }
+    parsed
+}
+
+/// Colors set by one SGR parameter list such as `1;38;5;208;48;2;0;0;0`.
+fn sgr_colors(params: &str) -> Vec<(Layer, Color)> {
+    let codes: Vec<u32> = params
+        .split([';', ':'])
+        .map(|p| p.parse().unwrap_or(0))
+        .collect();
+    let mut colors = Vec::new();
+    let mut i = 0;
+    while i < codes.len() {
+        let code = codes[i];
+        i += 1;
+        let (layer, base) = match code {
+            30..=37 => (Layer::Foreground, code - 30),
+            40..=47 => (Layer::Background, code - 40),
+            90..=97 => (Layer::Foreground, code - 90 + 8),
+            100..=107 => (Layer::Background, code - 100 + 8),
+            38 | 48 => {
+                let layer = if code == 38 {
+                    Layer::Foreground
+                } else {
+                    Layer::Background
+                };
+                match codes.get(i) {
+                    Some(5) => {
+                        if let Some(&n) = codes.get(i + 1) {
+                            colors.push((layer, Color::Indexed(n.min(255) as u8)));
+                        }
+                        i += 2;
+                    }
+                    Some(2) => {
+                        if let Some(rgb) = codes.get(i + 1..i + 4) {
+                            let [r, g, b] = [rgb[0], rgb[1], rgb[2]].map(|v| v.min(255) as u8);
+                            colors.push((layer, Color::Rgb(r, g, b)));
+                        }
+                        i += 4;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        colors.push((layer, Color::Indexed(base as u8)));
+    }
+    colors
+}
@@ -0,0 +1,81 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Key sizes (in bytes) commonly used for TOTP/HOTP secrets.
+const TOTP_KEY_LENGTHS: &[usize] = &[10, 16, 20, 32, 64];
+
+pub struct Base32Interpreter;
+
+impl Interpreter for Base32Interpreter {
+    fn name(&self) -> &str {
+        "Base32"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let compact: String = content.split_whitespace().collect();
+        // `decode` takes lower case too, but plain lower-case words would
+        // match far too often here.
+        if compact.len() < 8 || compact.bytes().any(|b| b.is_ascii_lowercase()) {
+            return None;
+        }
+        let bytes = decode(&compact)?;
+        if bytes.is_empty() {
+            return None;
+        }
+
+        let mut items = vec![InterpretItem::text("Byte length", bytes.len().to_string())];
+        if let Ok(text) = std::str::from_utf8(&bytes) {
+            if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+                items.push(InterpretItem::text("Decoded (UTF-8)", text));
+            }
+        }
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        items.push(InterpretItem::text("Decoded (hex)", hex));
+        if TOTP_KEY_LENGTHS.contains(&bytes.len()) {
+            items.push(InterpretItem::text(
+                "TOTP secret",
+                format!("plausible ({}-bit key)", bytes.len() * 8),
+            ));
+        }
+
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// Decode RFC 4648 base32 (optional `=` padding). Case and spaces are
+/// ignored, as secrets are often shown as lower-case groups of four.
+/// Returns `None` on characters outside the alphabet or an impossible length.
+pub fn decode(s: &str) -> Option<Vec<u8>> {
+    let s: String = s.chars().filter(|&c| c != ' ').map(|c| c.to_ascii_uppercase()).collect();
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+
+    // Only these remainders can end a base32 quantum; padding must fill it to 8.
+    let expected_padding = match data.len() % 8 {
+        0 => 0,
+        2 => 6,
+        4 => 4,
+        5 => 3,
+        7 => 1,
+        _ => return None,
+    };
+    if padding != 0 && padding != expected_padding {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in data.bytes() {
+        let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,167 @@
+use std::io::Read as _;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine as _;
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Bytes shown in the hex preview of binary payloads.
+const HEX_PREVIEW_BYTES: usize = 32;
+/// Decompressed payloads are cut off here, so a compression bomb can't
+/// exhaust memory.
+const MAX_INFLATED: u64 = 16 * 1024 * 1024;
+/// Decompressed text longer than this is cut off in the panel.
+const PREVIEW_CHARS: usize = 4000;
+
+pub struct Base64Interpreter;
+
+impl Interpreter for Base64Interpreter {
+    fn name(&self) -> &str {
+        "Base64"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let (variant, bytes) = decode(content)?;
+        let mut items = vec![
+            InterpretItem::text("Variant", variant),
+            InterpretItem::text("Byte length", bytes.len().to_string()),
+        ];
+        let mut actions = Vec::new();
+        if let Some((format, inflated)) = decompress(&bytes) {
+            let ratio = inflated.len() as f64 / bytes.len() as f64;
+            items.push(InterpretItem::text("Compression", format));
+            let size = if inflated.len() as u64 >= MAX_INFLATED {
+                format!("at least {MAX_INFLATED} bytes (stopped there)")
+            } else {
+                format!("{} bytes", inflated.len())
+            };
+            items.push(InterpretItem::text("Decompressed size", size));
+            items.push(InterpretItem::text("Ratio", format!("{ratio:.1}×")));
+            match std::str::from_utf8(&inflated) {
+                Ok(text) => {
+                    // Compressed JSON is the common case; show it indented.
+                    let json = serde_json::from_str::<serde_json::Value>(text.trim()).ok();
+                    let text = match json.and_then(|v| serde_json::to_string_pretty(&v).ok()) {
+                        Some(pretty) => {
+                            items.push(InterpretItem::text("Content", "JSON"));
+                            pretty
+                        }
+                        None => text.to_string(),
+                    };
+                    let preview = if text.chars().count() > PREVIEW_CHARS {
+                        format!("{}…", text.chars().take(PREVIEW_CHARS).collect::<String>())
+                    } else {
+                        text.clone()
+                    };
+                    items.push(InterpretItem::text("Decompressed", preview));
+                    actions.push(CopyAction::new("Decompressed", text));
+                }
+                Err(_) => items.push(InterpretItem::text("Decompressed", hex_preview(&inflated))),
+            }
+        } else {
+            match std::str::from_utf8(&bytes) {
+                Ok(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+                    items.push(InterpretItem::text("Decoded (UTF-8)", text));
+                }
+                _ => items.push(InterpretItem::text("Decoded (bytes)", hex_preview(&bytes))),
+            }
+        }
+
+        // Re-encode in both alphabets; this also unwraps line-wrapped input.
+        actions.push(CopyAction::new("Standard", STANDARD.encode(&bytes)));
+        actions.push(CopyAction::new("URL-safe", URL_SAFE.encode(&bytes)));
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+
+    /// Decoding is enough to know; a compressed payload isn't inflated.
+    fn applies(&self, content: &str) -> bool {
+        decode(content).is_some()
+    }
+}
+
+/// The alphabet and bytes of `content`, if it looks like base64 and decodes.
+fn decode(content: &str) -> Option<(&'static str, Vec<u8>)> {
+    let trimmed = content.trim();
+    // Wrapped blobs span lines, but a space mid-text means prose.
+    if trimmed.contains([' ', '\t']) {
+        return None;
+    }
+    let compact: String = trimmed.split_whitespace().collect();
+    if compact.len() < 8 || !compact.len().is_multiple_of(4) {
+        return None;
+    }
+    // Long identifiers and words are valid base64 too; require some
+    // non-letter before taking a short all-letter string seriously.
+    if compact.len() < 16 && compact.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let (variant, bytes) = if compact.contains(['-', '_']) {
+        ("URL-safe", URL_SAFE.decode(&compact).ok()?)
+    } else {
+        ("Standard", STANDARD.decode(&compact).ok()?)
+    };
+    (bytes.len() >= 3).then_some((variant, bytes))
+}
+
+fn hex_preview(bytes: &[u8]) -> String {
+    let preview: Vec<String> =
+        bytes.iter().take(HEX_PREVIEW_BYTES).map(|b| format!("{b:02x}")).collect();
+    let more = if bytes.len() > HEX_PREVIEW_BYTES { " …" } else { "" };
+    format!("{}{more}", preview.join(" "))
+}
+
+/// Inflate a gzip (`1f 8b`) or zlib (`78 ..` with a valid header checksum)
+/// payload. `None` if there's no such header or the stream is corrupt, in
+/// which case the bytes are shown as plain base64.
+fn decompress(bytes: &[u8]) -> Option<(&'static str, Vec<u8>)> {
+    let (format, reader): (&str, Box<dyn std::io::Read + '_>) = match bytes {
+        [0x1f, 0x8b, ..] => ("gzip", Box::new(MultiGzDecoder::new(bytes))),
+        [cmf @ 0x78, flg, ..] if (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0 => {
+            ("zlib", Box::new(ZlibDecoder::new(bytes)))
+        }
+        _ => return None,
+    };
+    let mut out = Vec::new();
+    reader.take(MAX_INFLATED).read_to_end(&mut out).ok()?;
+    Some((format, out))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    fn labels(result: &InterpretResult) -> Vec<&str> {
+        result.actions.iter().map(|a| a.label.as_str()).collect()
+    }
+
+    #[test]
+    fn plain_payload_offers_both_alphabets() {
+        let result = Base64Interpreter.interpret("aGVsbG8gd29ybGQ=").unwrap();
+        assert_eq!(labels(&result), ["Standard", "URL-safe"]);
+    }
+
+    #[test]
+    fn compressed_payload_keeps_the_re_encodings() {
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(br#"{"a": 1}"#).unwrap();
+        let blob = STANDARD.encode(gz.finish().unwrap());
+
+        assert!(Base64Interpreter.applies(&blob));
+        let result = Base64Interpreter.interpret(&blob).unwrap();
+        assert_eq!(labels(&result), ["Decompressed", "Standard", "URL-safe"]);
+        assert_eq!(result.actions[1].text, blob);
+    }
+
+    #[test]
+    fn prose_does_not_apply() {
+        assert!(!Base64Interpreter.applies("hello world"));
+        assert!(!Base64Interpreter.applies("Abcdefgh"));
+    }
+}
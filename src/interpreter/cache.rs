@@ -0,0 +1,191 @@
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::{InterpretContext, InterpretResult, Interpreter};
+
+/// Content at least this large is interpreted on a worker thread.
+const BACKGROUND_THRESHOLD: usize = 256 * 1024;
+/// How often results of time-dependent interpreters (TOTP codes, relative
+/// times) are refreshed while the same entry stays selected.
+const LIVE_REFRESH: Duration = Duration::from_secs(1);
+
+/// Results of every interpreter, in `get_interpreters()` order.
+type Results = Vec<Option<InterpretResult>>;
+
+/// Interpretation results for the entry shown in the detail panel, so a
+/// repaint doesn't re-run every interpreter over the whole content.
+pub struct InterpretCache {
+    interpreters: Arc<Vec<Box<dyn Interpreter>>>,
+    /// One flag per interpreter; disabled ones aren't run at all.
+    enabled: Arc<Vec<bool>>,
+    current: Option<Cached>,
+    job: Option<Job>,
+}
+
+/// Identifies what a set of results was computed for.
+#[derive(Clone, Copy, PartialEq)]
+struct Key {
+    content_hash: u64,
+    ctx: InterpretContext,
+}
+
+struct Cached {
+    key: Key,
+    results: Results,
+    live_refreshed: Instant,
+}
+
+struct Job {
+    key: Key,
+    rx: Receiver<Results>,
+}
+
+impl InterpretCache {
+    pub fn new(interpreters: Arc<Vec<Box<dyn Interpreter>>>) -> Self {
+        let enabled = Arc::new(vec![true; interpreters.len()]);
+        Self { interpreters, enabled, current: None, job: None }
+    }
+
+    /// Set which interpreters run, one flag each in `get_interpreters()`
+    /// order. Disabled ones get `None`. A change drops the cached results.
+    pub fn set_enabled(&mut self, enabled: Vec<bool>) {
+        if *self.enabled != enabled {
+            self.enabled = Arc::new(enabled);
+            self.current = None;
+            self.job = None;
+        }
+    }
+
+    /// Results for `content` (whose `content_hash` is `hash`) under `ctx`.
+    /// They are computed when the content or context changes; large content
+    /// is handed to a worker thread and `None` is returned until it finishes.
+    pub fn results(
+        &mut self,
+        content: &str,
+        hash: u64,
+        ctx: InterpretContext,
+    ) -> Option<&[Option<InterpretResult>]> {
+        let key = Key { content_hash: hash, ctx };
+        if self.current.as_ref().is_some_and(|c| c.key == key) {
+            self.refresh_live(content);
+        } else {
+            self.current = None;
+            if content.len() < BACKGROUND_THRESHOLD {
+                self.job = None;
+                let results = run_all(&self.interpreters, &self.enabled, content, &ctx);
+                self.store(key, results);
+            } else {
+                self.poll_job(content, key);
+            }
+        }
+        self.current.as_ref().map(|c| c.results.as_slice())
+    }
+
+    /// Start a worker for `key` unless one is running, and take its results
+    /// once it's done. A worker for a previous selection is abandoned; its
+    /// send fails harmlessly when it finishes.
+    fn poll_job(&mut self, content: &str, key: Key) {
+        if self.job.as_ref().is_none_or(|job| job.key != key) {
+            let (tx, rx) = mpsc::channel();
+            let interpreters = Arc::clone(&self.interpreters);
+            let enabled = Arc::clone(&self.enabled);
+            let content = content.to_string();
+            std::thread::spawn(move || {
+                let _ = tx.send(run_all(&interpreters, &enabled, &content, &key.ctx));
+            });
+            self.job = Some(Job { key, rx });
+        }
+        let Some(job) = &self.job else {
+            return;
+        };
+        if let Ok(results) = job.rx.try_recv() {
+            self.job = None;
+            self.store(key, results);
+        }
+    }
+
+    fn store(&mut self, key: Key, results: Results) {
+        self.current = Some(Cached { key, results, live_refreshed: Instant::now() });
+    }
+
+    /// Re-run the time-dependent interpreters that applied, once per
+    /// `LIVE_REFRESH`. Whether they apply doesn't depend on the time, so the
+    /// ones that returned `None` are skipped.
+    fn refresh_live(&mut self, content: &str) {
+        let Some(cached) = &mut self.current else {
+            return;
+        };
+        if cached.live_refreshed.elapsed() < LIVE_REFRESH {
+            return;
+        }
+        cached.live_refreshed = Instant::now();
+        for (interp, result) in self.interpreters.iter().zip(&mut cached.results) {
+            if interp.is_live() && result.is_some() {
+                *result = interp.interpret_with(content, &cached.key.ctx);
+            }
+        }
+    }
+}
+
+fn run_all(
+    interpreters: &[Box<dyn Interpreter>],
+    enabled: &[bool],
+    content: &str,
+    ctx: &InterpretContext,
+) -> Results {
+    interpreters
+        .iter()
+        .zip(enabled)
+        .map(|(interp, &on)| if on { interp.interpret_with(content, ctx) } else { None })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::InterpretItem;
+
+    struct Echo;
+
+    impl Interpreter for Echo {
+        fn name(&self) -> &str {
+            "Echo"
+        }
+
+        fn interpret(&self, content: &str) -> Option<InterpretResult> {
+            Some(InterpretResult::new(vec![InterpretItem::text("Echo", content)]))
+        }
+    }
+
+    struct Unusable;
+
+    impl Interpreter for Unusable {
+        fn name(&self) -> &str {
+            "Unusable"
+        }
+
+        fn interpret(&self, _content: &str) -> Option<InterpretResult> {
+            panic!("disabled interpreter was run");
+        }
+    }
+
+    #[test]
+    fn disabled_interpreters_are_not_run() {
+        let interpreters: Vec<Box<dyn Interpreter>> = vec![Box::new(Echo), Box::new(Unusable)];
+        let mut cache = InterpretCache::new(Arc::new(interpreters));
+        cache.set_enabled(vec![true, false]);
+        let results = cache.results("hi", 1, InterpretContext::default()).unwrap();
+        assert!(results[0].is_some());
+        assert!(results[1].is_none());
+    }
+
+    #[test]
+    fn changing_the_enabled_set_recomputes() {
+        let interpreters: Vec<Box<dyn Interpreter>> = vec![Box::new(Echo)];
+        let mut cache = InterpretCache::new(Arc::new(interpreters));
+        assert!(cache.results("hi", 1, InterpretContext::default()).unwrap()[0].is_some());
+        cache.set_enabled(vec![false]);
+        assert!(cache.results("hi", 1, InterpretContext::default()).unwrap()[0].is_none());
+    }
+}
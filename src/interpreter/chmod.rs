@@ -0,0 +1,91 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+pub struct ChmodInterpreter;
+
+impl Interpreter for ChmodInterpreter {
+    fn name(&self) -> &str {
+        "Unix Permissions"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let mode = parse_mode(content.trim())?;
+        let special = (mode >> 9) & 0o7;
+        let classes = [(mode >> 6) & 0o7, (mode >> 3) & 0o7, mode & 0o7];
+
+        let mut items = vec![
+            InterpretItem::text("Octal", format!("{mode:04o}")),
+            InterpretItem::text("Symbolic", symbolic(mode)),
+            InterpretItem::text(
+                "chmod form",
+                format!(
+                    "u={},g={},o={}",
+                    letters(classes[0]),
+                    letters(classes[1]),
+                    letters(classes[2])
+                ),
+            ),
+        ];
+        for (label, bits) in ["Owner", "Group", "Others"].into_iter().zip(classes) {
+            items.push(InterpretItem::text(label, describe(bits)));
+        }
+        if special != 0 {
+            let flags: Vec<&str> = [(0o4, "setuid"), (0o2, "setgid"), (0o1, "sticky")]
+                .into_iter()
+                .filter(|&(bit, _)| special & bit != 0)
+                .map(|(_, name)| name)
+                .collect();
+            items.push(InterpretItem::text("Special bits", flags.join(", ")));
+        }
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// `755`, `0644`, `4755` or `0o755`. Three digits are the plain mode; a
+/// fourth leading digit carries setuid/setgid/sticky.
+fn parse_mode(s: &str) -> Option<u32> {
+    let digits = s.strip_prefix("0o").unwrap_or(s);
+    if !(3..=4).contains(&digits.len()) || !digits.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return None;
+    }
+    u32::from_str_radix(digits, 8).ok()
+}
+
+/// `ls -l` style, e.g. `rwsr-xr-t`: special bits replace the matching `x`
+/// (upper-case when the execute bit underneath is off).
+fn symbolic(mode: u32) -> String {
+    let mut out = String::with_capacity(9);
+    let classes = [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')];
+    for (shift, special_bit, special_char) in classes {
+        let bits = (mode >> shift) & 0o7;
+        out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        out.push(match (mode & special_bit != 0, bits & 0o1 != 0) {
+            (true, true) => special_char,
+            (true, false) => special_char.to_ascii_uppercase(),
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
+fn letters(bits: u32) -> String {
+    [(0o4, 'r'), (0o2, 'w'), (0o1, 'x')]
+        .into_iter()
+        .filter(|&(bit, _)| bits & bit != 0)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+fn describe(bits: u32) -> String {
+    let words: Vec<&str> = [(0o4, "read"), (0o2, "write"), (0o1, "execute")]
+        .into_iter()
+        .filter(|&(bit, _)| bits & bit != 0)
+        .map(|(_, word)| word)
+        .collect();
+    if words.is_empty() {
+        "no access".to_string()
+    } else {
+        words.join(", ")
+    }
+}
@@ -1,4 +1,4 @@
-use super::{InterpretItem, InterpretResult, Interpreter};
+use super::{CopyAction, InterpretContext, InterpretItem, InterpretResult, Interpreter};
 
 pub struct ColorInterpreter;
 
@@ -8,27 +8,57 @@ impl Interpreter for ColorInterpreter {
     }
 
     fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        self.interpret_with(content, &InterpretContext::default())
+    }
+
+    fn interpret_with(&self, content: &str, ctx: &InterpretContext) -> Option<InterpretResult> {
         let trimmed = content.trim();
-        parse_color(trimmed).map(|(r, g, b, a)| build_result(r, g, b, a))
+        let (form, rgba) = parse_color(trimmed)?;
+        Some(build_result(form, rgba, ctx.hex_uppercase))
     }
 }
 
-/// Parse color string into (r, g, b, a) with u8 components.
-fn parse_color(s: &str) -> Option<(u8, u8, u8, u8)> {
+/// The syntax the color was written in, kept to echo a canonical input form.
+#[derive(Clone, Copy)]
+enum Form {
+    /// `#…` or `0x…` with the given number of hex digits.
+    Hex(usize),
+    Rgb,
+    Rgba,
+}
+
+/// Parse a color string into `[r, g, b, a]`, for callers that don't care about its form.
+pub fn parse_rgba(s: &str) -> Option<[u8; 4]> {
+    parse_color(s).map(|(_, (r, g, b, a))| [r, g, b, a])
+}
+
+/// Parse color string into its form and (r, g, b, a) with u8 components.
+fn parse_color(s: &str) -> Option<(Form, (u8, u8, u8, u8))> {
     if let Some(hex) = s.strip_prefix('#') {
-        return parse_hex(hex);
+        return parse_hex(hex).map(|c| (Form::Hex(hex.len()), c));
+    }
+    // `0x` only for full-length forms; `0xfff` is far more likely a number.
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        if hex.len() == 6 || hex.len() == 8 {
+            return parse_hex(hex).map(|c| (Form::Hex(hex.len()), c));
+        }
+        return None;
     }
     let lower = s.to_lowercase();
     if lower.starts_with("rgb(") && lower.ends_with(')') {
-        return parse_rgb_fn(&lower[4..lower.len() - 1], false);
+        return parse_rgb_fn(&lower[4..lower.len() - 1], false).map(|c| (Form::Rgb, c));
     }
     if lower.starts_with("rgba(") && lower.ends_with(')') {
-        return parse_rgb_fn(&lower[5..lower.len() - 1], true);
+        return parse_rgb_fn(&lower[5..lower.len() - 1], true).map(|c| (Form::Rgba, c));
     }
     None
 }
 
 fn parse_hex(hex: &str) -> Option<(u8, u8, u8, u8)> {
+    // The lengths below are in bytes; slicing needs ASCII to be safe.
+    if !hex.is_ascii() {
+        return None;
+    }
     match hex.len() {
         3 => {
             // #RGB → #RRGGBB
@@ -85,14 +115,38 @@ fn parse_channel(s: &str) -> Option<u8> {
     s.trim().parse::<u8>().ok()
 }
 
-fn build_result(r: u8, g: u8, b: u8, a: u8) -> InterpretResult {
-    let hex6 = format!("#{:02x}{:02x}{:02x}", r, g, b);
-    let hex8 = format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+fn build_result(form: Form, (r, g, b, a): (u8, u8, u8, u8), uppercase: bool) -> InterpretResult {
+    let hex6 = hex_case(format!("#{:02x}{:02x}{:02x}", r, g, b), uppercase);
+    let hex8 = hex_case(format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a), uppercase);
     let (h, s, l) = rgb_to_hsl(r, g, b);
     let alpha_pct = format!("{:.1}%", a as f32 / 255.0 * 100.0);
+    let hsl = format!("hsl({:.0}, {:.1}%, {:.1}%)", h, s * 100.0, l * 100.0);
+    let alpha = a as f32 / 255.0;
+
+    let normalized = match form {
+        Form::Hex(3) => hex_case(format!("#{:x}{:x}{:x}", r / 17, g / 17, b / 17), uppercase),
+        Form::Hex(4) => hex_case(
+            format!("#{:x}{:x}{:x}{:x}", r / 17, g / 17, b / 17, a / 17),
+            uppercase,
+        ),
+        Form::Hex(8) => hex8.clone(),
+        Form::Hex(_) => hex6.clone(),
+        Form::Rgb => format!("rgb({r}, {g}, {b})"),
+        Form::Rgba => format!("rgba({r}, {g}, {b}, {})", format_alpha(alpha)),
+    };
+
+    let actions = vec![
+        CopyAction::new("Hex (RGB)", hex6.clone()),
+        CopyAction::new("Hex (RGBA)", hex8.clone()),
+        CopyAction::new("rgb()", format!("rgb({r}, {g}, {b})")),
+        CopyAction::new("rgba()", format!("rgba({r}, {g}, {b}, {})", format_alpha(alpha))),
+        CopyAction::new("hsl()", hsl),
+        CopyAction::new("CSS variable", format!("--color: {hex6};")),
+    ];
 
     InterpretResult::new(vec![
         InterpretItem::with_color("Preview", &hex6, [r, g, b, a]),
+        InterpretItem::text("Normalized", normalized),
         InterpretItem::text("Hex (RGB)", hex6),
         InterpretItem::text("Hex (RGBA)", hex8),
         InterpretItem::text("R", r.to_string()),
@@ -101,6 +155,23 @@ fn build_result(r: u8, g: u8, b: u8, a: u8) -> InterpretResult {
         InterpretItem::text("A", format!("{} ({})", a, alpha_pct)),
         InterpretItem::text("HSL", format!("hsl({:.0}°, {:.1}%, {:.1}%)", h, s * 100.0, l * 100.0)),
     ])
+    .with_actions(actions)
+}
+
+/// Apply the preferred letter case to the hex digits of `#…`.
+fn hex_case(hex: String, uppercase: bool) -> String {
+    if uppercase {
+        hex.to_uppercase()
+    } else {
+        hex
+    }
+}
+
+/// Format an alpha value (0–1) with up to 3 decimals and no trailing zeros.
+fn format_alpha(alpha: f32) -> String {
+    let s = format!("{alpha:.3}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s.is_empty() { "0".to_string() } else { s.to_string() }
 }
 
 /// Convert RGB (0–255) to HSL (H: 0–360, S: 0–1, L: 0–1).
@@ -130,3 +201,31 @@ fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
 
     (h * 60.0, s, l)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_ascii_hex_is_rejected() {
+        assert_eq!(parse_rgba("#éa"), None);
+        assert_eq!(parse_rgba("#ééé"), None);
+        assert_eq!(parse_rgba("0xé12345"), None);
+        assert!(ColorInterpreter.interpret("#éa").is_none());
+        assert!(matches!(crate::classify::classify("#éa"), crate::classify::ContentKind::Text));
+    }
+
+    #[test]
+    fn alpha_keeps_three_decimals() {
+        assert_eq!(format_alpha(128.0 / 255.0), "0.502");
+        assert_eq!(format_alpha(0.5), "0.5");
+        assert_eq!(format_alpha(1.0), "1");
+        assert_eq!(format_alpha(0.0), "0");
+    }
+
+    #[test]
+    fn short_and_long_hex_forms() {
+        assert_eq!(parse_rgba("#fa0"), Some([255, 170, 0, 255]));
+        assert_eq!(parse_rgba("#ff550080"), Some([255, 85, 0, 128]));
+    }
+}
@@ -0,0 +1,220 @@
+use sha2::{Digest as _, Sha256};
+use sha3::Keccak256;
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// Final polymod values for BIP-173 bech32 and BIP-350 bech32m.
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+const CHECKSUM_OK: &str = "✔ valid";
+const CHECKSUM_BAD: &str = "✘ INVALID — likely mistyped or tampered, do not send funds";
+
+pub struct CryptoAddressInterpreter;
+
+impl Interpreter for CryptoAddressInterpreter {
+    fn name(&self) -> &str {
+        "Crypto Address"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if let Some(hex) = trimmed.strip_prefix("0x") {
+            return ethereum(hex);
+        }
+        let lower = trimmed.to_ascii_lowercase();
+        if ["bc1", "tb1", "bcrt1"].iter().any(|p| lower.starts_with(p)) {
+            return bech32(trimmed);
+        }
+        base58check(trimmed)
+    }
+}
+
+fn ethereum(hex: &str) -> Option<InterpretResult> {
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let checksummed = format!("0x{}", eip55(hex));
+    let has_lower = hex.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex.chars().any(|c| c.is_ascii_uppercase());
+    let checksum = if has_lower && has_upper {
+        if checksummed[2..] == *hex {
+            CHECKSUM_OK
+        } else {
+            CHECKSUM_BAD
+        }
+    } else {
+        "not present (single-case address, EIP-55 can't verify it)"
+    };
+
+    Some(
+        InterpretResult::new(vec![
+            InterpretItem::text("Network", "Ethereum / EVM"),
+            InterpretItem::text("Type", "Account or contract address"),
+            InterpretItem::text("Checksum", checksum),
+            InterpretItem::text("EIP-55 form", checksummed.clone()),
+        ])
+        .with_actions(vec![CopyAction::new("EIP-55", checksummed)]),
+    )
+}
+
+/// EIP-55 mixed-case form: a letter is upper-cased when the matching nibble
+/// of keccak256(lowercase hex) is 8 or more.
+fn eip55(hex: &str) -> String {
+    let lower = hex.to_ascii_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+    lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Legacy (P2PKH) and script (P2SH) Bitcoin addresses.
+fn base58check(s: &str) -> Option<InterpretResult> {
+    if !(25..=35).contains(&s.len()) || !s.starts_with(['1', '3', 'm', 'n', '2']) {
+        return None;
+    }
+    let bytes = base58_decode(s)?;
+    if bytes.len() != 25 {
+        return None;
+    }
+    let (payload, checksum) = bytes.split_at(21);
+    let valid = Sha256::digest(Sha256::digest(payload))[..4] == *checksum;
+
+    let (network, kind) = match payload[0] {
+        0x00 => ("Bitcoin mainnet", "P2PKH (legacy)"),
+        0x05 => ("Bitcoin mainnet", "P2SH (script)"),
+        0x6f => ("Bitcoin testnet", "P2PKH (legacy)"),
+        0xc4 => ("Bitcoin testnet", "P2SH (script)"),
+        _ => return None,
+    };
+    Some(InterpretResult::new(vec![
+        InterpretItem::text("Network", network),
+        InterpretItem::text("Type", kind),
+        InterpretItem::text("Checksum", if valid { CHECKSUM_OK } else { CHECKSUM_BAD }),
+        InterpretItem::text("Hash160", hex_string(&payload[1..])),
+    ]))
+}
+
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    // Little-endian base-256 accumulator.
+    let mut num: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in &mut num {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            num.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' encodes a leading zero byte.
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    let mut out = vec![0u8; zeros];
+    out.extend(num.iter().rev());
+    Some(out)
+}
+
+/// SegWit addresses (BIP-173 bech32 for v0, BIP-350 bech32m for v1+).
+fn bech32(s: &str) -> Option<InterpretResult> {
+    let mixed_case = s.contains(char::is_lowercase) && s.contains(char::is_uppercase);
+    if !(14..=90).contains(&s.len()) || mixed_case {
+        return None;
+    }
+    let lower = s.to_ascii_lowercase();
+    let (hrp, data) = lower.rsplit_once('1')?;
+    let values: Vec<u8> = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&a| a == c).map(|v| v as u8))
+        .collect::<Option<_>>()?;
+    if values.len() < 7 {
+        return None;
+    }
+
+    let network = match hrp {
+        "bc" => "Bitcoin mainnet",
+        "tb" => "Bitcoin testnet",
+        "bcrt" => "Bitcoin regtest",
+        _ => return None,
+    };
+    let version = values[0];
+    let program = convert_bits(&values[1..values.len() - 6])?;
+    let kind = match (version, program.len()) {
+        (0, 20) => "P2WPKH (native SegWit)".to_string(),
+        (0, 32) => "P2WSH (native SegWit script)".to_string(),
+        (1, 32) => "P2TR (Taproot)".to_string(),
+        (v, _) => format!("Witness v{v} program"),
+    };
+
+    let expected = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+    let checksum = match bech32_polymod(hrp, &values) {
+        c if c == expected => CHECKSUM_OK,
+        BECH32_CONST | BECH32M_CONST => {
+            "✘ INVALID — wrong encoding variant for this witness version"
+        }
+        _ => CHECKSUM_BAD,
+    };
+    Some(InterpretResult::new(vec![
+        InterpretItem::text("Network", network),
+        InterpretItem::text("Type", kind),
+        InterpretItem::text("Checksum", checksum),
+        InterpretItem::text("Witness program", hex_string(&program)),
+    ]))
+}
+
+fn bech32_polymod(hrp: &str, values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let expanded = hrp
+        .bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 0x1f))
+        .chain(values.iter().copied());
+    expanded.fold(1u32, |chk, v| {
+        let top = chk >> 25;
+        let mut chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(v);
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+        chk
+    })
+}
+
+/// Regroup 5-bit values into bytes; leftover bits must be zero padding.
+fn convert_bits(values: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(values.len() * 5 / 8);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &v in values {
+        acc = (acc << 5) | u32::from(v);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
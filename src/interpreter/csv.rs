@@ -0,0 +1,152 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Candidate delimiters, in order of preference when several fit equally well.
+const DELIMITERS: [(char, &str); 3] = [('\t', "Tab"), (',', "Comma"), (';', "Semicolon")];
+/// Rows inspected when sniffing the delimiter.
+const SNIFF_ROWS: usize = 5;
+/// Tables up to this size get an aligned preview.
+const PREVIEW_MAX_ROWS: usize = 20;
+const PREVIEW_MAX_COLS: usize = 10;
+/// Longer cells are cut in the preview.
+const PREVIEW_CELL_CHARS: usize = 24;
+
+pub struct CsvInterpreter;
+
+impl Interpreter for CsvInterpreter {
+    fn name(&self) -> &str {
+        "CSV / TSV"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let (delimiter, name) = sniff_delimiter(content)?;
+        let rows = parse_rows(content, delimiter);
+        let widths: Vec<usize> = rows.iter().map(Vec::len).collect();
+        let min_cols = *widths.iter().min()?;
+        let max_cols = *widths.iter().max()?;
+        let header = &rows[0];
+
+        let mut items = vec![
+            InterpretItem::text("Delimiter", name),
+            InterpretItem::text("Rows", format!("{} (+ header)", rows.len() - 1)),
+            InterpretItem::text("Columns", max_cols.to_string()),
+            InterpretItem::text(
+                "Consistent",
+                if min_cols == max_cols {
+                    "yes".to_string()
+                } else {
+                    format!("no ({min_cols}–{max_cols} fields per row)")
+                },
+            ),
+            InterpretItem::text("Header", header.join(" | ")),
+        ];
+        if rows.len() <= PREVIEW_MAX_ROWS && max_cols <= PREVIEW_MAX_COLS {
+            items.push(InterpretItem::text("Preview", aligned_preview(&rows, max_cols)));
+        }
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// Pick the delimiter that splits the first few rows into the same number
+/// (≥ 2) of fields. Needs at least two rows, and comma/semicolon splits that
+/// look like sentences are rejected so prose doesn't qualify.
+fn sniff_delimiter(content: &str) -> Option<(char, &'static str)> {
+    let head: String = content.split_inclusive('\n').take(SNIFF_ROWS).collect();
+    let mut best: Option<(usize, char, &'static str)> = None;
+    for (delimiter, name) in DELIMITERS {
+        if !head.contains(delimiter) {
+            continue;
+        }
+        let sample = parse_rows(&head, delimiter);
+        if sample.len() < 2 {
+            continue;
+        }
+        let cols = sample[0].len();
+        if cols < 2 || sample.iter().any(|row| row.len() != cols) {
+            continue;
+        }
+        if delimiter != '\t' && looks_like_prose(&sample) {
+            continue;
+        }
+        if best.is_none_or(|(best_cols, ..)| cols > best_cols) {
+            best = Some((cols, delimiter, name));
+        }
+    }
+    best.map(|(_, delimiter, name)| (delimiter, name))
+}
+
+/// Half or more of the fields read like phrases (three or more words).
+fn looks_like_prose(rows: &[Vec<String>]) -> bool {
+    let fields: Vec<&String> = rows.iter().flatten().collect();
+    let wordy = fields
+        .iter()
+        .filter(|f| f.split_whitespace().count() >= 3)
+        .count();
+    wordy * 2 >= fields.len()
+}
+
+/// Split `content` into rows of trimmed fields. Double-quoted fields may
+/// contain the delimiter, newlines and `""` escapes. Blank lines are skipped.
+fn parse_rows(content: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            c if in_quotes => field.push(c),
+            c if c == delimiter => row.push(std::mem::take(&mut field).trim().to_string()),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field).trim().to_string());
+                push_row(&mut rows, std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    row.push(field.trim().to_string());
+    push_row(&mut rows, row);
+    rows
+}
+
+fn push_row(rows: &mut Vec<Vec<String>>, row: Vec<String>) {
+    if !(row.len() == 1 && row[0].is_empty()) {
+        rows.push(row);
+    }
+}
+
+/// Fixed-width rendering of the table with cells padded to their column width.
+fn aligned_preview(rows: &[Vec<String>], cols: usize) -> String {
+    let cell = |row: &Vec<String>, c: usize| -> String {
+        let text = row.get(c).map(String::as_str).unwrap_or("");
+        if text.chars().count() > PREVIEW_CELL_CHARS {
+            let cut: String = text.chars().take(PREVIEW_CELL_CHARS - 1).collect();
+            format!("{cut}…")
+        } else {
+            text.to_string()
+        }
+    };
+    let widths: Vec<usize> = (0..cols)
+        .map(|c| rows.iter().map(|r| cell(r, c).chars().count()).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|row| {
+            (0..cols)
+                .map(|c| format!("{:<w$}", cell(row, c), w = widths[c]))
+                .collect::<Vec<_>>()
+                .join(" │ ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,118 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use percent_encoding::percent_decode_str;
+
+use super::filepath::format_size;
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Media type assumed when the URI omits one (RFC 2397).
+const DEFAULT_MIME: &str = "text/plain";
+
+pub struct DataUriInterpreter;
+
+impl Interpreter for DataUriInterpreter {
+    fn name(&self) -> &str {
+        "Data URI"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        let rest = trimmed
+            .get(..5)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+            .map(|_| &trimmed[5..])?;
+        let (header, data) = rest.split_once(',')?;
+
+        let mut params: Vec<&str> = header.split(';').map(str::trim).collect();
+        let base64 = params.last().is_some_and(|p| p.eq_ignore_ascii_case("base64"));
+        if base64 {
+            params.pop();
+        }
+        let mime = match params.first() {
+            Some(m) if !m.is_empty() => m.to_ascii_lowercase(),
+            _ => DEFAULT_MIME.to_string(),
+        };
+        if !mime.contains('/') {
+            return None;
+        }
+        let bytes = if base64 {
+            // Base64 in CSS is sometimes wrapped or percent-escaped.
+            let unescaped = percent_decode_str(data).decode_utf8().ok()?;
+            let compact: String = unescaped.split_whitespace().collect();
+            STANDARD.decode(compact).ok()?
+        } else {
+            percent_decode_str(data).collect()
+        };
+
+        let mut items = vec![
+            InterpretItem::text("MIME type", mime.as_str()),
+            InterpretItem::text("Encoding", if base64 { "base64" } else { "percent-encoded" }),
+            InterpretItem::text(
+                "Decoded size",
+                format!("{} ({} bytes)", format_size(bytes.len() as u64), bytes.len()),
+            ),
+        ];
+        if params.len() > 1 {
+            items.push(InterpretItem::text("Parameters", params[1..].join("; ")));
+        }
+
+        let mut actions = Vec::new();
+        if is_textual(&mime) {
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+            items.push(InterpretItem::text("Text", text.as_str()));
+            actions.push(CopyAction::new("Decoded text", text));
+        }
+        if mime.starts_with("image/") {
+            let dimensions = match image_dimensions(&bytes) {
+                Some((w, h)) => format!("{w} × {h} px"),
+                None => "unknown (unsupported or corrupt image)".to_string(),
+            };
+            items.push(InterpretItem::text("Dimensions", dimensions));
+        }
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// `text/*` plus the structured text types that commonly appear in data URIs.
+fn is_textual(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || mime.ends_with("+xml")
+        || mime.ends_with("+json")
+        || matches!(mime, "application/json" | "application/xml" | "application/javascript")
+}
+
+/// Width and height from the header of a PNG, GIF, JPEG or BMP file.
+fn image_dimensions(b: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes(b.get(i..i + 2)?.try_into().ok()?) as u32);
+    let le16 = |i: usize| Some(u16::from_le_bytes(b.get(i..i + 2)?.try_into().ok()?) as u32);
+    let be32 = |i: usize| Some(u32::from_be_bytes(b.get(i..i + 4)?.try_into().ok()?));
+    let le32 = |i: usize| Some(i32::from_le_bytes(b.get(i..i + 4)?.try_into().ok()?));
+
+    if b.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(16)?, be32(20)?));
+    }
+    if b.starts_with(b"GIF87a") || b.starts_with(b"GIF89a") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if b.starts_with(b"BM") {
+        // Height is negative for top-down bitmaps.
+        return Some((le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs()));
+    }
+    if b.starts_with(&[0xff, 0xd8]) {
+        // Walk the segments to the first start-of-frame marker.
+        let mut i = 2;
+        while i + 4 <= b.len() {
+            if b[i] != 0xff {
+                return None;
+            }
+            let marker = b[i + 1];
+            let len = be16(i + 2)? as usize;
+            let is_sof = matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+            if is_sof {
+                return Some((be16(i + 7)?, be16(i + 5)?));
+            }
+            i += 2 + len;
+        }
+    }
+    None
+}
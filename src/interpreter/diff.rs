@@ -0,0 +1,214 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Lines of the patch shown in the colored preview.
+const PREVIEW_LINES: usize = 200;
+
+const ADDED: [u8; 4] = [80, 200, 120, 255];
+const REMOVED: [u8; 4] = [230, 90, 90, 255];
+const HUNK: [u8; 4] = [100, 160, 230, 255];
+
+pub struct DiffInterpreter;
+
+impl Interpreter for DiffInterpreter {
+    fn name(&self) -> &str {
+        "Diff / Patch"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let patch = parse(content)?;
+        let additions: usize = patch.files.iter().map(|f| f.additions).sum();
+        let deletions: usize = patch.files.iter().map(|f| f.deletions).sum();
+        let files: Vec<String> = patch
+            .files
+            .iter()
+            .map(|f| format!("{}  +{} −{}", f.name, f.additions, f.deletions))
+            .collect();
+
+        let mut preview: Vec<&str> = content.lines().take(PREVIEW_LINES).collect();
+        let mut colors: Vec<Option<[u8; 4]>> =
+            patch.line_kinds.iter().take(PREVIEW_LINES).map(|kind| kind.color()).collect();
+        if patch.line_kinds.len() > PREVIEW_LINES {
+            preview.push("…");
+            colors.push(None);
+        }
+
+        Some(InterpretResult::new(vec![
+            InterpretItem::text("Files", files.join("\n")),
+            InterpretItem::text("Hunks", patch.hunks.to_string()),
+            InterpretItem::text("Additions", additions.to_string()),
+            InterpretItem::text("Deletions", deletions.to_string()),
+            InterpretItem::with_line_colors("Preview", preview.join("\n"), colors),
+        ]))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum LineKind {
+    Other,
+    Hunk,
+    Added,
+    Removed,
+}
+
+impl LineKind {
+    fn color(self) -> Option<[u8; 4]> {
+        match self {
+            LineKind::Other => None,
+            LineKind::Hunk => Some(HUNK),
+            LineKind::Added => Some(ADDED),
+            LineKind::Removed => Some(REMOVED),
+        }
+    }
+}
+
+struct FileStats {
+    name: String,
+    additions: usize,
+    deletions: usize,
+}
+
+struct Patch {
+    files: Vec<FileStats>,
+    hunks: usize,
+    /// One per line of the input.
+    line_kinds: Vec<LineKind>,
+}
+
+/// Walk the patch file by file. Hunk bodies are consumed by the line counts
+/// in their `@@` header, so a removed line that itself starts with `--` isn't
+/// mistaken for the next file header. `None` unless there is at least one
+/// `---`/`+++` header pair followed by a hunk.
+fn parse(content: &str) -> Option<Patch> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut patch =
+        Patch { files: Vec::new(), hunks: 0, line_kinds: vec![LineKind::Other; lines.len()] };
+    let mut i = 0;
+    while i < lines.len() {
+        let new = lines.get(i + 1).and_then(|l| l.strip_prefix("+++ "));
+        let (Some(old), Some(new)) = (lines[i].strip_prefix("--- "), new) else {
+            i += 1;
+            continue;
+        };
+        let mut file = FileStats { name: file_name(old, new), additions: 0, deletions: 0 };
+        i += 2;
+        while let Some((mut old_left, mut new_left)) = lines.get(i).and_then(|l| hunk_header(l)) {
+            patch.line_kinds[i] = LineKind::Hunk;
+            patch.hunks += 1;
+            i += 1;
+            while i < lines.len() && (old_left > 0 || new_left > 0) {
+                let line = lines[i];
+                if line.starts_with('+') && new_left > 0 {
+                    patch.line_kinds[i] = LineKind::Added;
+                    file.additions += 1;
+                    new_left -= 1;
+                } else if line.starts_with('-') && old_left > 0 {
+                    patch.line_kinds[i] = LineKind::Removed;
+                    file.deletions += 1;
+                    old_left -= 1;
+                } else if line.starts_with(' ') || line.is_empty() {
+                    // Some tools strip the space off empty context lines.
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                } else if !line.starts_with('\\') {
+                    // "\ No newline at end of file" is the only other line
+                    // allowed; anything else means the hunk was cut short.
+                    break;
+                }
+                i += 1;
+            }
+            // The marker can also follow the last line of a hunk.
+            if lines.get(i).is_some_and(|l| l.starts_with('\\')) {
+                i += 1;
+            }
+        }
+        patch.files.push(file);
+    }
+    (patch.hunks > 0).then_some(patch)
+}
+
+/// Old and new line counts from `@@ -l,s +l,s @@`; a missing count means 1.
+fn hunk_header(line: &str) -> Option<(usize, usize)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let count = |range: &str| -> Option<usize> {
+        match range.split_once(',') {
+            Some((start, len)) => {
+                start.parse::<usize>().ok()?;
+                len.parse().ok()
+            }
+            None => range.parse::<usize>().ok().map(|_| 1),
+        }
+    };
+    Some((count(old)?, count(new)?))
+}
+
+/// The path a header pair refers to: the new path, or the old one for a
+/// deletion, without git's `a/`/`b/` prefixes or a trailing timestamp.
+fn file_name(old: &str, new: &str) -> String {
+    let path = |header: &str| -> String {
+        let path = header.split('\t').next().unwrap_or(header).trim_end();
+        path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path).to_string()
+    };
+    let (old, new) = (path(old), path(new));
+    match (old.as_str(), new.as_str()) {
+        (_, "/dev/null") => format!("{old} (deleted)"),
+        ("/dev/null", _) => format!("{new} (new)"),
+        _ if old != new => format!("{old} → {new}"),
+        _ => new,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,4 @@
+ fn main() {
+--- removed line starting with dashes
++++ added line starting with pluses
+ }
+
+--- /dev/null
++++ b/NEW.md
+@@ -0,0 +1 @@
++hello
+\\ No newline at end of file
+";
+
+    #[test]
+    fn counts_files_hunks_and_lines() {
+        let patch = parse(PATCH).unwrap();
+        assert_eq!(patch.hunks, 2);
+        let files: Vec<(&str, usize, usize)> =
+            patch.files.iter().map(|f| (f.name.as_str(), f.additions, f.deletions)).collect();
+        assert_eq!(files, [("src/lib.rs", 1, 1), ("NEW.md (new)", 1, 0)]);
+        assert!(patch.line_kinds[5] == LineKind::Removed);
+        assert!(patch.line_kinds[6] == LineKind::Added);
+    }
+
+    #[test]
+    fn hunk_header_counts() {
+        assert_eq!(hunk_header("@@ -1,4 +1,5 @@ fn main"), Some((4, 5)));
+        assert_eq!(hunk_header("@@ -3 +3 @@"), Some((1, 1)));
+        assert_eq!(hunk_header("@@ -a,1 +1 @@"), None);
+        assert_eq!(hunk_header("@@ not a hunk"), None);
+    }
+
+    #[test]
+    fn file_names_drop_prefixes_and_timestamps() {
+        assert_eq!(file_name("a/x.rs", "b/x.rs"), "x.rs");
+        assert_eq!(file_name("x.rs\t2024-01-01", "/dev/null"), "x.rs (deleted)");
+        assert_eq!(file_name("a/old.rs", "b/new.rs"), "old.rs → new.rs");
+    }
+
+    #[test]
+    fn headers_without_hunks_are_not_a_patch() {
+        assert!(parse("--- a\n+++ b\n").is_none());
+        assert!(parse("- item\n+ item\n").is_none());
+    }
+}
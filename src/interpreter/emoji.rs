@@ -0,0 +1,195 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Distinct emoji or shortcodes listed one per row; the counts cover all.
+const MAX_LISTED: usize = 50;
+
+pub struct EmojiInterpreter;
+
+impl Interpreter for EmojiInterpreter {
+    fn name(&self) -> &str {
+        "Emoji"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let emoji: Vec<&'static emojis::Emoji> =
+            content.graphemes(true).filter_map(as_emoji).collect();
+        let shortcodes = find_shortcodes(content);
+        if emoji.is_empty() && shortcodes.is_empty() {
+            return None;
+        }
+
+        let mut items = Vec::new();
+        let mut actions = Vec::new();
+        if !emoji.is_empty() {
+            let distinct = unique(&emoji);
+            items.push(InterpretItem::text(
+                "Emoji",
+                format!("{} ({} distinct)", emoji.len(), distinct.len()),
+            ));
+            for e in distinct.iter().take(MAX_LISTED) {
+                let count = emoji.iter().filter(|x| *x == e).count();
+                let mut value = format!("{}  {}", e.name(), code_points(e.as_str()));
+                if let Some(code) = e.shortcode() {
+                    value.push_str(&format!("  :{code}:"));
+                } else if let Some(code) = untoned_shortcode(e) {
+                    value.push_str(&format!("  :{code}: (without skin tone)"));
+                }
+                if count > 1 {
+                    value.push_str(&format!("  ×{count}"));
+                }
+                items.push(InterpretItem::text(e.as_str(), value));
+            }
+            if distinct.len() > MAX_LISTED {
+                let rest = distinct.len() - MAX_LISTED;
+                items.push(InterpretItem::text("…", format!("{rest} more not listed")));
+            }
+            actions.push(CopyAction::new("With shortcodes", to_shortcodes(content)));
+        }
+        if !shortcodes.is_empty() {
+            let emoji: Vec<&'static emojis::Emoji> =
+                shortcodes.iter().map(|(_, e)| *e).collect();
+            items.push(InterpretItem::text(
+                "Shortcodes",
+                format!("{} ({} distinct)", shortcodes.len(), unique(&emoji).len()),
+            ));
+            let mut listed: Vec<&str> = Vec::new();
+            for (code, e) in &shortcodes {
+                if listed.contains(code) {
+                    continue;
+                }
+                if listed.len() == MAX_LISTED {
+                    break;
+                }
+                listed.push(code);
+                let value = format!("{}  {}", e.as_str(), e.name());
+                items.push(InterpretItem::text(format!(":{code}:"), value));
+            }
+            let converted = from_shortcodes(content);
+            items.push(InterpretItem::text("Converted", &converted));
+            actions.push(CopyAction::new("With emoji", converted));
+        }
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// The emoji a grapheme cluster spells, ZWJ sequences and skin tones
+/// included. Characters that are text by default (`©`, `™`, `↔`) only count
+/// when followed by the emoji variation selector.
+fn as_emoji(grapheme: &str) -> Option<&'static emojis::Emoji> {
+    let emoji = emojis::get(grapheme)?;
+    (emoji.as_str() == grapheme || grapheme.contains('\u{FE0F}')).then_some(emoji)
+}
+
+/// Shortcodes only exist for the default skin tone; this is that one's.
+fn untoned_shortcode(emoji: &'static emojis::Emoji) -> Option<&'static str> {
+    emoji.with_skin_tone(emojis::SkinTone::Default)?.shortcode()
+}
+
+/// `U+XXXX` for every code point, e.g. `U+1F44B U+1F3FD` for 👋🏽.
+fn code_points(s: &str) -> String {
+    s.chars().map(|c| format!("U+{:04X}", c as u32)).collect::<Vec<_>>().join(" ")
+}
+
+fn unique(emoji: &[&'static emojis::Emoji]) -> Vec<&'static emojis::Emoji> {
+    let mut out: Vec<&'static emojis::Emoji> = Vec::new();
+    for e in emoji {
+        if !out.contains(e) {
+            out.push(e);
+        }
+    }
+    out
+}
+
+/// Every `:shortcode:` GitHub knows, in order, with its emoji. A colon that
+/// doesn't close a known code can still open the next one.
+fn find_shortcodes(s: &str) -> Vec<(&str, &'static emojis::Emoji)> {
+    let mut found = Vec::new();
+    let mut rest = s;
+    while let Some(open) = rest.find(':') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find(':') else {
+            break;
+        };
+        let code = &after[..close];
+        let valid = !code.is_empty()
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'));
+        match emojis::get_by_shortcode(code).filter(|_| valid) {
+            Some(emoji) => {
+                found.push((code, emoji));
+                rest = &after[close + 1..];
+            }
+            None => rest = &after[close..],
+        }
+    }
+    found
+}
+
+/// `s` with each known `:shortcode:` replaced by its emoji.
+fn from_shortcodes(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    for (code, emoji) in find_shortcodes(s) {
+        let pattern = format!(":{code}:");
+        if let Some(at) = rest.find(&pattern) {
+            out.push_str(&rest[..at]);
+            out.push_str(emoji.as_str());
+            rest = &rest[at + pattern.len()..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `s` with each emoji that has a shortcode replaced by `:shortcode:`.
+fn to_shortcodes(s: &str) -> String {
+    s.graphemes(true)
+        .map(|g| match as_emoji(g).and_then(emojis::Emoji::shortcode) {
+            Some(code) => format!(":{code}:"),
+            None => g.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcodes_convert_both_ways() {
+        assert_eq!(from_shortcodes("ship it :rocket: :tada:"), "ship it 🚀 🎉");
+        assert_eq!(to_shortcodes("ship it 🚀 🎉"), "ship it :rocket: :tada:");
+    }
+
+    #[test]
+    fn unknown_codes_and_stray_colons_are_kept() {
+        assert_eq!(
+            from_shortcodes("time 10:30 :not_a_code: :smile:"),
+            "time 10:30 :not_a_code: 😄"
+        );
+        let codes: Vec<&str> = find_shortcodes("re:qqq:+1:").iter().map(|(c, _)| *c).collect();
+        assert_eq!(codes, ["+1"]);
+    }
+
+    #[test]
+    fn text_style_symbols_need_the_variation_selector() {
+        assert!(as_emoji("©").is_none());
+        assert!(as_emoji("©\u{FE0F}").is_some());
+        assert!(as_emoji("a").is_none());
+    }
+
+    #[test]
+    fn zwj_sequences_and_skin_tones_are_one_emoji() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(family.graphemes(true).filter_map(as_emoji).count(), 1);
+        let wave = as_emoji("👋🏽").unwrap();
+        assert_eq!(code_points(wave.as_str()), "U+1F44B U+1F3FD");
+        assert_eq!(untoned_shortcode(wave), Some("wave"));
+    }
+
+    #[test]
+    fn plain_text_has_no_result() {
+        assert!(EmojiInterpreter.interpret("just words: nothing else").is_none());
+    }
+}
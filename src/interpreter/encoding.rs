@@ -0,0 +1,216 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Decoded text longer than this is cut off in the panel.
+const PREVIEW_CHARS: usize = 4000;
+/// Longest encoded quoted-printable line, `=` of a soft break included.
+const QP_LINE_LEN: usize = 76;
+
+/// Everything but RFC 3986 unreserved characters, as `encodeURIComponent`
+/// would (except that it also keeps `!'()*`).
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+pub struct EncodingInterpreter;
+
+impl Interpreter for EncodingInterpreter {
+    fn name(&self) -> &str {
+        "Quoted-Printable / URL Encoding"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let percent = count_escapes(content, '%');
+        let qp = count_escapes(content, '=');
+        let soft_breaks = content.matches("=\n").count() + content.matches("=\r\n").count();
+        if percent == 0 && qp == 0 && soft_breaks == 0 {
+            return None;
+        }
+
+        // The escape syntax decides; the one with more escapes wins, and
+        // soft line breaks only occur in quoted-printable.
+        let (encoding, decoded, escapes, reencoded) = if qp + soft_breaks > percent {
+            let decoded = String::from_utf8(decode_qp(content)).ok()?;
+            let reencoded = encode_qp(&decoded);
+            ("Quoted-printable", decoded, qp, reencoded)
+        } else {
+            // `+` means a space in form data, which has no literal spaces.
+            let form = !content.trim().contains(char::is_whitespace) && content.contains('=');
+            let decoded = String::from_utf8(decode_percent(content, form)).ok()?;
+            let mut reencoded = utf8_percent_encode(&decoded, COMPONENT).to_string();
+            if form {
+                reencoded = reencoded.replace("%20", "+");
+            }
+            let encoding = if form { "URL encoding (form, + as space)" } else { "URL encoding" };
+            (encoding, decoded, percent, reencoded)
+        };
+
+        let mut escapes_text = escapes.to_string();
+        if soft_breaks > 0 && encoding == "Quoted-printable" {
+            escapes_text.push_str(&format!(" + {soft_breaks} soft line breaks"));
+        }
+        let preview = if decoded.chars().count() > PREVIEW_CHARS {
+            format!("{}…", decoded.chars().take(PREVIEW_CHARS).collect::<String>())
+        } else {
+            decoded.clone()
+        };
+        Some(
+            InterpretResult::new(vec![
+                InterpretItem::text("Encoding", encoding),
+                InterpretItem::text("Escapes decoded", escapes_text),
+                InterpretItem::text("Decoded", preview),
+            ])
+            .with_actions(vec![
+                CopyAction::new("Decoded", decoded),
+                CopyAction::new("Re-encoded", reencoded),
+            ]),
+        )
+    }
+}
+
+/// Occurrences of `marker` followed by two hex digits. Quoted-printable
+/// escapes are upper case by definition, so `=` only counts with those.
+fn count_escapes(s: &str, marker: char) -> usize {
+    let bytes = s.as_bytes();
+    let is_digit = |b: u8| match marker {
+        '=' => b.is_ascii_digit() || (b'A'..=b'F').contains(&b),
+        _ => b.is_ascii_hexdigit(),
+    };
+    bytes
+        .windows(3)
+        .filter(|w| w[0] == marker as u8 && is_digit(w[1]) && is_digit(w[2]))
+        .count()
+}
+
+/// The byte two hex digits spell.
+fn escaped(hi: u8, lo: u8) -> Option<u8> {
+    let digit = |b: u8| (b as char).to_digit(16);
+    Some((digit(hi)? << 4 | digit(lo)?) as u8)
+}
+
+/// `%XX` escapes, and `+` as a space when `form` is set.
+fn decode_percent(s: &str, form: bool) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => match escaped(bytes[i + 1], bytes[i + 2]) {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 3;
+                    continue;
+                }
+                None => out.push(b'%'),
+            },
+            b'+' if form => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// RFC 2045 decoding: `=XX` escapes, and `=` at the end of a line (soft line
+/// break, trailing whitespace allowed) joins it with the next. Anything
+/// malformed is kept as is.
+fn decode_qp(s: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut lines = s.split('\n').peekable();
+    while let Some(line) = lines.next() {
+        let (line, ending) = match line.strip_suffix('\r') {
+            Some(line) => (line, "\r\n"),
+            None => (line, "\n"),
+        };
+        let (line, soft) = match line.trim_end_matches([' ', '\t']).strip_suffix('=') {
+            Some(body) => (body, true),
+            None => (line, false),
+        };
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'=' && i + 2 < bytes.len() {
+                if let Some(byte) = escaped(bytes[i + 1], bytes[i + 2]) {
+                    out.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        if !soft && lines.peek().is_some() {
+            out.extend_from_slice(ending.as_bytes());
+        }
+    }
+    out
+}
+
+/// RFC 2045 encoding with `=XX` for bytes outside printable ASCII, `=`
+/// itself and whitespace at the end of a line, and soft breaks keeping
+/// lines within `QP_LINE_LEN`.
+fn encode_qp(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for chunk in s.split_inclusive('\n') {
+        let line = chunk.trim_end_matches(['\r', '\n']);
+        let bytes = line.as_bytes();
+        let mut width = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            let last = i + 1 == bytes.len();
+            let literal = (b'!'..=b'~').contains(&b) && b != b'='
+                || (matches!(b, b' ' | b'\t') && !last);
+            let token = if literal { (b as char).to_string() } else { format!("={b:02X}") };
+            // Leave room for the `=` of a soft break unless this ends the line.
+            let limit = if last { QP_LINE_LEN } else { QP_LINE_LEN - 1 };
+            if width + token.len() > limit {
+                out.push_str("=\n");
+                width = 0;
+            }
+            out.push_str(&token);
+            width += token.len();
+        }
+        out.push_str(&chunk[line.len()..]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(content: &str) -> Option<String> {
+        let result = EncodingInterpreter.interpret(content)?;
+        let item = result.items.iter().find(|item| item.label == "Decoded")?;
+        Some(item.value.clone())
+    }
+
+    #[test]
+    fn percent_and_form_decoding() {
+        assert_eq!(decoded("caf%C3%A9%20au%20lait").as_deref(), Some("café au lait"));
+        assert_eq!(decoded("q=a+b%26c").as_deref(), Some("q=a b&c"));
+        // Literal spaces mean it isn't form data, so `+` stays.
+        assert_eq!(decoded("1+1 is 100%25").as_deref(), Some("1+1 is 100%"));
+    }
+
+    #[test]
+    fn quoted_printable_with_soft_breaks() {
+        assert_eq!(decoded("caf=C3=A9 =\r\nau lait=3D").as_deref(), Some("café au lait="));
+        assert_eq!(decode_qp("a=\nb\r\nc"), b"ab\r\nc");
+    }
+
+    #[test]
+    fn malformed_or_non_utf8_escapes_are_skipped() {
+        assert!(EncodingInterpreter.interpret("no escapes here").is_none());
+        assert!(EncodingInterpreter.interpret("color=FF0000").is_none());
+        assert!(EncodingInterpreter.interpret("%FF%FE").is_none());
+        assert_eq!(decode_percent("100% %zz", false), b"100% %zz");
+    }
+
+    #[test]
+    fn quoted_printable_round_trips_and_wraps() {
+        let text = format!("{} é=\ntrailing \n", "x".repeat(100));
+        let encoded = encode_qp(&text);
+        assert!(encoded.lines().all(|line| line.len() <= QP_LINE_LEN), "{encoded}");
+        assert!(encoded.contains("trailing=20\n"));
+        assert_eq!(String::from_utf8(decode_qp(&encoded)).unwrap(), text);
+    }
+}
@@ -0,0 +1,120 @@
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// OpenStreetMap zoom level for the map link (15 ≈ streets).
+const OSM_ZOOM: u32 = 15;
+/// Decimal places in normalized output; 6 is about 10 cm.
+const DECIMALS: usize = 6;
+
+pub struct GeoInterpreter;
+
+impl Interpreter for GeoInterpreter {
+    fn name(&self) -> &str {
+        "GPS Coordinates"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if trimmed.contains('\n') {
+            return None;
+        }
+        let (lat, lon, form) = match parse_decimal(trimmed) {
+            Some((lat, lon)) => (lat, lon, "Decimal degrees"),
+            None => {
+                let (lat, lon) = parse_dms(trimmed)?;
+                (lat, lon, "Degrees, minutes, seconds")
+            }
+        };
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return None;
+        }
+
+        let decimal = format!("{lat:.DECIMALS$}, {lon:.DECIMALS$}");
+        let dms = format!("{} {}", to_dms(lat, 'N', 'S'), to_dms(lon, 'E', 'W'));
+        let geo_uri = format!("geo:{lat:.DECIMALS$},{lon:.DECIMALS$}");
+        let osm = format!(
+            "https://www.openstreetmap.org/?mlat={lat:.DECIMALS$}&mlon={lon:.DECIMALS$}\
+             #map={OSM_ZOOM}/{lat:.DECIMALS$}/{lon:.DECIMALS$}"
+        );
+
+        let items = vec![
+            InterpretItem::text("Format", form),
+            InterpretItem::text("Decimal", decimal.as_str()),
+            InterpretItem::text("DMS", dms.as_str()),
+            InterpretItem::text("geo: URI", geo_uri.as_str()),
+            InterpretItem::text("OpenStreetMap", osm.as_str()),
+        ];
+        let actions = vec![
+            CopyAction::new("Decimal", decimal),
+            CopyAction::new("DMS", dms),
+            CopyAction::new("geo: URI", geo_uri),
+            CopyAction::new("OpenStreetMap link", osm),
+        ];
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// `35.6895, 139.6917`. Both numbers need a fractional part, so integer pairs
+/// like `3, 4` are left to other interpreters.
+fn parse_decimal(s: &str) -> Option<(f64, f64)> {
+    let (lat, lon) = s.split_once(',')?;
+    let parse = |part: &str| -> Option<f64> {
+        let part = part.trim();
+        let (int, frac) = part.split_once('.')?;
+        let digits = int.strip_prefix(['-', '+']).unwrap_or(int);
+        let valid = !digits.is_empty()
+            && !frac.is_empty()
+            && digits.chars().all(|c| c.is_ascii_digit())
+            && frac.chars().all(|c| c.is_ascii_digit());
+        valid.then(|| part.parse().ok()).flatten()
+    };
+    Some((parse(lat)?, parse(lon)?))
+}
+
+/// `35°41'22"N 139°41'30"E`, with the latitude first. Minutes and seconds are
+/// optional; typographic primes (′ ″) and a separating comma are accepted.
+fn parse_dms(s: &str) -> Option<(f64, f64)> {
+    let split = s.find(['N', 'S', 'n', 's'])? + 1;
+    let (lat, lon) = s.split_at(split);
+    let lon = lon.trim_start_matches([',', ' ']);
+    Some((parse_dms_part(lat, 'N', 'S')?, parse_dms_part(lon, 'E', 'W')?))
+}
+
+/// One `D°M'S"H` component; `positive`/`negative` are the hemisphere letters.
+fn parse_dms_part(s: &str, positive: char, negative: char) -> Option<f64> {
+    let s = s.trim();
+    let hemisphere = s.chars().last()?.to_ascii_uppercase();
+    let sign = match hemisphere {
+        h if h == positive => 1.0,
+        h if h == negative => -1.0,
+        _ => return None,
+    };
+    let body = &s[..s.len() - 1];
+    if !body.contains('°') {
+        return None;
+    }
+    let numbers: Vec<f64> = body
+        .split(['°', '\'', '"', '′', '″', ' '])
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let (deg, min, sec) = match numbers.as_slice() {
+        [d] => (*d, 0.0, 0.0),
+        [d, m] => (*d, *m, 0.0),
+        [d, m, s] => (*d, *m, *s),
+        _ => return None,
+    };
+    if deg < 0.0 || !(0.0..60.0).contains(&min) || !(0.0..60.0).contains(&sec) {
+        return None;
+    }
+    Some(sign * (deg + min / 60.0 + sec / 3600.0))
+}
+
+/// `35.6895` → `35°41'22.2"N`.
+fn to_dms(value: f64, positive: char, negative: char) -> String {
+    let hemisphere = if value < 0.0 { negative } else { positive };
+    // Round to tenths of a second up front so 59.95" doesn't print as 60.0".
+    let tenths = (value.abs() * 36_000.0).round() as u64;
+    let (deg, rest) = (tenths / 36_000, tenths % 36_000);
+    let (min, sec_tenths) = (rest / 600, rest % 600);
+    format!("{deg}°{min}'{}.{}\"{hemisphere}", sec_tenths / 10, sec_tenths % 10)
+}
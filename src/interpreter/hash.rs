@@ -0,0 +1,39 @@
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+pub struct HashInterpreter;
+
+impl Interpreter for HashInterpreter {
+    fn name(&self) -> &str {
+        "Hash Digest"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if !trimmed.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        // Identification is by length only; other digests of the same size
+        // are listed as alternatives.
+        let (family, alternatives) = match trimmed.len() {
+            32 => ("MD5", "MD4, NTLM"),
+            40 => ("SHA-1", "RIPEMD-160"),
+            64 => ("SHA-256", "SHA3-256, BLAKE2s-256"),
+            96 => ("SHA-384", "SHA3-384"),
+            128 => ("SHA-512", "SHA3-512, BLAKE2b-512"),
+            _ => return None,
+        };
+        let normalized = trimmed.to_ascii_lowercase();
+        Some(
+            InterpretResult::new(vec![
+                InterpretItem::text("Likely", family),
+                InterpretItem::text("Also possible", alternatives),
+                InterpretItem::text("Bits", (trimmed.len() * 4).to_string()),
+                InterpretItem::text("Normalized", normalized.clone()),
+            ])
+            .with_actions(vec![
+                CopyAction::new("Lowercase", normalized),
+                CopyAction::new("Uppercase", trimmed.to_ascii_uppercase()),
+            ]),
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use super::{InterpretItem, InterpretResult, Interpreter};
+use super::{InterpretContext, InterpretItem, InterpretResult, Interpreter};
 
 pub struct HexInterpreter;
 
@@ -8,45 +8,192 @@ impl Interpreter for HexInterpreter {
     }
 
     fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        self.interpret_with(content, &InterpretContext::default())
+    }
+
+    fn interpret_with(&self, content: &str, ctx: &InterpretContext) -> Option<InterpretResult> {
         let bytes = content.as_bytes();
         let byte_count = bytes.len();
         let char_count = content.chars().count();
 
-        // Build hex string in groups of 8 bytes per line
-        let hex_lines: Vec<String> = bytes
-            .chunks(16)
-            .enumerate()
-            .map(|(i, chunk)| {
-                let offset = format!("{:04x}", i * 16);
-                let hex: String = chunk
-                    .iter()
-                    .map(|b| format!("{:02x}", b))
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                let ascii: String = chunk
-                    .iter()
-                    .map(|&b| {
-                        if (0x20..0x7f).contains(&b) {
-                            b as char
-                        } else {
-                            '.'
-                        }
-                    })
-                    .collect();
-                format!("{offset}  {hex:<47}  {ascii}")
-            })
-            .collect();
+        // Past MAX_FULL_BYTES only the first and last EDGE_BYTES are rendered,
+        // even with "show full"; the tail starts on a 16-byte row boundary.
+        let (head_len, tail_start) = if byte_count > MAX_FULL_BYTES {
+            (EDGE_BYTES, (byte_count - EDGE_BYTES) & !0xf)
+        } else {
+            (byte_count, byte_count)
+        };
 
+        let width = offset_width(byte_count);
+        let max_lines = ctx.max_dump_lines.unwrap_or(usize::MAX);
+        let mut hex_lines = dump_rows(&bytes[..head_len], 0, width, max_lines);
+
+        let shown_bytes = max_lines.saturating_mul(16).min(head_len);
+        let truncated = shown_bytes < head_len;
+        if truncated {
+            hex_lines.push(format!("… {} more bytes (show full)", byte_count - shown_bytes));
+        } else if tail_start < byte_count {
+            hex_lines.push(format!("… {} bytes omitted …", tail_start - head_len));
+            hex_lines.extend(dump_rows(&bytes[tail_start..], tail_start, width, usize::MAX));
+        }
         let hex_display = hex_lines.join("\n");
 
         // Also provide a plain compact hex string (useful for short content)
-        let compact_hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        let compact_hex = if tail_start < byte_count {
+            format!(
+                "{}… {} bytes omitted …{}",
+                compact(&bytes[..head_len]),
+                byte_count - head_len - EDGE_BYTES,
+                compact(&bytes[byte_count - EDGE_BYTES..])
+            )
+        } else {
+            compact(bytes)
+        };
 
-        Some(InterpretResult::new(vec![
+        let mut result = InterpretResult::new(vec![
             InterpretItem::text("Bytes", format!("{byte_count}")),
             InterpretItem::text("Chars (UTF-8)", format!("{char_count}")),
             InterpretItem::text("Compact hex", compact_hex),
             InterpretItem::text("Hex dump", hex_display),
-        ]))
+        ]);
+        result.truncated = truncated;
+        Some(result)
+    }
+}
+
+/// Content up to this size is dumped in full when "show full" is on. Beyond
+/// it the dump would freeze the UI, so only both ends are shown.
+const MAX_FULL_BYTES: usize = 64 * 1024;
+/// Bytes shown at each end of content larger than `MAX_FULL_BYTES`.
+const EDGE_BYTES: usize = 4 * 1024;
+
+/// Rows of 16 bytes (`offset  hex  ascii`), numbered from `start_offset`.
+fn dump_rows(bytes: &[u8], start_offset: usize, width: usize, max_lines: usize) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .take(max_lines)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let offset = format!("{:0width$x}", start_offset + i * 16);
+            let hex: String = chunk
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if (0x20..0x7f).contains(&b) {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{offset}  {hex:<47}  {ascii}")
+        })
+        .collect()
+}
+
+fn compact(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Number of hex digits needed for the largest line offset: at least 4, and
+/// grown in steps of 2 (6, 8, …) so every row's columns stay aligned.
+fn offset_width(byte_count: usize) -> usize {
+    let last_offset = byte_count.saturating_sub(1) & !0xf;
+    let digits = (usize::BITS - last_offset.leading_zeros()).div_ceil(4) as usize;
+    digits.max(4).next_multiple_of(2)
+}
+
+/// Inputs longer than this are sampled at an even stride for the histogram.
+const HISTOGRAM_MAX_SAMPLES: usize = 1 << 20;
+
+/// Count occurrences of each byte value. Large inputs are sampled, so counts
+/// are only meaningful relative to each other.
+pub fn byte_histogram(bytes: &[u8]) -> [u32; 256] {
+    let step = bytes.len().div_ceil(HISTOGRAM_MAX_SAMPLES).max(1);
+    let mut counts = [0u32; 256];
+    for &b in bytes.iter().step_by(step) {
+        counts[b as usize] += 1;
+    }
+    counts
+}
+
+/// Shannon entropy of a byte histogram in bits per byte (0–8).
+pub fn entropy(counts: &[u32; 256]) -> f64 {
+    let total: f64 = counts.iter().map(|&c| c as f64).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(result: &'a InterpretResult, label: &str) -> &'a str {
+        &result.items.iter().find(|item| item.label == label).unwrap().value
+    }
+
+    #[test]
+    fn huge_input_gives_bounded_output() {
+        let content = "a".repeat(1 << 20);
+        let result = HexInterpreter.interpret(&content).unwrap();
+        assert_eq!(value(&result, "Bytes"), "1048576");
+        assert_eq!(value(&result, "Chars (UTF-8)"), "1048576");
+
+        let dump = value(&result, "Hex dump");
+        assert!(dump.len() < 64 * 1024, "hex dump is {} bytes", dump.len());
+        assert_eq!(dump.lines().count(), 2 * EDGE_BYTES / 16 + 1);
+        assert!(dump.contains(&format!("… {} bytes omitted …", (1 << 20) - 2 * EDGE_BYTES)));
+
+        let compact_len = value(&result, "Compact hex").len();
+        assert!(compact_len < 4 * EDGE_BYTES + 64, "compact hex is {compact_len} bytes");
+    }
+
+    #[test]
+    fn offset_width_grows_in_pairs() {
+        assert_eq!(offset_width(0), 4);
+        assert_eq!(offset_width(0x10000), 4);
+        assert_eq!(offset_width(0x10001), 6);
+        assert_eq!(offset_width(0x100_0000), 6);
+        assert_eq!(offset_width(0x100_0001), 8);
+    }
+
+    #[test]
+    fn large_input_keeps_columns_aligned() {
+        let content = "ab".repeat(0x8100);
+        let result = HexInterpreter.interpret(&content).unwrap();
+        let rows: Vec<&str> = value(&result, "Hex dump")
+            .lines()
+            .filter(|line| !line.starts_with('…'))
+            .collect();
+        let last = rows.last().unwrap();
+        assert!(last.starts_with("0101f0  "), "{last}");
+        for row in &rows {
+            let (offset, rest) = row.split_at(6);
+            assert!(offset.chars().all(|c| c.is_ascii_hexdigit()), "{row}");
+            assert_eq!(&rest[..2], "  ", "{row}");
+            assert_eq!(&rest[2 + 47..2 + 47 + 2], "  ", "{row}");
+            assert_eq!(&rest[2 + 47 + 2..], "abababababababab", "{row}");
+        }
+    }
+
+    #[test]
+    fn input_at_the_limit_is_dumped_in_full() {
+        let content = "a".repeat(MAX_FULL_BYTES);
+        let result = HexInterpreter.interpret(&content).unwrap();
+        assert_eq!(value(&result, "Hex dump").lines().count(), MAX_FULL_BYTES / 16);
+        assert_eq!(value(&result, "Compact hex").len(), 2 * MAX_FULL_BYTES);
     }
 }
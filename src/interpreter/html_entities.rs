@@ -0,0 +1,161 @@
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Named entities understood when decoding. HTML defines over 2000; these
+/// are the ones that turn up in copied markup.
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", "\u{a0}"),
+    ("copy", "©"),
+    ("reg", "®"),
+    ("trade", "™"),
+    ("hellip", "…"),
+    ("mdash", "—"),
+    ("ndash", "–"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("laquo", "«"),
+    ("raquo", "»"),
+    ("bull", "•"),
+    ("middot", "·"),
+    ("deg", "°"),
+    ("plusmn", "±"),
+    ("times", "×"),
+    ("divide", "÷"),
+    ("micro", "µ"),
+    ("para", "¶"),
+    ("sect", "§"),
+    ("cent", "¢"),
+    ("pound", "£"),
+    ("yen", "¥"),
+    ("euro", "€"),
+    ("larr", "←"),
+    ("rarr", "→"),
+    ("uarr", "↑"),
+    ("darr", "↓"),
+    ("harr", "↔"),
+    ("check", "✓"),
+    ("hearts", "♥"),
+    ("shy", "\u{ad}"),
+    ("zwj", "\u{200d}"),
+    ("zwnj", "\u{200c}"),
+    ("ensp", "\u{2002}"),
+    ("emsp", "\u{2003}"),
+    ("thinsp", "\u{2009}"),
+    ("iexcl", "¡"),
+    ("iquest", "¿"),
+    ("auml", "ä"),
+    ("ouml", "ö"),
+    ("uuml", "ü"),
+    ("Auml", "Ä"),
+    ("Ouml", "Ö"),
+    ("Uuml", "Ü"),
+    ("szlig", "ß"),
+    ("eacute", "é"),
+    ("egrave", "è"),
+    ("agrave", "à"),
+    ("ccedil", "ç"),
+    ("ntilde", "ñ"),
+];
+
+/// Longest entity body considered, e.g. `#x10FFFF` or `thinsp`.
+const MAX_ENTITY_LEN: usize = 10;
+
+pub struct HtmlEntitiesInterpreter;
+
+impl Interpreter for HtmlEntitiesInterpreter {
+    fn name(&self) -> &str {
+        "HTML Entities"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let mut items = Vec::new();
+        let mut actions = Vec::new();
+
+        let (decoded, count) = decode(content);
+        if count > 0 {
+            items.push(InterpretItem::text("Decoded", decoded.clone()));
+            items.push(InterpretItem::text("Entities decoded", count.to_string()));
+            actions.push(CopyAction::new("Decoded", decoded));
+        }
+        // Quotes alone don't count as markup, or every apostrophe in prose
+        // would trigger this; they are still escaped in the output.
+        if content.contains(['<', '>', '&']) {
+            let escaped = escape(content);
+            items.push(InterpretItem::text("Escaped", escaped.clone()));
+            actions.push(CopyAction::new("HTML-escaped", escaped));
+        }
+
+        if items.is_empty() {
+            return None;
+        }
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// Replace named (`&amp;`) and numeric (`&#233;`, `&#xE9;`) entities.
+/// Unknown or malformed ones are left as they are. Returns the text and how
+/// many entities were replaced.
+fn decode(s: &str) -> (String, usize) {
+    let mut out = String::with_capacity(s.len());
+    let mut count = 0;
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let body_end = rest[1..]
+            .char_indices()
+            .take(MAX_ENTITY_LEN + 1)
+            .find(|&(_, c)| c == ';')
+            .map(|(i, _)| i + 1);
+        let decoded = body_end.and_then(|end| decode_entity(&rest[1..end]).map(|d| (d, end)));
+        match decoded {
+            Some((text, end)) => {
+                out.push_str(&text);
+                count += 1;
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, count)
+}
+
+/// The text for an entity body (between `&` and `;`).
+fn decode_entity(body: &str) -> Option<String> {
+    if let Some(num) = body.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code).map(String::from);
+    }
+    NAMED_ENTITIES
+        .iter()
+        .find(|(name, _)| *name == body)
+        .map(|(_, text)| text.to_string())
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,34 @@
+use super::filepath::format_size;
+use super::{InterpretItem, InterpretResult};
+use crate::history::ClipboardImage;
+
+/// Basic facts about an image entry. Images don't go through the text
+/// `Interpreter` trait, so the detail panel calls this directly.
+pub fn interpret_image(image: &ClipboardImage) -> InterpretResult {
+    let (w, h) = (image.width, image.height);
+    let mut items = vec![
+        InterpretItem::text("Dimensions", format!("{w} × {h} px")),
+        InterpretItem::text("Pixels", (w * h).to_string()),
+    ];
+    if w > 0 && h > 0 {
+        let g = gcd(w, h);
+        items.push(InterpretItem::text("Aspect ratio", format!("{}:{}", w / g, h / g)));
+    }
+    match image.rgba() {
+        Ok(rgba) => {
+            items.push(InterpretItem::text("Size (RGBA)", format_size(rgba.len() as u64)));
+            let opaque = rgba.chunks_exact(4).all(|px| px[3] == 255);
+            items.push(InterpretItem::text("Transparency", if opaque { "none" } else { "yes" }));
+        }
+        Err(e) => items.push(InterpretItem::text("Pixels unreadable", e)),
+    }
+    InterpretResult::new(items)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,165 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+pub struct IpAddrInterpreter;
+
+impl Interpreter for IpAddrInterpreter {
+    fn name(&self) -> &str {
+        "IP Address"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        let (addr, prefix) = match trimmed.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix.parse::<u32>().ok()?)),
+            None => (trimmed, None),
+        };
+        let items = match addr.parse::<IpAddr>().ok()? {
+            IpAddr::V4(ip) => {
+                let mut items = v4_items(ip);
+                if let Some(prefix) = prefix {
+                    items.extend(v4_cidr_items(ip, prefix)?);
+                }
+                items
+            }
+            IpAddr::V6(ip) => {
+                let mut items = v6_items(ip);
+                if let Some(prefix) = prefix {
+                    items.extend(v6_cidr_items(ip, prefix)?);
+                }
+                items
+            }
+        };
+        Some(InterpretResult::new(items))
+    }
+}
+
+fn v4_items(ip: Ipv4Addr) -> Vec<InterpretItem> {
+    let n = u32::from(ip);
+    let octets = ip.octets();
+    let mut items = vec![
+        InterpretItem::text("Version", "IPv4"),
+        InterpretItem::text("Integer", n.to_string()),
+        InterpretItem::text("Hex", format!("0x{n:08x}")),
+        InterpretItem::text("Class", v4_class(ip)),
+    ];
+    for (i, octet) in octets.iter().enumerate() {
+        let label = format!("Octet {}", i + 1);
+        items.push(InterpretItem::text(label, format!("{octet} (0x{octet:02x})")));
+    }
+    items
+}
+
+fn v4_class(ip: Ipv4Addr) -> &'static str {
+    let [a, b, ..] = ip.octets();
+    if ip.is_unspecified() {
+        "unspecified"
+    } else if ip.is_loopback() {
+        "loopback"
+    } else if ip.is_private() {
+        "private (RFC 1918)"
+    } else if a == 100 && (64..128).contains(&b) {
+        "shared / carrier-grade NAT (RFC 6598)"
+    } else if ip.is_link_local() {
+        "link-local"
+    } else if ip.is_multicast() {
+        "multicast"
+    } else if ip.is_broadcast() {
+        "broadcast"
+    } else if ip.is_documentation() {
+        "documentation (TEST-NET)"
+    } else {
+        "public"
+    }
+}
+
+fn v4_cidr_items(ip: Ipv4Addr, prefix: u32) -> Option<Vec<InterpretItem>> {
+    if prefix > 32 {
+        return None;
+    }
+    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    let network = u32::from(ip) & mask;
+    let broadcast = network | !mask;
+    let mut items = vec![
+        InterpretItem::text("Netmask", Ipv4Addr::from(mask).to_string()),
+        InterpretItem::text("Network", format!("{}/{prefix}", Ipv4Addr::from(network))),
+    ];
+    // /31 point-to-point links and /32 host routes have no network/broadcast
+    // addresses to set aside (RFC 3021).
+    let (first, last, hosts) = match prefix {
+        32 => (network, network, 1u64),
+        31 => (network, broadcast, 2),
+        _ => {
+            items.push(InterpretItem::text("Broadcast", Ipv4Addr::from(broadcast).to_string()));
+            (network + 1, broadcast - 1, (1u64 << (32 - prefix)) - 2)
+        }
+    };
+    items.push(InterpretItem::text("Usable hosts", hosts.to_string()));
+    items.push(InterpretItem::text("First host", Ipv4Addr::from(first).to_string()));
+    items.push(InterpretItem::text("Last host", Ipv4Addr::from(last).to_string()));
+    Some(items)
+}
+
+fn v6_items(ip: Ipv6Addr) -> Vec<InterpretItem> {
+    let expanded: Vec<String> = ip.segments().iter().map(|s| format!("{s:04x}")).collect();
+    let mut items = vec![
+        InterpretItem::text("Version", "IPv6"),
+        InterpretItem::text("Expanded", expanded.join(":")),
+        InterpretItem::text("Compressed", ip.to_string()),
+        InterpretItem::text("Scope", v6_scope(ip)),
+    ];
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        items.push(InterpretItem::text("IPv4-mapped", v4.to_string()));
+    }
+    items
+}
+
+fn v6_scope(ip: Ipv6Addr) -> String {
+    let first = ip.segments()[0];
+    if ip.is_unspecified() {
+        "unspecified".to_string()
+    } else if ip.is_loopback() {
+        "loopback".to_string()
+    } else if ip.is_multicast() {
+        let scope = match first & 0x000f {
+            0x1 => "interface-local",
+            0x2 => "link-local",
+            0x4 => "admin-local",
+            0x5 => "site-local",
+            0x8 => "organization-local",
+            0xe => "global",
+            _ => "reserved",
+        };
+        format!("multicast ({scope})")
+    } else if first & 0xffc0 == 0xfe80 {
+        "link-local".to_string()
+    } else if first & 0xfe00 == 0xfc00 {
+        "unique local (private)".to_string()
+    } else if first == 0x2001 && ip.segments()[1] == 0x0db8 {
+        "documentation".to_string()
+    } else if ip.to_ipv4_mapped().is_some() {
+        "IPv4-mapped".to_string()
+    } else {
+        "global".to_string()
+    }
+}
+
+fn v6_cidr_items(ip: Ipv6Addr, prefix: u32) -> Option<Vec<InterpretItem>> {
+    if prefix > 128 {
+        return None;
+    }
+    let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+    let network = u128::from(ip) & mask;
+    let last = network | !mask;
+    let count = match 128 - prefix {
+        128 => "2^128".to_string(),
+        bits => (1u128 << bits).to_string(),
+    };
+    Some(vec![
+        InterpretItem::text("Network", format!("{}/{prefix}", Ipv6Addr::from(network))),
+        InterpretItem::text("Addresses", count),
+        InterpretItem::text("First address", Ipv6Addr::from(network).to_string()),
+        InterpretItem::text("Last address", Ipv6Addr::from(last).to_string()),
+    ])
+}
@@ -0,0 +1,65 @@
+use serde_json::Value;
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Pretty-printed output longer than this is cut off in the panel.
+const PREVIEW_CHARS: usize = 4000;
+
+pub struct JsonInterpreter;
+
+impl Interpreter for JsonInterpreter {
+    fn name(&self) -> &str {
+        "JSON"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        // Bare numbers, booleans and null are valid JSON but far more often
+        // just plain values; leave those to other interpreters.
+        if !trimmed.starts_with(['{', '[', '"']) {
+            return None;
+        }
+        let value: Value = serde_json::from_str(trimmed).ok()?;
+
+        let (kind, size) = match &value {
+            Value::Object(map) => ("object", Some(format!("{} keys", map.len()))),
+            Value::Array(items) => ("array", Some(format!("{} elements", items.len()))),
+            Value::String(_) => ("string", None),
+            Value::Number(_) => ("number", None),
+            Value::Bool(_) => ("boolean", None),
+            Value::Null => ("null", None),
+        };
+        let mut items = vec![InterpretItem::text("Type", kind)];
+        if let Some(size) = size {
+            items.push(InterpretItem::text("Size", size));
+        }
+        items.push(InterpretItem::text("Max depth", depth(&value).to_string()));
+        if let Value::Object(map) = &value {
+            let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+            items.push(InterpretItem::text("Keys", keys.join(", ")));
+        }
+
+        let pretty = serde_json::to_string_pretty(&value).ok()?;
+        let preview = if pretty.chars().count() > PREVIEW_CHARS {
+            format!("{}…", pretty.chars().take(PREVIEW_CHARS).collect::<String>())
+        } else {
+            pretty.clone()
+        };
+        items.push(InterpretItem::text("Pretty", preview));
+
+        let minified = serde_json::to_string(&value).ok()?;
+        Some(InterpretResult::new(items).with_actions(vec![
+            CopyAction::new("Pretty", pretty),
+            CopyAction::new("Minified", minified),
+        ]))
+    }
+}
+
+/// Nesting depth: scalars are 0, `[]`/`{}` are 1.
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => 1 + map.values().map(depth).max().unwrap_or(0),
+        Value::Array(items) => 1 + items.iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
@@ -0,0 +1,80 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Local, Utc};
+use serde_json::Value;
+
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+pub struct JwtInterpreter;
+
+impl Interpreter for JwtInterpreter {
+    fn name(&self) -> &str {
+        "JWT"
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        let token = trimmed.strip_prefix("Bearer ").unwrap_or(trimmed);
+        let mut segments = token.split('.');
+        let (header, payload, signature) = (segments.next()?, segments.next()?, segments.next()?);
+        if segments.next().is_some() {
+            return None;
+        }
+        let header = decode_json(header)?;
+        let payload = decode_json(payload)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature.trim_end_matches('=')).ok()?;
+
+        let mut items = vec![InterpretItem::text(
+            "Algorithm",
+            header.get("alg").and_then(Value::as_str).unwrap_or("—"),
+        )];
+        let now = Utc::now().timestamp();
+        for (claim, label) in [("iat", "Issued at"), ("nbf", "Not before"), ("exp", "Expires")] {
+            if let Some(secs) = payload.get(claim).and_then(Value::as_i64) {
+                items.push(InterpretItem::text(label, format_time(secs)));
+            }
+        }
+        let exp = payload.get("exp").and_then(Value::as_i64);
+        let nbf = payload.get("nbf").and_then(Value::as_i64);
+        let status = match (exp, nbf) {
+            (Some(exp), _) if exp <= now => "EXPIRED",
+            (_, Some(nbf)) if nbf > now => "not yet valid",
+            (Some(_), _) => "valid (not expired)",
+            (None, _) => "no expiry (exp missing)",
+        };
+        items.push(InterpretItem::text("Status", status));
+        items.push(InterpretItem::text(
+            "Signature",
+            if signature.is_empty() {
+                "none (unsigned token)".to_string()
+            } else {
+                format!("{} bytes (not verified)", signature.len())
+            },
+        ));
+        items.push(InterpretItem::text("Header", serde_json::to_string_pretty(&header).ok()?));
+        items.push(InterpretItem::text("Payload", serde_json::to_string_pretty(&payload).ok()?));
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// Base64url-decode a segment (padding optional) and parse it as a JSON object.
+fn decode_json(segment: &str) -> Option<Value> {
+    let bytes = URL_SAFE_NO_PAD.decode(segment.trim_end_matches('=')).ok()?;
+    let value: Value = serde_json::from_slice(&bytes).ok()?;
+    value.is_object().then_some(value)
+}
+
+fn format_time(secs: i64) -> String {
+    match DateTime::<Utc>::from_timestamp(secs, 0) {
+        Some(utc) => format!(
+            "{}  ({})",
+            utc.format("%Y-%m-%d %H:%M:%S UTC"),
+            utc.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z")
+        ),
+        None => secs.to_string(),
+    }
+}
@@ -0,0 +1,148 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Average silent reading speed used for the reading-time estimate.
+const WORDS_PER_MINUTE: usize = 200;
+/// Fewer list items than this (and no heading or fence) is just a dash in prose.
+const MIN_LIST_ITEMS: usize = 2;
+
+pub struct MarkdownInterpreter;
+
+impl Interpreter for MarkdownInterpreter {
+    fn name(&self) -> &str {
+        "Markdown"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let doc = scan(content);
+        if doc.headings.is_empty() && doc.code_blocks == 0 && doc.list_items < MIN_LIST_ITEMS {
+            return None;
+        }
+
+        let mut items = Vec::new();
+        if !doc.headings.is_empty() {
+            let outline: Vec<String> = doc
+                .headings
+                .iter()
+                .map(|(level, text)| format!("{}H{level} {text}", "  ".repeat(level - 1)))
+                .collect();
+            items.push(InterpretItem::text("Outline", outline.join("\n")));
+        }
+        items.push(InterpretItem::text("Headings", doc.headings.len().to_string()));
+        items.push(InterpretItem::text("List items", doc.list_items.to_string()));
+        items.push(InterpretItem::text("Links", doc.links.to_string()));
+        items.push(InterpretItem::text("Images", doc.images.to_string()));
+        items.push(InterpretItem::text("Code blocks", doc.code_blocks.to_string()));
+        items.push(InterpretItem::text("Words", doc.words.to_string()));
+        items.push(InterpretItem::text("Reading time", reading_time(doc.words)));
+        Some(InterpretResult::new(items))
+    }
+}
+
+#[derive(Default)]
+struct MarkdownStats {
+    /// (level 1–6, heading text) in document order.
+    headings: Vec<(usize, String)>,
+    list_items: usize,
+    links: usize,
+    images: usize,
+    code_blocks: usize,
+    /// Words of prose, excluding code blocks.
+    words: usize,
+}
+
+/// Line-based scan; text inside code fences only counts towards `code_blocks`.
+fn scan(content: &str) -> MarkdownStats {
+    let mut doc = MarkdownStats::default();
+    let mut fence: Option<&str> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(m)) => {
+                fence = Some(m);
+                continue;
+            }
+            (Some(open), Some(m)) if open == m => {
+                fence = None;
+                doc.code_blocks += 1;
+                continue;
+            }
+            (Some(_), _) => continue,
+            (None, None) => {}
+        }
+
+        if let Some(heading) = atx_heading(trimmed) {
+            doc.headings.push(heading);
+        } else if is_list_item(trimmed) {
+            doc.list_items += 1;
+        }
+        let (links, images) = count_links(line);
+        doc.links += links;
+        doc.images += images;
+        doc.words += line
+            .split_whitespace()
+            .filter(|w| w.chars().any(char::is_alphanumeric))
+            .count();
+    }
+    // An unterminated fence still marks a code block.
+    if fence.is_some() {
+        doc.code_blocks += 1;
+    }
+    doc
+}
+
+/// `## Title` → `(2, "Title")`. The space after the hashes is required, so
+/// `#hashtag` and `#!/bin/sh` don't count.
+fn atx_heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    (!text.is_empty()).then(|| (level, text.to_string()))
+}
+
+fn is_list_item(line: &str) -> bool {
+    if let Some(rest) = line.strip_prefix(['-', '*', '+']) {
+        return rest.starts_with(' ');
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    (1..=9).contains(&digits)
+        && (line[digits..].starts_with(". ") || line[digits..].starts_with(") "))
+}
+
+/// Count inline `[text](target)` links and `![alt](src)` images on a line.
+fn count_links(line: &str) -> (usize, usize) {
+    let (mut links, mut images) = (0, 0);
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let is_image = rest[..open].ends_with('!');
+        let after = &rest[open + 1..];
+        let Some(close) = after.find("](") else {
+            break;
+        };
+        let target = &after[close + 2..];
+        let Some(end) = target.find(')') else {
+            break;
+        };
+        if is_image {
+            images += 1;
+        } else {
+            links += 1;
+        }
+        rest = &target[end + 1..];
+    }
+    (links, images)
+}
+
+fn reading_time(words: usize) -> String {
+    if words < WORDS_PER_MINUTE {
+        "< 1 min".to_string()
+    } else {
+        format!("{} min", words.div_ceil(WORDS_PER_MINUTE))
+    }
+}
@@ -1,14 +1,51 @@
+pub mod ansi;
+pub mod base32;
+pub mod base64;
+pub mod cache;
+pub mod chmod;
 pub mod color;
+pub mod cryptoaddr;
+pub mod csv;
+pub mod data_uri;
+pub mod diff;
+pub mod emoji;
+pub mod encoding;
 pub mod filepath;
+pub mod geo;
+pub mod hash;
 pub mod hex;
+pub mod html_entities;
+pub mod image;
+pub mod ipaddr;
+pub mod json;
+pub mod jwt;
+pub mod markdown;
+pub mod number;
+pub mod numbers_series;
+pub mod otpauth;
+pub mod phone;
+pub mod roman;
+pub mod secret;
+pub mod semver;
+pub mod timestamp;
+pub mod totp;
+pub mod unicode;
+pub mod url;
 pub mod uuid;
+pub mod xml;
+pub mod yaml;
 
 /// A single interpreted field to display.
 pub struct InterpretItem {
     pub label: String,
     pub value: String,
-    /// Optional RGBA color for a swatch preview (used by ColorInterpreter).
+    /// Optional RGBA color for a swatch preview (used by ColorInterpreter and AnsiInterpreter).
     pub color: Option<[u8; 4]>,
+    /// Optional values drawn as a sparkline (used by NumberSeriesInterpreter).
+    pub series: Option<Vec<f64>>,
+    /// Optional RGBA text color per line of `value`; `None` entries use the
+    /// default color (used by DiffInterpreter).
+    pub line_colors: Option<Vec<Option<[u8; 4]>>>,
 }
 
 impl InterpretItem {
@@ -17,6 +54,8 @@ impl InterpretItem {
             label: label.into(),
             value: value.into(),
             color: None,
+            series: None,
+            line_colors: None,
         }
     }
 
@@ -25,6 +64,52 @@ impl InterpretItem {
             label: label.into(),
             value: value.into(),
             color: Some(rgba),
+            series: None,
+            line_colors: None,
+        }
+    }
+
+    pub fn with_series(
+        label: impl Into<String>,
+        value: impl Into<String>,
+        series: Vec<f64>,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            color: None,
+            series: Some(series),
+            line_colors: None,
+        }
+    }
+
+    pub fn with_line_colors(
+        label: impl Into<String>,
+        value: impl Into<String>,
+        line_colors: Vec<Option<[u8; 4]>>,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            color: None,
+            series: None,
+            line_colors: Some(line_colors),
+        }
+    }
+}
+
+/// An alternative representation offered in the "Copy as…" menu.
+pub struct CopyAction {
+    pub label: String,
+    /// Text written to the clipboard when the action is chosen.
+    pub text: String,
+}
+
+impl CopyAction {
+    pub fn new(label: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            text: text.into(),
         }
     }
 }
@@ -32,14 +117,33 @@ impl InterpretItem {
 /// The result of one interpreter run, containing a list of display items.
 pub struct InterpretResult {
     pub items: Vec<InterpretItem>,
+    /// Copy actions shown next to the result; empty for most interpreters.
+    pub actions: Vec<CopyAction>,
+    /// Output was cut short by a limit in `InterpretContext`.
+    pub truncated: bool,
 }
 
 impl InterpretResult {
     pub fn new(items: Vec<InterpretItem>) -> Self {
-        Self { items }
+        Self { items, actions: Vec::new(), truncated: false }
+    }
+
+    pub fn with_actions(mut self, actions: Vec<CopyAction>) -> Self {
+        self.actions = actions;
+        self
     }
 }
 
+/// Display limits passed down from the UI to interpreters that can produce
+/// very large output.
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct InterpretContext {
+    /// Maximum number of dump lines to render; `None` means unlimited.
+    pub max_dump_lines: Option<usize>,
+    /// Render hex digits (e.g. color codes) in upper case.
+    pub hex_uppercase: bool,
+}
+
 /// Trait for clipboard content interpreters.
 /// Returns `None` if the interpreter does not apply to the given content.
 ///
@@ -50,14 +154,64 @@ impl InterpretResult {
 pub trait Interpreter: Send + Sync {
     fn name(&self) -> &str;
     fn interpret(&self, content: &str) -> Option<InterpretResult>;
+
+    /// Like `interpret`, honoring the limits in `ctx`. Only interpreters with
+    /// potentially huge output need to override this.
+    fn interpret_with(&self, content: &str, _ctx: &InterpretContext) -> Option<InterpretResult> {
+        self.interpret(content)
+    }
+
+    /// Output depends on the current time (e.g. a TOTP code), so cached
+    /// results are refreshed periodically instead of kept until the
+    /// selection changes.
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    /// Whether `interpret` would return a result, for places that only list
+    /// the matching interpreters (e.g. the history row tooltip). Interpreters
+    /// with expensive output can answer without producing it.
+    fn applies(&self, content: &str) -> bool {
+        self.interpret(content).is_some()
+    }
 }
 
 /// Returns the ordered list of all active interpreters.
 pub fn get_interpreters() -> Vec<Box<dyn Interpreter>> {
     vec![
         Box::new(hex::HexInterpreter),
+        Box::new(unicode::UnicodeInterpreter),
+        Box::new(emoji::EmojiInterpreter),
+        Box::new(ansi::AnsiInterpreter),
         Box::new(uuid::UuidInterpreter),
+        Box::new(json::JsonInterpreter),
+        Box::new(xml::XmlInterpreter),
+        Box::new(yaml::YamlInterpreter),
+        Box::new(jwt::JwtInterpreter),
+        Box::new(data_uri::DataUriInterpreter),
         Box::new(color::ColorInterpreter),
         Box::new(filepath::FilePathInterpreter),
+        Box::new(semver::SemverInterpreter),
+        Box::new(timestamp::TimestampInterpreter),
+        Box::new(number::NumberInterpreter),
+        Box::new(roman::RomanInterpreter),
+        Box::new(ipaddr::IpAddrInterpreter),
+        Box::new(geo::GeoInterpreter),
+        Box::new(hash::HashInterpreter),
+        Box::new(secret::SecretInterpreter),
+        Box::new(numbers_series::NumberSeriesInterpreter),
+        Box::new(csv::CsvInterpreter),
+        Box::new(diff::DiffInterpreter),
+        Box::new(markdown::MarkdownInterpreter),
+        Box::new(html_entities::HtmlEntitiesInterpreter),
+        Box::new(encoding::EncodingInterpreter),
+        Box::new(base32::Base32Interpreter),
+        Box::new(base64::Base64Interpreter),
+        Box::new(totp::TotpInterpreter),
+        Box::new(otpauth::OtpAuthInterpreter),
+        Box::new(phone::PhoneInterpreter),
+        Box::new(url::UrlInterpreter),
+        Box::new(cryptoaddr::CryptoAddressInterpreter),
+        Box::new(chmod::ChmodInterpreter),
     ]
 }
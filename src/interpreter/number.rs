@@ -0,0 +1,117 @@
+use super::{color, CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+pub struct NumberInterpreter;
+
+impl Interpreter for NumberInterpreter {
+    fn name(&self) -> &str {
+        "Number Bases"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        // `0xff5500` is shown by the Color Code interpreter instead.
+        if color::parse_rgba(trimmed).is_some() {
+            return None;
+        }
+        let value = parse_int(trimmed)?;
+
+        let hex = with_sign(value, format!("0x{:x}", value.unsigned_abs()));
+        let octal = with_sign(value, format!("0o{:o}", value.unsigned_abs()));
+        let bits = group_bits(&format!("{:b}", value.unsigned_abs()));
+        let binary = with_sign(value, format!("0b{bits}"));
+        let items = vec![
+            InterpretItem::text("Decimal", value.to_string()),
+            InterpretItem::text("Hex", &hex),
+            InterpretItem::text("Octal", &octal),
+            InterpretItem::text("Binary", &binary),
+            InterpretItem::text("Two's complement (32-bit)", twos_complement(value, 32)),
+            InterpretItem::text("Two's complement (64-bit)", twos_complement(value, 64)),
+            InterpretItem::text("Fits in", fitting_types(value)),
+        ];
+        let actions = vec![
+            CopyAction::new("Decimal", value.to_string()),
+            CopyAction::new("Hex", hex),
+            CopyAction::new("Octal", octal),
+            CopyAction::new("Binary", binary.replace('_', "")),
+        ];
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// Integer literal in decimal, `0x` hex, `0o` octal or `0b` binary, with an
+/// optional leading `-` and `_` digit separators.
+fn parse_int(s: &str) -> Option<i128> {
+    let (negative, body) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let lower = body.to_ascii_lowercase();
+    let (radix, digits) = match lower.get(..2) {
+        Some("0x") => (16, &lower[2..]),
+        Some("0o") => (8, &lower[2..]),
+        Some("0b") => (2, &lower[2..]),
+        _ => (10, lower.as_str()),
+    };
+    let digits = digits.replace('_', "");
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return None;
+    }
+    let magnitude = i128::from_str_radix(&digits, radix).ok()?;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+fn with_sign(value: i128, magnitude: String) -> String {
+    if value < 0 {
+        format!("-{magnitude}")
+    } else {
+        magnitude
+    }
+}
+
+/// `101101` → `10_1101`, grouped from the least significant bit.
+fn group_bits(bits: &str) -> String {
+    let mut out = String::with_capacity(bits.len() + bits.len() / 4);
+    for (i, c) in bits.chars().enumerate() {
+        if i > 0 && (bits.len() - i).is_multiple_of(4) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Hex bit pattern of `value` in a `width`-bit register, if it fits either
+/// as a signed or an unsigned value.
+fn twos_complement(value: i128, width: u32) -> String {
+    let min = -(1i128 << (width - 1));
+    let max = (1i128 << width) - 1;
+    if value < min || value > max {
+        return "out of range".to_string();
+    }
+    let mask = (1u128 << width) - 1;
+    let digits = (width / 4) as usize;
+    format!("0x{:0digits$x}", (value as u128) & mask)
+}
+
+fn fitting_types(value: i128) -> String {
+    let types: [(&str, i128, i128); 8] = [
+        ("u8", 0, u8::MAX.into()),
+        ("u16", 0, u16::MAX.into()),
+        ("u32", 0, u32::MAX.into()),
+        ("u64", 0, u64::MAX.into()),
+        ("i8", i8::MIN.into(), i8::MAX.into()),
+        ("i16", i16::MIN.into(), i16::MAX.into()),
+        ("i32", i32::MIN.into(), i32::MAX.into()),
+        ("i64", i64::MIN.into(), i64::MAX.into()),
+    ];
+    let fits: Vec<&str> = types
+        .iter()
+        .filter(|(_, min, max)| (*min..=*max).contains(&value))
+        .map(|(name, _, _)| *name)
+        .collect();
+    if fits.is_empty() {
+        "none (needs 128 bits)".to_string()
+    } else {
+        fits.join(", ")
+    }
+}
@@ -0,0 +1,86 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Fewer values than this is a number or a pair, not a series.
+const MIN_VALUES: usize = 3;
+/// Share of tokens that must parse as numbers (headers/blank cells are tolerated).
+const MIN_NUMERIC_RATIO: f64 = 0.8;
+
+pub struct NumberSeriesInterpreter;
+
+impl Interpreter for NumberSeriesInterpreter {
+    fn name(&self) -> &str {
+        "Number Series"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let values = parse_series(content)?;
+        let stats = SeriesStats::compute(&values);
+
+        Some(InterpretResult::new(vec![
+            InterpretItem::with_series("Sparkline", format!("{} values", stats.count), values),
+            InterpretItem::text("Count", stats.count.to_string()),
+            InterpretItem::text("Min", format_number(stats.min)),
+            InterpretItem::text("Max", format_number(stats.max)),
+            InterpretItem::text("Mean", format_number(stats.mean)),
+            InterpretItem::text("Median", format_number(stats.median)),
+            InterpretItem::text("Sum", format_number(stats.sum)),
+        ]))
+    }
+}
+
+/// Extract a numeric series: one value per line, or a single comma-separated line.
+/// Returns `None` unless the content is mostly numbers.
+fn parse_series(content: &str) -> Option<Vec<f64>> {
+    let trimmed = content.trim();
+    let tokens: Vec<&str> = if trimmed.contains('\n') {
+        trimmed.lines().map(str::trim).filter(|l| !l.is_empty()).collect()
+    } else {
+        trimmed.split(',').map(str::trim).filter(|t| !t.is_empty()).collect()
+    };
+
+    let values: Vec<f64> = tokens
+        .iter()
+        .filter_map(|t| t.parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .collect();
+    if values.len() < MIN_VALUES || (values.len() as f64) < tokens.len() as f64 * MIN_NUMERIC_RATIO {
+        return None;
+    }
+    Some(values)
+}
+
+pub struct SeriesStats {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub sum: f64,
+}
+
+impl SeriesStats {
+    /// Summary statistics of a non-empty series.
+    pub fn compute(values: &[f64]) -> Self {
+        let count = values.len();
+        let sum: f64 = values.iter().sum();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        // For odd counts both indices point at the same middle element.
+        let median = (sorted[(count - 1) / 2] + sorted[count / 2]) / 2.0;
+
+        Self { count, min, max, mean: sum / count as f64, median, sum }
+    }
+}
+
+/// Print integers without a fractional part and everything else with up to 6 decimals.
+fn format_number(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        format!("{v:.0}")
+    } else {
+        let s = format!("{v:.6}");
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
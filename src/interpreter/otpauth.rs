@@ -0,0 +1,263 @@
+use base64::Engine as _;
+
+use super::totp::{mask_secret, Algorithm};
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+pub struct OtpAuthInterpreter;
+
+impl Interpreter for OtpAuthInterpreter {
+    fn name(&self) -> &str {
+        "OTP Auth URI"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if trimmed.starts_with("otpauth-migration://") {
+            return migration_result(trimmed);
+        }
+        parse_uri(trimmed).map(|uri| uri_result(&uri))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OtpKind {
+    Totp,
+    Hotp,
+}
+
+/// The components of an `otpauth://` key URI.
+pub struct OtpAuthUri {
+    pub kind: OtpKind,
+    pub issuer: Option<String>,
+    pub account: String,
+    /// Upper-cased base32 secret.
+    pub secret: String,
+    pub algorithm: Algorithm,
+    pub digits: u32,
+    pub period: u64,
+    pub counter: Option<u64>,
+}
+
+/// Parse `otpauth://totp/Issuer:account?secret=...&issuer=...`.
+/// Returns `None` for anything that isn't a well-formed key URI with a secret.
+pub fn parse_uri(s: &str) -> Option<OtpAuthUri> {
+    let rest = s.strip_prefix("otpauth://")?;
+    let (kind, rest) = rest.split_once('/')?;
+    let kind = match kind.to_ascii_lowercase().as_str() {
+        "totp" => OtpKind::Totp,
+        "hotp" => OtpKind::Hotp,
+        _ => return None,
+    };
+    let (label, query) = rest.split_once('?')?;
+    let label = percent_decode(label);
+    let (label_issuer, account) = match label.split_once(':') {
+        Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim().to_string()),
+        None => (None, label.trim().to_string()),
+    };
+
+    let mut uri = OtpAuthUri {
+        kind,
+        issuer: label_issuer,
+        account,
+        secret: String::new(),
+        algorithm: Algorithm::Sha1,
+        digits: 6,
+        period: 30,
+        counter: None,
+    };
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value);
+        match key.to_ascii_lowercase().as_str() {
+            "secret" => uri.secret = value.to_ascii_uppercase(),
+            // The query parameter takes precedence over the label prefix.
+            "issuer" => uri.issuer = Some(value),
+            "algorithm" => uri.algorithm = Algorithm::parse(&value)?,
+            "digits" => uri.digits = value.parse().ok().filter(|d| (6..=8).contains(d))?,
+            "period" => uri.period = value.parse().ok().filter(|&p| p > 0)?,
+            "counter" => uri.counter = Some(value.parse().ok()?),
+            _ => {}
+        }
+    }
+
+    if uri.secret.is_empty() {
+        return None;
+    }
+    Some(uri)
+}
+
+fn uri_result(uri: &OtpAuthUri) -> InterpretResult {
+    let mut items = vec![
+        InterpretItem::text(
+            "Type",
+            match uri.kind {
+                OtpKind::Totp => "TOTP (time-based)",
+                OtpKind::Hotp => "HOTP (counter-based)",
+            },
+        ),
+        InterpretItem::text("Issuer", uri.issuer.as_deref().unwrap_or("—")),
+        InterpretItem::text("Account", &uri.account),
+        InterpretItem::text("Secret", mask_secret(&uri.secret)),
+        InterpretItem::text("Algorithm", uri.algorithm.name()),
+        InterpretItem::text("Digits", uri.digits.to_string()),
+    ];
+    match uri.kind {
+        OtpKind::Totp => items.push(InterpretItem::text("Period", format!("{}s", uri.period))),
+        OtpKind::Hotp => items.push(InterpretItem::text(
+            "Counter",
+            uri.counter.map(|c| c.to_string()).unwrap_or_else(|| "—".to_string()),
+        )),
+    }
+    InterpretResult::new(items)
+}
+
+/// Google Authenticator export: `otpauth-migration://offline?data=<base64 protobuf>`.
+fn migration_result(s: &str) -> Option<InterpretResult> {
+    let (_, query) = s.split_once('?')?;
+    let data = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("data="))
+        .map(percent_decode)?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .or_else(|_| base64::engine::general_purpose::URL_SAFE.decode(data.trim()))
+        .ok()?;
+
+    let accounts = parse_migration_payload(&bytes)?;
+    let mut items = vec![
+        InterpretItem::text("Type", "Authenticator export"),
+        InterpretItem::text("Accounts", accounts.len().to_string()),
+    ];
+    for (i, account) in accounts.iter().enumerate() {
+        let name = match &account.issuer {
+            Some(issuer) if !issuer.is_empty() => format!("{issuer}: {}", account.name),
+            _ => account.name.clone(),
+        };
+        items.push(InterpretItem::text(format!("Account {}", i + 1), name));
+    }
+    Some(InterpretResult::new(items))
+}
+
+struct MigrationAccount {
+    name: String,
+    issuer: Option<String>,
+}
+
+/// Walk the `MigrationPayload` protobuf just far enough to list the bundled
+/// accounts (field 1: repeated `OtpParameters`; within it 2 = name, 3 = issuer).
+fn parse_migration_payload(bytes: &[u8]) -> Option<Vec<MigrationAccount>> {
+    let mut accounts = Vec::new();
+    for (field, value) in ProtoFields::new(bytes) {
+        if let (1, ProtoValue::Bytes(params)) = (field, value?) {
+            let mut account = MigrationAccount { name: String::new(), issuer: None };
+            for (field, value) in ProtoFields::new(params) {
+                match (field, value?) {
+                    (2, ProtoValue::Bytes(b)) => {
+                        account.name = String::from_utf8_lossy(b).into_owned();
+                    }
+                    (3, ProtoValue::Bytes(b)) => {
+                        account.issuer = Some(String::from_utf8_lossy(b).into_owned());
+                    }
+                    _ => {}
+                }
+            }
+            accounts.push(account);
+        }
+    }
+    Some(accounts)
+}
+
+enum ProtoValue<'a> {
+    Varint,
+    Bytes(&'a [u8]),
+    Fixed,
+}
+
+/// Minimal protobuf wire-format reader yielding `(field number, value)`.
+/// A malformed field yields `None` as its value and ends the iteration.
+struct ProtoFields<'a> {
+    buf: &'a [u8],
+    failed: bool,
+}
+
+impl<'a> ProtoFields<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, failed: false }
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self.buf.split_first()?;
+            self.buf = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.buf.len() < n {
+            return None;
+        }
+        let (head, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Some(head)
+    }
+
+    fn field(&mut self) -> Option<(u64, ProtoValue<'a>)> {
+        let key = self.varint()?;
+        let value = match key & 0x7 {
+            0 => self.varint().map(|_| ProtoValue::Varint)?,
+            1 => self.take(8).map(|_| ProtoValue::Fixed)?,
+            2 => {
+                let len = usize::try_from(self.varint()?).ok()?;
+                ProtoValue::Bytes(self.take(len)?)
+            }
+            5 => self.take(4).map(|_| ProtoValue::Fixed)?,
+            _ => return None,
+        };
+        Some((key >> 3, value))
+    }
+}
+
+impl<'a> Iterator for ProtoFields<'a> {
+    type Item = (u64, Option<ProtoValue<'a>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.buf.is_empty() {
+            return None;
+        }
+        match self.field() {
+            Some((field, value)) => Some((field, Some(value))),
+            None => {
+                self.failed = true;
+                Some((0, None))
+            }
+        }
+    }
+}
+
+/// Decode `%XX` escapes in a URI component. `+` is left alone: key URIs
+/// encode spaces as `%20`, and raw base64 in `data=` may contain `+`.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hi = (bytes[i + 1] as char).to_digit(16);
+            let lo = (bytes[i + 2] as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hi, lo) {
+                out.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
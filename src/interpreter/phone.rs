@@ -0,0 +1,205 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// ITU-T E.164 caps a full number (country code included) at 15 digits.
+const MAX_E164_DIGITS: usize = 15;
+/// Shorter digit runs are far more likely to be plain numbers than phones.
+const MIN_DIGITS: usize = 7;
+
+/// A trimmed country ruleset: calling code, region name, the expected
+/// national significant number length range (trunk prefix excluded), and the
+/// trunk prefix dialled before it domestically (empty if there is none).
+struct Country {
+    code: &'static str,
+    region: &'static str,
+    min_len: usize,
+    max_len: usize,
+    trunk: &'static str,
+}
+
+const fn country(
+    code: &'static str,
+    region: &'static str,
+    min_len: usize,
+    max_len: usize,
+    trunk: &'static str,
+) -> Country {
+    Country { code, region, min_len, max_len, trunk }
+}
+
+const COUNTRIES: &[Country] = &[
+    country("1", "United States / Canada (NANP)", 10, 10, ""),
+    country("7", "Russia / Kazakhstan", 10, 10, "8"),
+    country("20", "Egypt", 9, 10, "0"),
+    country("27", "South Africa", 9, 9, "0"),
+    country("30", "Greece", 10, 10, ""),
+    country("31", "Netherlands", 9, 9, "0"),
+    country("32", "Belgium", 8, 9, "0"),
+    country("33", "France", 9, 9, "0"),
+    country("34", "Spain", 9, 9, ""),
+    country("39", "Italy", 6, 11, ""),
+    country("41", "Switzerland", 9, 9, "0"),
+    country("43", "Austria", 4, 13, "0"),
+    country("44", "United Kingdom", 9, 10, "0"),
+    country("45", "Denmark", 8, 8, ""),
+    country("46", "Sweden", 7, 13, "0"),
+    country("47", "Norway", 8, 8, ""),
+    country("48", "Poland", 9, 9, ""),
+    country("49", "Germany", 6, 13, "0"),
+    country("52", "Mexico", 10, 10, ""),
+    country("55", "Brazil", 10, 11, "0"),
+    country("61", "Australia", 9, 9, "0"),
+    country("62", "Indonesia", 8, 12, "0"),
+    country("63", "Philippines", 10, 10, "0"),
+    country("64", "New Zealand", 8, 10, "0"),
+    country("65", "Singapore", 8, 8, ""),
+    country("66", "Thailand", 8, 9, "0"),
+    country("81", "Japan", 9, 10, "0"),
+    country("82", "South Korea", 8, 10, "0"),
+    country("84", "Vietnam", 9, 10, "0"),
+    country("86", "China", 10, 11, "0"),
+    country("90", "Turkey", 10, 10, "0"),
+    country("91", "India", 10, 10, "0"),
+    country("351", "Portugal", 9, 9, ""),
+    country("353", "Ireland", 7, 9, "0"),
+    country("358", "Finland", 6, 10, "0"),
+    country("380", "Ukraine", 9, 9, "0"),
+    country("852", "Hong Kong", 8, 8, ""),
+    country("886", "Taiwan", 8, 9, "0"),
+    country("966", "Saudi Arabia", 9, 9, "0"),
+    country("971", "United Arab Emirates", 8, 9, "0"),
+    country("972", "Israel", 8, 9, "0"),
+];
+
+pub struct PhoneInterpreter;
+
+impl Interpreter for PhoneInterpreter {
+    fn name(&self) -> &str {
+        "Phone Number"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let phone = parse_phone(content.trim())?;
+        let items = if phone.international {
+            international_items(&phone)
+        } else {
+            vec![
+                InterpretItem::text("Format", "National (no country code)"),
+                InterpretItem::text("Digits", &phone.digits),
+                InterpretItem::text("Validity", "unknown — add a +country code to validate"),
+            ]
+        };
+        Some(InterpretResult::new(items))
+    }
+}
+
+struct Phone {
+    /// Digits only, without the `+` / `00` international prefix.
+    digits: String,
+    international: bool,
+    /// Written as `+` followed by digits only.
+    strict_e164: bool,
+}
+
+/// Accept digits with an optional leading `+` (or `00`) and the usual
+/// separators. Anything else, or a digit count outside what phones use,
+/// yields `None`.
+fn parse_phone(s: &str) -> Option<Phone> {
+    if !s.chars().all(|c| c.is_ascii_digit() || " +-.()/".contains(c)) {
+        return None;
+    }
+    if s.matches('+').count() > 1 || (s.contains('+') && !s.starts_with('+')) {
+        return None;
+    }
+    // A single dot is a decimal number, not a separator.
+    if s.matches('.').count() == 1 || looks_like_date(s) {
+        return None;
+    }
+
+    let strict_e164 = s.starts_with('+') && s[1..].chars().all(|c| c.is_ascii_digit());
+    let (international, body) = if let Some(rest) = s.strip_prefix('+') {
+        (true, rest)
+    } else if let Some(rest) = s.strip_prefix("00") {
+        (true, rest)
+    } else {
+        (false, s)
+    };
+    // "+44 (0)20 …": the bracketed trunk zero is dropped when dialling internationally.
+    let body = if international { body.replacen("(0)", "", 1) } else { body.to_string() };
+    let digits: String = body.chars().filter(char::is_ascii_digit).collect();
+
+    if digits.len() < MIN_DIGITS || digits.len() > MAX_E164_DIGITS {
+        return None;
+    }
+    if !international {
+        // Bare digit runs are usually just numbers; national phone numbers
+        // are written with a trunk `0` or with separators.
+        let has_separators = s.chars().any(|c| " -()/.".contains(c));
+        if !has_separators && !s.starts_with('0') {
+            return None;
+        }
+    }
+    Some(Phone { digits, international, strict_e164 })
+}
+
+/// `2024-01-15` and `2024/01/15` have the right digit count but aren't phones.
+fn looks_like_date(s: &str) -> bool {
+    let groups: Vec<&str> = s.split(['-', '/', '.']).collect();
+    matches!(groups.as_slice(), [y, m, d] if y.len() == 4 && m.len() <= 2 && d.len() <= 2)
+}
+
+fn international_items(phone: &Phone) -> Vec<InterpretItem> {
+    let format = if phone.strict_e164 { "E.164" } else { "International" };
+    let mut items = vec![
+        InterpretItem::text("Format", format),
+        InterpretItem::text("E.164", format!("+{}", phone.digits)),
+    ];
+
+    // Calling codes are prefix-free, so at most one entry matches.
+    let Some(country) = COUNTRIES.iter().find(|c| phone.digits.starts_with(c.code)) else {
+        items.push(InterpretItem::text("Country code", "unknown (not in built-in list)"));
+        return items;
+    };
+    let national = &phone.digits[country.code.len()..];
+    items.push(InterpretItem::text("Country code", format!("+{}", country.code)));
+    items.push(InterpretItem::text("Region", country.region));
+    items.push(InterpretItem::text("National number", national));
+    items.push(InterpretItem::text("National format", national_format(country, national)));
+
+    let len = national.len();
+    let validity = if (country.min_len..=country.max_len).contains(&len) {
+        format!("likely valid for {}", country.region)
+    } else {
+        let expected = if country.min_len == country.max_len {
+            country.min_len.to_string()
+        } else {
+            format!("{}–{}", country.min_len, country.max_len)
+        };
+        format!("length unexpected (expected {expected} digits, got {len})")
+    };
+    items.push(InterpretItem::text("Validity", validity));
+    items
+}
+
+/// How the number is written for domestic dialling: the trunk prefix plus
+/// digit groups. NANP and France have fixed conventions; elsewhere the last
+/// eight digits form two blocks of four with the area code in front, which
+/// matches most of the listed countries closely enough to read.
+fn national_format(country: &Country, national: &str) -> String {
+    let n = national;
+    match (country.code, n.len()) {
+        ("1", 10) => format!("({}) {}-{}", &n[..3], &n[3..6], &n[6..]),
+        ("33", 9) => {
+            let pairs: Vec<&str> = (1..9).step_by(2).map(|i| &n[i..i + 2]).collect();
+            format!("0{} {}", &n[..1], pairs.join(" "))
+        }
+        (_, len) if len > 8 => {
+            let (area, rest) = n.split_at(len - 8);
+            format!("{}{area} {} {}", country.trunk, &rest[..4], &rest[4..])
+        }
+        (_, len) if len > 4 => {
+            let (head, tail) = n.split_at(len - 4);
+            format!("{}{head} {tail}", country.trunk)
+        }
+        _ => format!("{}{n}", country.trunk),
+    }
+}
@@ -0,0 +1,105 @@
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Largest value writable in standard form (no overline for thousands).
+const MAX_ROMAN: u32 = 3999;
+
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+pub struct RomanInterpreter;
+
+impl Interpreter for RomanInterpreter {
+    fn name(&self) -> &str {
+        "Roman Numeral"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let s = content.trim();
+        if let Ok(n) = s.parse::<u32>() {
+            // Leading zeros or `+` aren't how anyone writes a page number.
+            if !(1..=MAX_ROMAN).contains(&n) || s != n.to_string() {
+                return None;
+            }
+            let roman = to_roman(n);
+            return Some(InterpretResult::new(vec![
+                InterpretItem::text("Roman", &roman),
+                InterpretItem::text("Lowercase", roman.to_lowercase()),
+            ]));
+        }
+        let n = parse_roman(s)?;
+        Some(InterpretResult::new(vec![InterpretItem::text("Decimal", n.to_string())]))
+    }
+}
+
+fn to_roman(mut n: u32) -> String {
+    let mut out = String::new();
+    for (value, symbol) in NUMERALS {
+        while n >= value {
+            out.push_str(symbol);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// Value of a numeral in strict standard form, any case. Anything that
+/// doesn't round-trip through `to_roman` (`IIII`, `VV`, `IC`) or exceeds
+/// `MAX_ROMAN` (`MMMM`) is rejected.
+fn parse_roman(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 15 {
+        return None;
+    }
+    let upper = s.to_ascii_uppercase();
+    let mut rest = upper.as_str();
+    let mut total = 0;
+    for (value, symbol) in NUMERALS {
+        while let Some(tail) = rest.strip_prefix(symbol) {
+            total += value;
+            rest = tail;
+        }
+    }
+    (rest.is_empty() && total <= MAX_ROMAN && to_roman(total) == upper).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_round_trip() {
+        for n in 1..=MAX_ROMAN {
+            assert_eq!(parse_roman(&to_roman(n)), Some(n));
+        }
+        assert_eq!(to_roman(1994), "MCMXCIV");
+        assert_eq!(parse_roman("mmxxiv"), Some(2024));
+    }
+
+    #[test]
+    fn non_standard_forms_are_rejected() {
+        for s in ["IIII", "VV", "IC", "MMMM", "", "XIV ", "ⅩⅣ", "abc"] {
+            assert_eq!(parse_roman(s), None, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn decimals_outside_the_range_are_ignored() {
+        assert!(RomanInterpreter.interpret("0").is_none());
+        assert!(RomanInterpreter.interpret("4000").is_none());
+        assert!(RomanInterpreter.interpret("007").is_none());
+        let result = RomanInterpreter.interpret(" 42 ").unwrap();
+        assert_eq!(result.items[0].value, "XLII");
+    }
+}
@@ -0,0 +1,237 @@
+use semver::{Comparator, Op, Version, VersionReq};
+
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+pub struct SemverInterpreter;
+
+impl Interpreter for SemverInterpreter {
+    fn name(&self) -> &str {
+        "SemVer"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if trimmed.is_empty() || trimmed.contains('\n') {
+            return None;
+        }
+
+        let bare = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        if let Ok(version) = Version::parse(bare) {
+            return Some(version_result(&version));
+        }
+
+        parse_requirement(trimmed).and_then(|req| requirement_result(&req))
+    }
+}
+
+fn version_result(v: &Version) -> InterpretResult {
+    let mut items = vec![
+        InterpretItem::text("Kind", "Version"),
+        InterpretItem::text("Major", v.major.to_string()),
+        InterpretItem::text("Minor", v.minor.to_string()),
+        InterpretItem::text("Patch", v.patch.to_string()),
+    ];
+    if !v.pre.is_empty() {
+        items.push(InterpretItem::text("Pre-release", identifiers(v.pre.as_str())));
+    }
+    if !v.build.is_empty() {
+        items.push(InterpretItem::text("Build metadata", identifiers(v.build.as_str())));
+    }
+    let stability = if !v.pre.is_empty() {
+        "yes"
+    } else if v.major == 0 {
+        "no (0.x: initial development, anything may change)"
+    } else {
+        "no (stable release)"
+    };
+    items.push(InterpretItem::text("Is pre-release", stability));
+    InterpretResult::new(items)
+}
+
+/// `rc.1` → `rc.1 (rc, 1)`; a single identifier is shown as is.
+fn identifiers(s: &str) -> String {
+    let parts: Vec<&str> = s.split('.').collect();
+    if parts.len() == 1 {
+        s.to_string()
+    } else {
+        format!("{s} ({})", parts.join(", "))
+    }
+}
+
+/// Parse a version requirement. Only strings carrying an explicit operator or
+/// wildcard are accepted, so that bare numbers like `1.2` aren't claimed.
+/// Ruby's pessimistic operator (`~>`) is translated to the equivalent Cargo range.
+fn parse_requirement(s: &str) -> Option<VersionReq> {
+    if !s.contains(['^', '~', '>', '<', '=', '*', 'x', 'X']) {
+        return None;
+    }
+    let translated: Vec<String> = s
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            match part.strip_prefix("~>") {
+                Some(rest) => translate_pessimistic(rest.trim()).unwrap_or_else(|| part.to_string()),
+                None => part.to_string(),
+            }
+        })
+        .collect();
+    VersionReq::parse(&translated.join(", ")).ok()
+}
+
+/// `~> 3.1` → `>=3.1.0, <4.0.0`; `~> 3.1.2` → `>=3.1.2, <3.2.0`.
+/// `None` when the upper bound would overflow.
+fn translate_pessimistic(v: &str) -> Option<String> {
+    let parts: Vec<u64> = v
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [major] => Some(format!(">={major}.0.0, <{}.0.0", major.checked_add(1)?)),
+        [major, minor] => Some(format!(">={major}.{minor}.0, <{}.0.0", major.checked_add(1)?)),
+        [major, minor, patch] => {
+            Some(format!(">={major}.{minor}.{patch}, <{major}.{}.0", minor.checked_add(1)?))
+        }
+        _ => None,
+    }
+}
+
+/// `None` when a bound overflows, e.g. `^18446744073709551615`.
+fn requirement_result(req: &VersionReq) -> Option<InterpretResult> {
+    let explicit: Vec<String> =
+        req.comparators.iter().map(explicit_range).collect::<Option<_>>()?;
+
+    let mut items = vec![
+        InterpretItem::text("Kind", "Requirement"),
+        InterpretItem::text("Normalized", req.to_string()),
+        InterpretItem::text(
+            "Explicit range",
+            if explicit.is_empty() { "any version".to_string() } else { explicit.join(", ") },
+        ),
+    ];
+
+    let samples = sample_versions(req);
+    let (matching, rejected): (Vec<&Version>, Vec<&Version>) =
+        samples.iter().partition(|v| req.matches(v));
+    items.push(InterpretItem::text("Satisfied by", join_versions(&matching)));
+    items.push(InterpretItem::text("Not satisfied by", join_versions(&rejected)));
+
+    Some(InterpretResult::new(items))
+}
+
+fn join_versions(versions: &[&Version]) -> String {
+    if versions.is_empty() {
+        "—".to_string()
+    } else {
+        versions.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Expand a comparator into plain `>=`/`<` bounds, following Cargo's rules.
+/// `None` when the upper bound would overflow.
+fn explicit_range(c: &Comparator) -> Option<String> {
+    let (major, minor, patch) = (c.major, c.minor, c.patch);
+    let lower = format!(
+        "{major}.{}.{}{}",
+        minor.unwrap_or(0),
+        patch.unwrap_or(0),
+        if c.pre.is_empty() { String::new() } else { format!("-{}", c.pre) }
+    );
+    let next = |n: u64| n.checked_add(1);
+    let range = match c.op {
+        Op::Exact => match (minor, patch) {
+            (Some(_), Some(_)) => format!("={lower}"),
+            (Some(mi), None) => format!(">={lower}, <{major}.{}.0", next(mi)?),
+            _ => format!(">={lower}, <{}.0.0", next(major)?),
+        },
+        Op::Greater => match (minor, patch) {
+            (Some(_), Some(_)) => format!(">{lower}"),
+            (Some(mi), None) => format!(">={major}.{}.0", next(mi)?),
+            _ => format!(">={}.0.0", next(major)?),
+        },
+        Op::GreaterEq => format!(">={lower}"),
+        Op::Less => format!("<{lower}"),
+        Op::LessEq => match (minor, patch) {
+            (Some(_), Some(_)) => format!("<={lower}"),
+            (Some(mi), None) => format!("<{major}.{}.0", next(mi)?),
+            _ => format!("<{}.0.0", next(major)?),
+        },
+        Op::Tilde => match minor {
+            Some(mi) => format!(">={lower}, <{major}.{}.0", next(mi)?),
+            None => format!(">={lower}, <{}.0.0", next(major)?),
+        },
+        Op::Caret => match (major, minor, patch) {
+            (0, Some(0), Some(pa)) => format!(">={lower}, <0.0.{}", next(pa)?),
+            (0, Some(mi), _) => format!(">={lower}, <0.{}.0", next(mi)?),
+            _ => format!(">={lower}, <{}.0.0", next(major)?),
+        },
+        Op::Wildcard => match minor {
+            Some(mi) => format!(">={lower}, <{major}.{}.0", next(mi)?),
+            None => format!(">={lower}, <{}.0.0", next(major)?),
+        },
+        _ => c.to_string(),
+    };
+    Some(range)
+}
+
+/// Pick versions around each comparator's boundaries so the satisfied /
+/// not-satisfied lists illustrate where the range starts and ends.
+fn sample_versions(req: &VersionReq) -> Vec<Version> {
+    let mut samples: Vec<Version> = Vec::new();
+    for c in &req.comparators {
+        let (major, minor, patch) = (c.major, c.minor.unwrap_or(0), c.patch.unwrap_or(0));
+        samples.push(Version::new(major, minor, patch));
+        // Bounds at u64::MAX have nothing above them.
+        samples.extend(patch.checked_add(1).map(|pa| Version::new(major, minor, pa)));
+        samples.extend(minor.checked_add(1).map(|mi| Version::new(major, mi, 0)));
+        samples.extend(major.checked_add(1).map(|ma| Version::new(ma, 0, 0)));
+        if patch > 0 {
+            samples.push(Version::new(major, minor, patch - 1));
+        } else if minor > 0 {
+            samples.push(Version::new(major, minor - 1, 0));
+        } else if major > 0 {
+            samples.push(Version::new(major - 1, 0, 0));
+        }
+    }
+    if samples.is_empty() {
+        samples.push(Version::new(0, 1, 0));
+        samples.push(Version::new(1, 0, 0));
+    }
+    samples.sort();
+    samples.dedup();
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explicit(content: &str) -> Option<String> {
+        let result = SemverInterpreter.interpret(content)?;
+        let item = result.items.iter().find(|item| item.label == "Explicit range")?;
+        Some(item.value.clone())
+    }
+
+    #[test]
+    fn ranges_are_expanded() {
+        assert_eq!(explicit("^1.2").as_deref(), Some(">=1.2.0, <2.0.0"));
+        assert_eq!(explicit("~1.2.3").as_deref(), Some(">=1.2.3, <1.3.0"));
+        assert_eq!(explicit("~> 3.1").as_deref(), Some(">=3.1.0, <4.0.0"));
+    }
+
+    #[test]
+    fn overflowing_bounds_are_rejected() {
+        let max = u64::MAX;
+        assert!(SemverInterpreter.interpret(&format!("^{max}")).is_none());
+        assert!(SemverInterpreter.interpret(&format!("~1.{max}")).is_none());
+        assert!(SemverInterpreter.interpret(&format!("<=1.{max}")).is_none());
+        assert!(translate_pessimistic(&max.to_string()).is_none());
+        assert!(translate_pessimistic(&format!("1.{max}.0")).is_none());
+    }
+
+    #[test]
+    fn max_exact_bound_still_works() {
+        let max = u64::MAX;
+        let content = format!(">={max}.{max}.{max}");
+        assert_eq!(explicit(&content), Some(content.clone()));
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Datelike as _, Local, Utc};
+
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Readings outside these years are treated as implausible for that unit.
+const MIN_YEAR: i32 = 1900;
+const MAX_YEAR: i32 = 2200;
+
+/// Resolution name and how many of its units make one second.
+const RESOLUTIONS: [(&str, i64); 4] = [
+    ("Seconds", 1),
+    ("Milliseconds", 1_000),
+    ("Microseconds", 1_000_000),
+    ("Nanoseconds", 1_000_000_000),
+];
+
+pub struct TimestampInterpreter;
+
+impl Interpreter for TimestampInterpreter {
+    fn name(&self) -> &str {
+        "Unix Timestamp"
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        let digits = trimmed.strip_prefix('-').unwrap_or(trimmed);
+        if !(8..=19).contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let value: i64 = trimmed.parse().ok()?;
+        let now = Utc::now();
+
+        // Every unit that lands on a plausible date is shown; the user knows
+        // better than a heuristic which one the log meant.
+        let mut items = Vec::new();
+        for (unit, per_second) in RESOLUTIONS {
+            let secs = value.div_euclid(per_second);
+            let nanos = value.rem_euclid(per_second) * (1_000_000_000 / per_second);
+            let Some(utc) = DateTime::<Utc>::from_timestamp(secs, nanos as u32) else {
+                continue;
+            };
+            if !(MIN_YEAR..=MAX_YEAR).contains(&utc.year()) {
+                continue;
+            }
+            let local = utc.with_timezone(&Local);
+            items.push(InterpretItem::text(
+                format!("{unit} (UTC)"),
+                format!("{}  ({})", utc.format("%Y-%m-%d %H:%M:%S%.f UTC"), relative(utc, now)),
+            ));
+            items.push(InterpretItem::text(
+                format!("{unit} (local)"),
+                local.format("%Y-%m-%d %H:%M:%S%.f %:z").to_string(),
+            ));
+        }
+        if items.is_empty() {
+            return None;
+        }
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// "3 hours ago" / "in 2 days", using the largest whole unit.
+fn relative(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = now.signed_duration_since(then).num_seconds();
+    let abs = delta.unsigned_abs();
+    if abs < 10 {
+        return "just now".to_string();
+    }
+    let (n, unit) = [
+        (365 * 24 * 3600, "year"),
+        (30 * 24 * 3600, "month"),
+        (24 * 3600, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ]
+    .into_iter()
+    .find(|&(size, _)| abs >= size)
+    .map(|(size, unit)| (abs / size, unit))
+    .unwrap_or((abs, "second"));
+    let plural = if n == 1 { "" } else { "s" };
+    if delta > 0 {
+        format!("{n} {unit}{plural} ago")
+    } else {
+        format!("in {n} {unit}{plural}")
+    }
+}
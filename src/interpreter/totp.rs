@@ -0,0 +1,180 @@
+use std::time::SystemTime;
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+
+use super::otpauth::{self, OtpKind};
+use super::{base32, CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Shortest secret accepted from plain base32 content (80 bits, as issued by
+/// most authenticator setups). Anything shorter is more likely ordinary text.
+const MIN_SECRET_BYTES: usize = 10;
+
+pub struct TotpInterpreter;
+
+impl Interpreter for TotpInterpreter {
+    fn name(&self) -> &str {
+        "TOTP"
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        let params = if trimmed.starts_with("otpauth://") {
+            TotpParams::from_uri(trimmed)?
+        } else {
+            let secret: String = trimmed.split_whitespace().collect();
+            TotpParams::from_secret(&secret)?
+        };
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let counter = now / params.period;
+        let remaining = params.period - now % params.period;
+        let code = hotp(&params, counter)?;
+        let next = hotp(&params, counter + 1)?;
+
+        Some(
+            InterpretResult::new(vec![
+                InterpretItem::text("Code", code.clone()),
+                InterpretItem::text("Expires in", format!("{remaining}s")),
+                InterpretItem::text("Next code", next),
+                InterpretItem::text("Secret", mask_secret(&params.secret_b32)),
+                InterpretItem::text("Algorithm", params.algorithm.name()),
+                InterpretItem::text("Digits", params.digits.to_string()),
+                InterpretItem::text("Period", format!("{}s", params.period)),
+            ])
+            .with_actions(vec![CopyAction::new("Code", code)]),
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Algorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "SHA1" => Some(Self::Sha1),
+            "SHA256" => Some(Self::Sha256),
+            "SHA512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+            Self::Sha512 => "SHA512",
+        }
+    }
+}
+
+struct TotpParams {
+    secret_b32: String,
+    key: Vec<u8>,
+    algorithm: Algorithm,
+    digits: u32,
+    period: u64,
+}
+
+impl TotpParams {
+    /// Standard parameters (SHA1, 6 digits, 30s) for a bare base32 secret.
+    fn from_secret(secret: &str) -> Option<Self> {
+        let key = base32::decode(secret)?;
+        if key.len() < MIN_SECRET_BYTES {
+            return None;
+        }
+        Some(Self {
+            secret_b32: secret.to_ascii_uppercase(),
+            key,
+            algorithm: Algorithm::Sha1,
+            digits: 6,
+            period: 30,
+        })
+    }
+
+    /// Parameters from an `otpauth://totp/...?secret=...` URI.
+    fn from_uri(s: &str) -> Option<Self> {
+        let uri = otpauth::parse_uri(s)?;
+        if uri.kind != OtpKind::Totp {
+            return None;
+        }
+        let key = base32::decode(&uri.secret)?;
+        Some(Self {
+            secret_b32: uri.secret,
+            key,
+            algorithm: uri.algorithm,
+            digits: uri.digits,
+            period: uri.period,
+        })
+    }
+}
+
+/// RFC 4226 HOTP value for `counter`, zero-padded to `params.digits`.
+fn hotp(params: &TotpParams, counter: u64) -> Option<String> {
+    let msg = counter.to_be_bytes();
+    let digest = match params.algorithm {
+        Algorithm::Sha1 => hmac_digest::<Hmac<sha1::Sha1>>(&params.key, &msg)?,
+        Algorithm::Sha256 => hmac_digest::<Hmac<sha2::Sha256>>(&params.key, &msg)?,
+        Algorithm::Sha512 => hmac_digest::<Hmac<sha2::Sha512>>(&params.key, &msg)?,
+    };
+
+    // Dynamic truncation: the low nibble of the last byte selects a 4-byte window.
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes(digest[offset..offset + 4].try_into().ok()?) & 0x7fff_ffff;
+    let code = binary % 10u32.pow(params.digits);
+    Some(format!("{:0width$}", code, width = params.digits as usize))
+}
+
+fn hmac_digest<M: Mac + KeyInit>(key: &[u8], msg: &[u8]) -> Option<Vec<u8>> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).ok()?;
+    mac.update(msg);
+    Some(mac.finalize().into_bytes().to_vec())
+}
+
+/// Show only the last 4 characters of a secret, enough to tell secrets
+/// apart; short ones are hidden entirely.
+pub fn mask_secret(secret: &str) -> String {
+    let n = secret.chars().count();
+    if n <= 8 {
+        return "•".repeat(n);
+    }
+    let tail: String = secret.chars().skip(n - 4).collect();
+    format!("{}{tail}", "•".repeat(n - 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_shows_at_most_the_last_four() {
+        assert_eq!(mask_secret("JBSWY3DPEHPK3PXP"), "••••••••••••3PXP");
+        assert_eq!(mask_secret("JBSWY3DPE"), "•••••3DPE");
+        assert_eq!(mask_secret("JBSWY3DP"), "••••••••");
+        assert_eq!(mask_secret(""), "");
+    }
+
+    #[test]
+    fn lower_case_secrets_are_accepted() {
+        let upper = TotpParams::from_secret("JBSWY3DPEHPK3PXP").unwrap();
+        let lower = TotpParams::from_secret("jbswy3dpehpk3pxp").unwrap();
+        assert_eq!(lower.key, upper.key);
+        assert_eq!(lower.secret_b32, "JBSWY3DPEHPK3PXP");
+        assert_eq!(hotp(&lower, 1), hotp(&upper, 1));
+
+        let result = TotpInterpreter.interpret("jbsw y3dp ehpk 3pxp").unwrap();
+        assert!(result.items.iter().any(|item| item.label == "Code" && item.value.len() == 6));
+    }
+}
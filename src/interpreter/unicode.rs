@@ -0,0 +1,110 @@
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{InterpretItem, InterpretResult, Interpreter};
+
+/// Characters listed one per row; the counts always cover the whole content.
+const MAX_LISTED: usize = 64;
+
+/// Zero-width characters that are neither marks nor bidi controls.
+const ZERO_WIDTH: &[char] =
+    &['\u{180E}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+
+/// Explicit directional marks, embeddings, overrides and isolates, the
+/// characters behind "Trojan Source" style reordering.
+const BIDI_CONTROLS: &[char] = &[
+    '\u{061C}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}',
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+pub struct UnicodeInterpreter;
+
+impl Interpreter for UnicodeInterpreter {
+    fn name(&self) -> &str {
+        "Unicode Code Points"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        if content.is_empty() {
+            return None;
+        }
+        let code_points = content.chars().count();
+        let mut items = vec![
+            InterpretItem::text("Graphemes", content.graphemes(true).count().to_string()),
+            InterpretItem::text("Code points", code_points.to_string()),
+            InterpretItem::text("Bytes (UTF-8)", content.len().to_string()),
+        ];
+
+        let mut flagged = [("combining", 0), ("zero-width", 0), ("bidi control", 0)];
+        for f in content.chars().filter_map(flag) {
+            if let Some((_, count)) = flagged.iter_mut().find(|(name, _)| *name == f) {
+                *count += 1;
+            }
+        }
+        let summary: Vec<String> = flagged
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(name, count)| format!("{count} {name}"))
+            .collect();
+        if !summary.is_empty() {
+            items.push(InterpretItem::text("Flagged", summary.join(", ")));
+        }
+
+        for c in content.chars().take(MAX_LISTED) {
+            items.push(InterpretItem::text(format!("U+{:04X}", c as u32), describe(c)));
+        }
+        if code_points > MAX_LISTED {
+            let rest = code_points - MAX_LISTED;
+            items.push(InterpretItem::text("…", format!("{rest} more not listed")));
+        }
+        Some(InterpretResult::new(items))
+    }
+}
+
+/// The character (when it's visible on its own), its name, general category
+/// and flag, e.g. `é  LATIN SMALL LETTER E WITH ACUTE  Ll`.
+fn describe(c: char) -> String {
+    let category = get_general_category(c);
+    let name = match unicode_names2::name(c) {
+        Some(name) => name.to_string(),
+        None if category == GeneralCategory::Control => "<control>".to_string(),
+        None => "<unnamed>".to_string(),
+    };
+    let glyph = match category {
+        // Shown on a dotted circle so the mark has something to attach to.
+        _ if is_mark(category) => format!("◌{c}"),
+        GeneralCategory::Control
+        | GeneralCategory::Format
+        | GeneralCategory::SpaceSeparator
+        | GeneralCategory::LineSeparator
+        | GeneralCategory::ParagraphSeparator => String::new(),
+        _ => c.to_string(),
+    };
+    let mut out = format!("{glyph}  {name}  {}", category.abbreviation());
+    if let Some(flag) = flag(c) {
+        out.push_str(&format!("  ⚠ {flag}"));
+    }
+    out.trim_start().to_string()
+}
+
+/// Why `c` deserves attention when spotting hidden or reordering text.
+fn flag(c: char) -> Option<&'static str> {
+    if BIDI_CONTROLS.contains(&c) {
+        Some("bidi control")
+    } else if ZERO_WIDTH.contains(&c) {
+        Some("zero-width")
+    } else if is_mark(get_general_category(c)) {
+        Some("combining")
+    } else {
+        None
+    }
+}
+
+fn is_mark(category: GeneralCategory) -> bool {
+    matches!(
+        category,
+        GeneralCategory::NonspacingMark
+            | GeneralCategory::SpacingMark
+            | GeneralCategory::EnclosingMark
+    )
+}
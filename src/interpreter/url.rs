@@ -0,0 +1,108 @@
+use url::Url;
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Query parameters added by ad/analytics platforms; `utm_*` is matched by prefix.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "gclsrc", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid",
+    "ttclid", "igshid", "mc_cid", "mc_eid", "_hsenc", "_hsmi", "mkt_tok", "vero_id",
+    "oly_anon_id", "oly_enc_id", "ref_src",
+];
+
+pub struct UrlInterpreter;
+
+impl Interpreter for UrlInterpreter {
+    fn name(&self) -> &str {
+        "URL"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if trimmed.contains(char::is_whitespace) {
+            return None;
+        }
+        // `Url::parse` only accepts absolute URLs. Requiring an authority keeps
+        // things like `a:b` or `C:\dir` from passing as a scheme plus path.
+        let url = Url::parse(trimmed).ok()?;
+        if !url.has_host() {
+            return None;
+        }
+
+        let mut items = vec![
+            InterpretItem::text("Scheme", url.scheme()),
+            InterpretItem::text("Host", url.host_str().unwrap_or("—")),
+        ];
+        if !url.username().is_empty() {
+            items.push(InterpretItem::text("User", decode(url.username())));
+        }
+        match (url.port(), url.port_or_known_default()) {
+            (Some(port), _) => items.push(InterpretItem::text("Port", port.to_string())),
+            (None, Some(port)) => {
+                items.push(InterpretItem::text("Port", format!("{port} (default)")));
+            }
+            (None, None) => {}
+        }
+        items.push(InterpretItem::text("Path", decode(url.path())));
+        // `query_pairs` decodes keys and values and keeps repeated keys in order.
+        for (key, value) in url.query_pairs() {
+            items.push(InterpretItem::text(format!("?{key}"), value));
+        }
+        if let Some(fragment) = url.fragment() {
+            items.push(InterpretItem::text("Fragment", decode(fragment)));
+        }
+
+        let mut actions = Vec::new();
+        if let Some((cleaned, removed)) = strip_tracking(&url) {
+            items.push(InterpretItem::text("Tracking parameters", removed.join(", ")));
+            items.push(InterpretItem::text("Cleaned URL", cleaned.as_str()));
+            actions.push(CopyAction::new("Cleaned URL", cleaned.to_string()));
+        }
+        Some(InterpretResult::new(items).with_actions(actions))
+    }
+}
+
+/// Percent-decode a URL component, keeping it as-is if it isn't valid UTF-8.
+fn decode(component: &str) -> String {
+    percent_encoding::percent_decode_str(component)
+        .decode_utf8()
+        .map_or_else(|_| component.to_string(), |s| s.into_owned())
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// Drop tracking parameters from the query, keeping the remaining pairs
+/// byte-for-byte. Returns the cleaned URL and the removed keys, or `None`
+/// when there was nothing to remove.
+fn strip_tracking(url: &Url) -> Option<(Url, Vec<String>)> {
+    let query = url.query()?;
+    let mut removed = Vec::new();
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split_once('=').map_or(*pair, |(k, _)| k);
+            let decoded: String = url::form_urlencoded::parse(key.as_bytes())
+                .map(|(k, _)| k.into_owned())
+                .collect();
+            if is_tracking_param(&decoded) {
+                removed.push(decoded);
+                false
+            } else {
+                true
+            }
+        })
+        .collect();
+    if removed.is_empty() {
+        return None;
+    }
+
+    let mut cleaned = url.clone();
+    if kept.is_empty() {
+        cleaned.set_query(None);
+    } else {
+        cleaned.set_query(Some(&kept.join("&")));
+    }
+    Some((cleaned, removed))
+}
@@ -0,0 +1,266 @@
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// Pretty-printed output longer than this is cut off in the panel.
+const PREVIEW_CHARS: usize = 4000;
+const INDENT: &str = "  ";
+
+/// Roots that mark an HTML fragment rather than XML when there is no
+/// `<?xml …?>` declaration.
+const HTML_ROOTS: &[&str] = &[
+    "html", "head", "body", "div", "span", "p", "a", "b", "i", "em", "strong", "ul", "ol", "li",
+    "table", "tr", "td", "th", "img", "br", "hr", "h1", "h2", "h3", "h4", "h5", "h6", "form",
+    "input", "button", "script", "style", "link", "meta", "section", "article", "nav", "header",
+    "footer", "main", "pre", "code", "label", "select", "option", "textarea", "iframe",
+];
+
+pub struct XmlInterpreter;
+
+impl Interpreter for XmlInterpreter {
+    fn name(&self) -> &str {
+        "XML"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        if !looks_like_xml(trimmed) {
+            return None;
+        }
+        let doc = match scan(trimmed) {
+            Ok(doc) => doc,
+            Err(e) => {
+                return Some(InterpretResult::new(vec![
+                    InterpretItem::text("Well-formed", "no"),
+                    InterpretItem::text("Error", e),
+                ]));
+            }
+        };
+
+        let mut items = vec![
+            InterpretItem::text("Well-formed", "yes"),
+            InterpretItem::text("Root element", doc.root.as_str()),
+            InterpretItem::text("Elements", doc.elements.to_string()),
+            InterpretItem::text("Max depth", doc.max_depth.to_string()),
+        ];
+        if !doc.namespaces.is_empty() {
+            let namespaces: Vec<String> = doc
+                .namespaces
+                .iter()
+                .map(|(prefix, uri)| match prefix.as_str() {
+                    "" => format!("(default) = {uri}"),
+                    p => format!("{p} = {uri}"),
+                })
+                .collect();
+            items.push(InterpretItem::text("Namespaces", namespaces.join("\n")));
+        }
+        let preview = if doc.pretty.chars().count() > PREVIEW_CHARS {
+            format!("{}…", doc.pretty.chars().take(PREVIEW_CHARS).collect::<String>())
+        } else {
+            doc.pretty.clone()
+        };
+        items.push(InterpretItem::text("Pretty", preview));
+        Some(InterpretResult::new(items).with_actions(vec![CopyAction::new("Pretty", doc.pretty)]))
+    }
+}
+
+/// An `<?xml` declaration, or a root element that isn't a common HTML tag.
+fn looks_like_xml(s: &str) -> bool {
+    if s.starts_with("<?xml") {
+        return true;
+    }
+    if !s.ends_with('>') {
+        return false;
+    }
+    let Some(rest) = s.strip_prefix('<') else {
+        return false;
+    };
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, ':' | '-' | '_' | '.'))
+        .collect();
+    let starts_with_letter = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_');
+    starts_with_letter && !HTML_ROOTS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+struct XmlDoc {
+    root: String,
+    elements: usize,
+    max_depth: usize,
+    /// (prefix, URI) in declaration order; the default namespace has prefix "".
+    namespaces: Vec<(String, String)>,
+    pretty: String,
+}
+
+/// Tokenize, check that tags nest and match, collect stats and re-indent.
+/// Errors carry a `line:column` position.
+fn scan(s: &str) -> Result<XmlDoc, String> {
+    let mut doc = XmlDoc {
+        root: String::new(),
+        elements: 0,
+        max_depth: 0,
+        namespaces: Vec::new(),
+        pretty: String::new(),
+    };
+    // Open elements as (qualified name, byte offset of the start tag).
+    let mut stack: Vec<(String, usize)> = Vec::new();
+    let mut root_closed = false;
+    // The current element has no child elements yet, so its end tag (or text)
+    // stays on the same line.
+    let mut inline = false;
+
+    for token in Tokenizer::from(s) {
+        let token = token.map_err(|e| e.to_string())?;
+        match token {
+            Token::ElementStart { prefix, local, span } => {
+                if root_closed {
+                    return Err(format!("{}: second root element", position(s, span.start())));
+                }
+                let name = qualified(prefix.as_str(), local.as_str());
+                if stack.is_empty() {
+                    doc.root = name.clone();
+                }
+                doc.elements += 1;
+                newline(&mut doc.pretty, stack.len());
+                doc.pretty.push('<');
+                doc.pretty.push_str(&name);
+                stack.push((name, span.start()));
+                doc.max_depth = doc.max_depth.max(stack.len());
+            }
+            Token::Attribute { prefix, local, value, .. } => {
+                let (prefix, local, value) = (prefix.as_str(), local.as_str(), value.as_str());
+                if prefix == "xmlns" {
+                    doc.namespaces.push((local.to_string(), value.to_string()));
+                } else if prefix.is_empty() && local == "xmlns" {
+                    doc.namespaces.push((String::new(), value.to_string()));
+                }
+                let quote = if value.contains('"') { '\'' } else { '"' };
+                let name = qualified(prefix, local);
+                doc.pretty.push_str(&format!(" {name}={quote}{value}{quote}"));
+            }
+            Token::ElementEnd { end, span } => match end {
+                ElementEnd::Open => {
+                    doc.pretty.push('>');
+                    inline = true;
+                }
+                ElementEnd::Empty => {
+                    doc.pretty.push_str("/>");
+                    stack.pop();
+                    root_closed = stack.is_empty();
+                    inline = false;
+                }
+                ElementEnd::Close(prefix, local) => {
+                    let name = qualified(prefix.as_str(), local.as_str());
+                    let at = position(s, span.start());
+                    let (open, _) = stack
+                        .pop()
+                        .ok_or_else(|| format!("{at}: unexpected </{name}>"))?;
+                    if open != name {
+                        return Err(format!("{at}: </{name}> closes <{open}>"));
+                    }
+                    if !inline {
+                        newline(&mut doc.pretty, stack.len());
+                    }
+                    doc.pretty.push_str(&format!("</{name}>"));
+                    root_closed = stack.is_empty();
+                    inline = false;
+                }
+            },
+            Token::Text { text } => {
+                let text = text.as_str().trim();
+                if text.is_empty() {
+                    continue;
+                }
+                if !inline {
+                    newline(&mut doc.pretty, stack.len());
+                }
+                doc.pretty.push_str(text);
+            }
+            Token::Cdata { span, .. } => {
+                if !inline {
+                    newline(&mut doc.pretty, stack.len());
+                }
+                doc.pretty.push_str(span.as_str());
+            }
+            Token::Comment { span, .. }
+            | Token::ProcessingInstruction { span, .. }
+            | Token::Declaration { span, .. }
+            | Token::DtdStart { span, .. }
+            | Token::EmptyDtd { span, .. }
+            | Token::EntityDeclaration { span, .. }
+            | Token::DtdEnd { span } => {
+                newline(&mut doc.pretty, stack.len());
+                doc.pretty.push_str(span.as_str());
+                inline = false;
+            }
+        }
+    }
+    if let Some((open, start)) = stack.pop() {
+        return Err(format!("{}: <{open}> is never closed", position(s, start)));
+    }
+    if doc.root.is_empty() {
+        return Err("no root element".to_string());
+    }
+    Ok(doc)
+}
+
+fn qualified(prefix: &str, local: &str) -> String {
+    if prefix.is_empty() {
+        local.to_string()
+    } else {
+        format!("{prefix}:{local}")
+    }
+}
+
+/// Start a new line at `depth`, except at the very beginning.
+fn newline(out: &mut String, depth: usize) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(&INDENT.repeat(depth));
+}
+
+/// 1-based `line:column` of byte offset `pos`.
+fn position(s: &str, pos: usize) -> String {
+    let before = &s[..pos];
+    let line = before.matches('\n').count() + 1;
+    let col = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    format!("{line}:{col}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(result: &'a InterpretResult, label: &str) -> &'a str {
+        &result.items.iter().find(|item| item.label == label).unwrap().value
+    }
+
+    #[test]
+    fn well_formed_document_stats() {
+        let xml = concat!(
+            r#"<?xml version="1.0"?><soap:Envelope xmlns:soap="urn:s" xmlns="urn:d">"#,
+            "<soap:Body><a>1</a><b/></soap:Body></soap:Envelope>",
+        );
+        let result = XmlInterpreter.interpret(xml).unwrap();
+        assert_eq!(value(&result, "Well-formed"), "yes");
+        assert_eq!(value(&result, "Root element"), "soap:Envelope");
+        assert_eq!(value(&result, "Elements"), "4");
+        assert_eq!(value(&result, "Max depth"), "3");
+        assert_eq!(value(&result, "Namespaces"), "soap = urn:s\n(default) = urn:d");
+    }
+
+    #[test]
+    fn malformed_xml_reports_a_position() {
+        let result = XmlInterpreter.interpret("<config>\n  <a></b>\n</config>").unwrap();
+        assert_eq!(value(&result, "Well-formed"), "no");
+        assert!(value(&result, "Error").starts_with("2:"), "{}", value(&result, "Error"));
+    }
+
+    #[test]
+    fn html_and_prose_are_not_xml() {
+        assert!(XmlInterpreter.interpret("<div>hi</div>").is_none());
+        assert!(XmlInterpreter.interpret("a < b and c > d").is_none());
+        assert!(XmlInterpreter.interpret("<3 you>").is_none());
+    }
+}
@@ -0,0 +1,170 @@
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use super::{CopyAction, InterpretItem, InterpretResult, Interpreter};
+
+/// JSON rendering longer than this is cut off in the panel.
+const PREVIEW_CHARS: usize = 4000;
+/// Nested mappings are summarized this many levels deep.
+const STRUCTURE_DEPTH: usize = 2;
+
+pub struct YamlInterpreter;
+
+impl Interpreter for YamlInterpreter {
+    fn name(&self) -> &str {
+        "YAML"
+    }
+
+    fn interpret(&self, content: &str) -> Option<InterpretResult> {
+        let trimmed = content.trim();
+        // JSON is valid YAML; the JSON interpreter already covers it.
+        let is_json = || serde_json::from_str::<serde_json::Value>(trimmed).is_ok();
+        if trimmed.starts_with(['{', '[']) && is_json() {
+            return None;
+        }
+        let docs = match parse_documents(trimmed) {
+            Ok(docs) => docs,
+            Err(e) if looks_like_yaml(trimmed) => return Some(error_result(trimmed, &e)),
+            Err(_) => return None,
+        };
+        if !docs.iter().any(is_structured) || !trimmed.contains('\n') {
+            return None;
+        }
+
+        let mut items = Vec::new();
+        if docs.len() > 1 {
+            items.push(InterpretItem::text("Documents", docs.len().to_string()));
+        }
+        let kinds: Vec<String> = docs.iter().map(kind).collect();
+        items.push(InterpretItem::text("Kind", kinds.join("\n")));
+        if let [doc] = docs.as_slice() {
+            let mut lines = Vec::new();
+            structure(doc, 0, &mut lines);
+            if !lines.is_empty() {
+                items.push(InterpretItem::text("Structure", lines.join("\n")));
+            }
+        }
+
+        // Multi-document files become a JSON array, one element per document.
+        let json = match docs.as_slice() {
+            [doc] => serde_json::to_value(doc),
+            _ => serde_json::to_value(&docs),
+        };
+        let Ok(json) = json else {
+            items.push(InterpretItem::text("JSON", "not representable (non-string keys)"));
+            return Some(InterpretResult::new(items));
+        };
+        let pretty = serde_json::to_string_pretty(&json).ok()?;
+        let preview = if pretty.chars().count() > PREVIEW_CHARS {
+            format!("{}…", pretty.chars().take(PREVIEW_CHARS).collect::<String>())
+        } else {
+            pretty.clone()
+        };
+        items.push(InterpretItem::text("JSON", preview));
+        let minified = serde_json::to_string(&json).ok()?;
+        Some(InterpretResult::new(items).with_actions(vec![
+            CopyAction::new("JSON", pretty),
+            CopyAction::new("JSON (minified)", minified),
+        ]))
+    }
+}
+
+/// Every `---`-separated document in `s`.
+fn parse_documents(s: &str) -> Result<Vec<Value>, serde_yaml::Error> {
+    serde_yaml::Deserializer::from_str(s).map(Value::deserialize).collect()
+}
+
+/// Whether a parse error is worth reporting: the text starts with a document
+/// marker or its first line is a `key:` entry. Prose with a stray colon
+/// doesn't get an error item.
+fn looks_like_yaml(s: &str) -> bool {
+    if s.starts_with("---") {
+        return true;
+    }
+    let Some(first) = s.lines().map(str::trim_end).find(|l| !l.is_empty() && !l.starts_with('#'))
+    else {
+        return false;
+    };
+    let Some((key, rest)) = first.split_once(':') else {
+        return false;
+    };
+    !key.is_empty()
+        && key.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && (rest.is_empty() || rest.starts_with(' '))
+        && s.contains('\n')
+}
+
+fn error_result(s: &str, e: &serde_yaml::Error) -> InterpretResult {
+    let mut items = vec![InterpretItem::text("Error", e.to_string())];
+    if let Some(loc) = e.location() {
+        if let Some(line) = s.lines().nth(loc.line().saturating_sub(1)) {
+            items.push(InterpretItem::text(format!("Line {}", loc.line()), line));
+        }
+    }
+    InterpretResult::new(items)
+}
+
+/// A mapping, or a sequence containing a mapping or sequence. Lists of plain
+/// scalars are usually bullet points, not YAML.
+fn is_structured(value: &Value) -> bool {
+    match value {
+        Value::Mapping(_) => true,
+        Value::Sequence(items) => {
+            items.iter().any(|v| matches!(v, Value::Mapping(_) | Value::Sequence(_)))
+        }
+        Value::Tagged(tagged) => is_structured(&tagged.value),
+        _ => false,
+    }
+}
+
+/// The document's shape, plus `kind`/`apiVersion`/`metadata.name` for
+/// Kubernetes-style manifests.
+fn kind(value: &Value) -> String {
+    match value {
+        Value::Mapping(map) => {
+            let field = |v: &Value, key: &str| v.get(key).and_then(Value::as_str).map(String::from);
+            let Some(kind) = field(value, "kind") else {
+                return format!("mapping ({} keys)", map.len());
+            };
+            let mut out = kind;
+            if let Some(api) = field(value, "apiVersion") {
+                out.push_str(&format!(" ({api})"));
+            }
+            if let Some(name) = value.get("metadata").and_then(|m| field(m, "name")) {
+                out.push_str(&format!(" {name}"));
+            }
+            out
+        }
+        Value::Sequence(items) => format!("sequence ({} items)", items.len()),
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, kind(&tagged.value)),
+        Value::String(_) => "string".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::Bool(_) => "boolean".to_string(),
+        Value::Null => "null".to_string(),
+    }
+}
+
+/// One line per key, indented by depth, down to `STRUCTURE_DEPTH` levels.
+/// Sequences are summarized by their length and, for sequences of mappings,
+/// the keys of the first element.
+fn structure(value: &Value, depth: usize, lines: &mut Vec<String>) {
+    let Value::Mapping(map) = value else {
+        return;
+    };
+    let indent = "  ".repeat(depth);
+    for (key, child) in map {
+        let key = match key {
+            Value::String(s) => s.clone(),
+            other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+        };
+        match child {
+            Value::Mapping(_) if depth + 1 < STRUCTURE_DEPTH => {
+                lines.push(format!("{indent}{key}:"));
+                structure(child, depth + 1, lines);
+            }
+            Value::Mapping(m) => lines.push(format!("{indent}{key}: {{{} keys}}", m.len())),
+            Value::Sequence(items) => lines.push(format!("{indent}{key}: [{} items]", items.len())),
+            _ => lines.push(format!("{indent}{key}")),
+        }
+    }
+}
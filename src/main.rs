@@ -1,8 +1,16 @@
 mod app;
+mod classify;
+mod cli;
 mod clipboard_backend;
+#[cfg(unix)]
+mod control;
+mod detect;
+mod event;
 mod history;
 mod hotkey;
 mod interpreter;
+mod settings;
+mod watch;
 mod window_state;
 
 fn load_icon() -> egui::IconData {
@@ -19,23 +27,39 @@ fn load_icon() -> egui::IconData {
 }
 
 fn main() -> eframe::Result<()> {
+    // Any arguments select a headless command; none starts the GUI.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(e) = cli::run(&args) {
+            eprintln!("clipboard-hack: {e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let ws_path = window_state::window_state_file_path();
     let ws = window_state::load(&ws_path);
 
-    let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_title("Clipboard Hack")
-            .with_app_id("clipboard-hack")
-            .with_icon(load_icon())
-            .with_inner_size([ws.width, ws.height])
-            .with_position([ws.x, ws.y])
-            .with_min_inner_size([600.0, 400.0]),
-        ..Default::default()
-    };
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_title(app::WINDOW_TITLE)
+        .with_app_id("clipboard-hack")
+        .with_icon(load_icon())
+        .with_inner_size([ws.width, ws.height])
+        .with_min_inner_size([600.0, 400.0])
+        .with_maximized(ws.maximized)
+        .with_fullscreen(ws.fullscreen);
+    // A maximized window is placed by the window manager.
+    if !ws.maximized && !ws.fullscreen {
+        viewport = viewport.with_position([ws.x, ws.y]);
+    }
+    let options = eframe::NativeOptions { viewport, ..Default::default() };
 
     eframe::run_native(
         "Clipboard Hack",
         options,
-        Box::new(|cc| Ok(Box::new(app::App::new(cc)))),
+        Box::new(|cc| {
+            let clipboard = Box::new(clipboard_backend::SystemClipboard::new());
+            Ok(Box::new(app::App::new(cc, clipboard)))
+        }),
     )
 }
@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Smallest and largest zoom factor reachable via the keyboard shortcuts.
+pub const MIN_ZOOM: f32 = 0.5;
+pub const MAX_ZOOM: f32 = 3.0;
+/// Amount added/subtracted per Ctrl+= / Ctrl+- press.
+pub const ZOOM_STEP: f32 = 0.1;
+
+/// Allowed range for the history capacity setting.
+pub const MIN_HISTORY_SIZE: usize = 1;
+pub const MAX_HISTORY_SIZE: usize = 10_000;
+
+/// What double-clicking a history row does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoubleClickAction {
+    Copy,
+    CopyAndHide,
+    OpenInEditor,
+}
+
+impl DoubleClickAction {
+    pub const ALL: [Self; 3] = [Self::Copy, Self::CopyAndHide, Self::OpenInEditor];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Copy => "Copy to clipboard",
+            Self::CopyAndHide => "Copy and hide window",
+            Self::OpenInEditor => "Open in editor",
+        }
+    }
+}
+
+/// What gets written to the trigger result file after a file-triggered capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TriggerResult {
+    /// The captured text itself (or `error: …` on failure).
+    CapturedText,
+    /// A one-line status: `ok <bytes>` or `error: …`.
+    Status,
+}
+
+impl TriggerResult {
+    pub const ALL: [Self; 2] = [Self::CapturedText, Self::Status];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CapturedText => "Captured text",
+            Self::Status => "Status line",
+        }
+    }
+}
+
+/// How entries that look like secrets are written to the history file.
+/// They stay usable in memory for the rest of the session either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecretPolicy {
+    Keep,
+    /// Saved with each secret replaced by a `[REDACTED …]` marker.
+    Redact,
+    /// Not saved at all.
+    Skip,
+}
+
+impl SecretPolicy {
+    pub const ALL: [Self; 3] = [Self::Keep, Self::Redact, Self::Skip];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Keep => "Save as is",
+            Self::Redact => "Save redacted",
+            Self::Skip => "Don't save",
+        }
+    }
+}
+
+/// Which secret detections `SecretPolicy` applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretRules {
+    /// PEM private key blocks.
+    pub private_keys: bool,
+    /// Tokens with a known provider prefix (AWS, GitHub, Slack, …).
+    pub known_tokens: bool,
+    /// A whole entry that is one long high-entropy token. Off by default, as
+    /// it also matches ordinary generated IDs.
+    pub random_strings: bool,
+}
+
+impl Default for SecretRules {
+    fn default() -> Self {
+        Self { private_keys: true, known_tokens: true, random_strings: false }
+    }
+}
+
+/// Light/dark appearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    Dark,
+    Light,
+    /// Follow the OS setting; dark when the platform doesn't report one.
+    FollowSystem,
+}
+
+impl Theme {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::FollowSystem => "System",
+        }
+    }
+
+    /// The next theme in the toolbar toggle's cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Dark => Self::Light,
+            Self::Light => Self::FollowSystem,
+            Self::FollowSystem => Self::Dark,
+        }
+    }
+
+    pub fn preference(self) -> egui::ThemePreference {
+        match self {
+            Self::Dark => egui::ThemePreference::Dark,
+            Self::Light => egui::ThemePreference::Light,
+            Self::FollowSystem => egui::ThemePreference::System,
+        }
+    }
+}
+
+/// User preferences that persist across restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// User zoom on top of the display's native pixels-per-point.
+    pub zoom_factor: f32,
+    /// Hex dump lines rendered before the "show full" control appears.
+    pub hex_dump_max_lines: usize,
+    /// Write hex color codes as `#FF5500` instead of `#ff5500`.
+    pub hex_uppercase: bool,
+    pub double_click_action: DoubleClickAction,
+    /// File written after a trigger-file capture; empty disables write-back.
+    pub trigger_result_path: String,
+    pub trigger_result: TriggerResult,
+    /// Listen on the Unix control socket (takes effect on restart).
+    pub control_socket: bool,
+    /// RGBA accent for selections, matched interpreter headers and buttons;
+    /// `None` keeps egui's default.
+    pub accent_color: Option<[u8; 4]>,
+    /// The first-run welcome panel was dismissed.
+    pub seen_onboarding: bool,
+    /// Interpreter names always shown first in the detail panel.
+    pub pinned_interpreters: Vec<String>,
+    /// Interpreter names hidden from the detail panel. Stored as the disabled
+    /// set so interpreters added in an upgrade start out enabled.
+    pub disabled_interpreters: Vec<String>,
+    /// Interpreter names in the order the detail panel lists them; ones not
+    /// listed follow in the built-in order.
+    pub interpreter_order: Vec<String>,
+    /// Soft-wrap long lines in the detail content view.
+    pub wrap_content: bool,
+    /// Clear history after this many minutes without activity; 0 disables.
+    pub auto_clear_idle_minutes: u32,
+    /// Poll the clipboard and capture every change.
+    pub watch_mode: bool,
+    /// Milliseconds between watch mode clipboard polls.
+    pub watch_interval_ms: u64,
+    /// In-app shortcut toggling watch mode, e.g. `Ctrl+Shift+W`.
+    pub watch_shortcut: String,
+    pub theme: Theme,
+    /// Most entries kept in history; pinned entries may exceed it.
+    pub max_history: usize,
+    /// Re-copying anything already in history moves it to the front instead
+    /// of adding a duplicate; off keeps chronological duplicates.
+    pub dedup_full: bool,
+    /// Treat the history search as a regular expression.
+    pub search_regex: bool,
+    /// Regex search ignores case (the fuzzy search always does).
+    pub search_ignore_case: bool,
+    /// Ask the window manager to flag the window when a capture adds an entry
+    /// while it's in the background.
+    pub attention_on_capture: bool,
+    pub secret_policy: SecretPolicy,
+    pub secret_rules: SecretRules,
+    /// strftime format for capture times in the history and detail panel.
+    pub timestamp_format: String,
+    /// `Local`, `UTC` or an IANA zone name such as `America/New_York`.
+    pub timezone: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            zoom_factor: 1.0,
+            hex_dump_max_lines: 256,
+            hex_uppercase: false,
+            double_click_action: DoubleClickAction::Copy,
+            trigger_result_path: String::new(),
+            trigger_result: TriggerResult::CapturedText,
+            control_socket: false,
+            accent_color: None,
+            seen_onboarding: false,
+            pinned_interpreters: Vec::new(),
+            disabled_interpreters: Vec::new(),
+            interpreter_order: Vec::new(),
+            wrap_content: false,
+            auto_clear_idle_minutes: 0,
+            watch_mode: false,
+            watch_interval_ms: 500,
+            watch_shortcut: "Ctrl+Shift+W".to_string(),
+            theme: Theme::Dark,
+            max_history: 50,
+            dedup_full: true,
+            search_regex: false,
+            search_ignore_case: true,
+            attention_on_capture: true,
+            secret_policy: SecretPolicy::Keep,
+            secret_rules: SecretRules::default(),
+            timestamp_format: crate::history::DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            timezone: "Local".to_string(),
+        }
+    }
+}
+
+/// Returns the path where settings are persisted.
+/// Linux/others: $XDG_DATA_HOME/clipboard-hack/settings.json
+/// macOS:        ~/Library/Application Support/clipboard-hack/settings.json
+pub fn settings_file_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        let home = std::env::var("HOME").unwrap_or_default();
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("clipboard-hack")
+            .join("settings.json")
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let base = std::env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_default();
+                PathBuf::from(home).join(".local").join("share")
+            });
+        base.join("clipboard-hack").join("settings.json")
+    }
+}
+
+/// Load settings from a JSON file. Returns Settings::default() on any error.
+pub fn load(path: &Path) -> Settings {
+    let Ok(json) = std::fs::read_to_string(path) else {
+        return Settings::default();
+    };
+    let Ok(settings) = serde_json::from_str::<Settings>(&json) else {
+        eprintln!("[settings] Failed to parse {}", path.display());
+        return Settings::default();
+    };
+    settings
+}
+
+/// Persist settings to a JSON file, creating parent directories as needed.
+pub fn save(settings: &Settings, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
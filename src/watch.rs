@@ -0,0 +1,119 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::clipboard_backend::{ClipboardAccess, SystemClipboard};
+use crate::event::AppEvent;
+use crate::history::content_hash;
+
+/// Controls for the background clipboard watcher, shared with the UI thread.
+pub struct WatchHandle {
+    enabled: AtomicBool,
+    interval_ms: AtomicU64,
+    /// Hash of text the app wrote itself; the watcher skips it once.
+    ignore_hash: Mutex<Option<u64>>,
+}
+
+impl WatchHandle {
+    /// Controls with no watcher thread behind them yet.
+    pub fn new(enabled: bool, interval_ms: u64) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            interval_ms: AtomicU64::new(interval_ms),
+            ignore_hash: Mutex::new(None),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn set_interval_ms(&self, ms: u64) {
+        self.interval_ms.store(ms, Ordering::Relaxed);
+    }
+
+    /// Mark `text` as written by the app so it isn't captured as a change.
+    pub fn ignore(&self, text: &str) {
+        *self.ignore_hash.lock().unwrap() = Some(content_hash(text));
+    }
+
+    /// Whether a poll that read text hashing to `hash` is a change to report,
+    /// given the previous poll's `last_hash`, which is updated. A pending
+    /// `ignore` is used up by this poll even when the text didn't change
+    /// (the app wrote what was already there), so it can't linger and hide a
+    /// later copy of the same text.
+    fn is_change(&self, hash: u64, last_hash: &mut Option<u64>) -> bool {
+        let ours = self.ignore_hash.lock().unwrap().take() == Some(hash);
+        let changed = last_hash.replace(hash).is_some_and(|prev| prev != hash);
+        changed && !ours
+    }
+}
+
+/// Spawns a thread that polls the clipboard every `interval_ms` while enabled
+/// and sends `AppEvent::ClipboardChanged` when the text differs from the last
+/// poll. The first read after enabling only records the current value.
+/// A repaint is requested when a change is sent, so the new entry shows up
+/// right away instead of on the UI's next periodic repaint.
+pub fn start_clipboard_watcher(
+    tx: Sender<AppEvent>,
+    ctx: egui::Context,
+    enabled: bool,
+    interval_ms: u64,
+) -> Arc<WatchHandle> {
+    let handle = Arc::new(WatchHandle::new(enabled, interval_ms));
+    let shared = Arc::clone(&handle);
+    std::thread::spawn(move || {
+        let mut clipboard = SystemClipboard::new();
+        let mut last_hash: Option<u64> = None;
+        loop {
+            let interval = shared.interval_ms.load(Ordering::Relaxed).max(100);
+            std::thread::sleep(Duration::from_millis(interval));
+            if !shared.enabled.load(Ordering::Relaxed) {
+                last_hash = None;
+                continue;
+            }
+            // Read failures (e.g. empty clipboard) are expected while polling; stay quiet.
+            let Ok((text, backend)) = clipboard.get_text() else {
+                continue;
+            };
+            if !shared.is_change(content_hash(&text), &mut last_hash) {
+                continue;
+            }
+            if tx.send(AppEvent::ClipboardChanged(text, backend)).is_err() {
+                break;
+            }
+            ctx.request_repaint();
+        }
+    });
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_write_is_skipped_once() {
+        let handle = WatchHandle::new(true, 500);
+        let mut last = None;
+        assert!(!handle.is_change(content_hash("a"), &mut last));
+        handle.ignore("b");
+        assert!(!handle.is_change(content_hash("b"), &mut last));
+        assert!(handle.is_change(content_hash("c"), &mut last));
+        assert!(handle.is_change(content_hash("b"), &mut last));
+    }
+
+    #[test]
+    fn ignore_of_unchanged_text_does_not_linger() {
+        let handle = WatchHandle::new(true, 500);
+        let mut last = None;
+        handle.is_change(content_hash("a"), &mut last);
+        // The app copies what's already on the clipboard.
+        handle.ignore("a");
+        assert!(!handle.is_change(content_hash("a"), &mut last));
+        assert!(handle.is_change(content_hash("b"), &mut last));
+        // The user copies "a" again later: that's a real change.
+        assert!(handle.is_change(content_hash("a"), &mut last));
+    }
+}
@@ -2,17 +2,39 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WindowState {
     pub x: f32,
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    /// The geometry above is the normal (restored) one; these say how the
+    /// window was shown on top of it. Missing in files from older versions.
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// Hash of the selected history entry, so the selection survives new
+    /// captures shifting the indices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_hash: Option<u64>,
+    /// Vertical scroll offset of the history list, in points.
+    #[serde(default)]
+    pub history_scroll: f32,
 }
 
 impl Default for WindowState {
     fn default() -> Self {
-        Self { x: 100.0, y: 100.0, width: 900.0, height: 600.0 }
+        Self {
+            x: 100.0,
+            y: 100.0,
+            width: 900.0,
+            height: 600.0,
+            maximized: false,
+            fullscreen: false,
+            selected_hash: None,
+            history_scroll: 0.0,
+        }
     }
 }
 